@@ -2,13 +2,14 @@ use futures_lite::stream::StreamExt;
 use oxidized_mdf::MdfDatabase;
 use prettytable::{Cell, Row, Table};
 use std::path::PathBuf;
+use std::str::FromStr;
 use structopt::StructOpt;
 
 async fn print_rows(db: &mut MdfDatabase, table: &str, row_limit: &Option<usize>) {
-    let mut rows = match db.rows(&table) {
-        Some(rows) => rows,
-        None => {
-            eprintln!("No table {}", table);
+    let mut rows = match db.rows(table) {
+        Ok(rows) => rows,
+        Err(err) => {
+            eprintln!("Cannot read table {}: {}", table, err);
             return;
         }
     };
@@ -43,21 +44,86 @@ async fn print_rows(db: &mut MdfDatabase, table: &str, row_limit: &Option<usize>
     pretty_table.printstd();
 }
 
+async fn print_csv(db: &mut MdfDatabase, table: &str) {
+    let stdout = std::io::stdout();
+    if let Err(err) = db.write_table_csv(table, stdout.lock()).await {
+        eprintln!("Cannot write table {}: {}", table, err);
+    }
+}
+
+async fn print_json(db: &mut MdfDatabase, table: &str, row_limit: &Option<usize>) {
+    let mut rows = match db.rows_as_json(table) {
+        Ok(rows) => rows,
+        Err(err) => {
+            eprintln!("Cannot read table {}: {}", table, err);
+            return;
+        }
+    };
+
+    let mut i = 0usize;
+    while let Some(row) = rows.next().await {
+        match row {
+            Ok(row) => println!("{}", row),
+            Err(err) => eprintln!("Cannot read row of table {}: {}", table, err),
+        }
+
+        i += 1;
+
+        if matches!(row_limit, Some(row_limit) if i >= *row_limit) {
+            break;
+        }
+    }
+}
+
+async fn print(db: &mut MdfDatabase, table: &str, opt: &Opts) {
+    match opt.format {
+        Format::Table => print_rows(db, table, &opt.row_limit).await,
+        Format::Csv => print_csv(db, table).await,
+        Format::Json => print_json(db, table, &opt.row_limit).await,
+    }
+}
+
 #[async_std::main]
 async fn main() {
-    femme::with_level(log::LevelFilter::Trace);
     let opt = Opts::from_args();
+    // The logger writes to stdout, where it would corrupt the CSV and JSON output
+    if let Format::Table = opt.format {
+        femme::with_level(log::LevelFilter::Trace);
+    }
 
-    let mut db = MdfDatabase::open(opt.path).await.unwrap();
+    let mut db = MdfDatabase::open(&opt.path).await.unwrap();
 
-    match opt.table {
+    match &opt.table {
         None => {
             for table in db.table_names() {
-                print_rows(&mut db, &table, &opt.row_limit).await;
+                print(&mut db, &table, &opt).await;
             }
         }
         Some(table) => {
-            print_rows(&mut db, &table, &opt.row_limit).await;
+            print(&mut db, table, &opt).await;
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Format {
+    Table,
+    Csv,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Format::Table),
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            _ => Err(format!(
+                "unknown format '{}', expected json, csv or table",
+                s
+            )),
         }
     }
 }
@@ -76,7 +142,11 @@ struct Opts {
     #[structopt(long)]
     table: Option<String>,
 
-    /// Max number of rows to print
+    /// Max number of rows to print (ignored for CSV, which always contains the whole table)
     #[structopt(long)]
     row_limit: Option<usize>,
+
+    /// The output format: json (one object per line), csv or table
+    #[structopt(long, default_value = "table")]
+    format: Format,
 }
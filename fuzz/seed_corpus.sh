@@ -0,0 +1,15 @@
+#!/bin/sh
+# Seeds the corpus of the parse_page target with the pages of the bundled MDF files.
+#
+# Usage: fuzz/seed_corpus.sh && cargo fuzz run parse_page
+set -e
+
+cd "$(dirname "$0")"
+
+corpus=corpus/parse_page
+mkdir -p "$corpus"
+
+for file in ../data/*.mdf; do
+    name=$(basename "$file" .mdf)
+    split -b 8192 -a 5 -d "$file" "$corpus/$name-"
+done
@@ -0,0 +1,776 @@
+//! Decoding of the records of ROW- and PAGE-compressed tables.
+//!
+//! Compressed records are stored in the CD format, which stores the length of each value in a
+//! column descriptor (CD) array and only the significant bytes of the values. PAGE compression
+//! additionally stores an anchor record with a common prefix of the values of each column and a
+//! dictionary of values that occur repeatedly in the compression information of the page.
+//!
+//! The records are converted to the FixedVar format of uncompressed records, so that their
+//! values are parsed like the ones of uncompressed tables.
+
+use crate::pages::Page;
+use crate::schema::ColumnType;
+use crate::sys::Column;
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::borrow::Cow;
+use std::convert::TryFrom;
+
+/// The short and long data of every 30 columns are grouped into a cluster.
+const CLUSTER_SIZE: usize = 30;
+
+const UNICODE_COMPRESSION: &str = "Unicode compressed values are not supported yet";
+
+/// The value of a column of a compressed record as described by its column descriptor.
+#[derive(Clone, Debug, PartialEq)]
+enum CompressedColumn<'a> {
+    Null,
+    /// The short or long data of the value and whether it is a complex column, e.g. a pointer
+    /// to LOB data stored out of row.
+    Data(&'a [u8], bool),
+    /// A bit column with the value 1, which has no data.
+    BitTrue,
+    /// The index of the entry of the page dictionary that holds the value.
+    Symbol(usize),
+}
+
+/// The bytes of a value and whether it is a complex column.
+type ColumnValue<'a> = (Cow<'a, [u8]>, bool);
+
+/// A record in the CD format of ROW- and PAGE-compressed tables.
+#[derive(Debug, PartialEq)]
+pub(crate) struct CompressedRecord<'a> {
+    columns: Vec<CompressedColumn<'a>>,
+    /// The number of bytes of the record, which is not stored.
+    length: usize,
+}
+
+impl<'a> CompressedRecord<'a> {
+    const CD_FORMAT: u8 = 0x01;
+    const HAS_LONG_DATA_REGION: u8 = 0x20;
+    const HAS_LONG_DATA_CLUSTERS: u8 = 0x01;
+
+    const NULL: u8 = 0;
+    const EMPTY: u8 = 1;
+    const LONG: u8 = 10;
+    const BIT_TRUE: u8 = 11;
+    const PAGE_SYMBOL: u8 = 12;
+
+    /// Returns the values of the complex columns, e.g. pointers to LOB data stored out of row.
+    pub(crate) fn complex_columns(&self) -> Vec<&'a [u8]> {
+        self.columns
+            .iter()
+            .filter_map(|column| match column {
+                CompressedColumn::Data(bytes, true) => Some(*bytes),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Converts the record to the FixedVar format of uncompressed records of the given stored
+    /// columns, resolving page symbols and column prefixes with the compression information of
+    /// the page. Fails with the index of the column whose value cannot be converted.
+    pub(crate) fn decompress(
+        &self,
+        compression_info: Option<&CompressionInfo<'a>>,
+        columns: &[&Column<'_>],
+    ) -> Result<Vec<u8>, (usize, &'static str)> {
+        let mut fixed_bytes = Vec::new();
+        let mut null_bitmap = vec![0u8; columns.len().div_ceil(8)];
+        let mut variable_columns = Vec::new();
+        // The position of the byte shared by up to eight bit columns and of the next bit in it
+        let mut packed_bits: Option<(usize, u8)> = None;
+
+        for (index, column) in columns.iter().enumerate() {
+            let value = self
+                .value(index, compression_info)
+                .map_err(|err| (index, err))?;
+            if value.is_none() {
+                null_bitmap[index / 8] |= 1 << (index % 8);
+            }
+
+            match column.column_type().ok_or((index, "Unknown column type"))? {
+                ColumnType::Bit => {
+                    let (position, bit) = match packed_bits {
+                        Some((position, bit)) if bit < 8 => (position, bit),
+                        _ => {
+                            fixed_bytes.push(0);
+                            (fixed_bytes.len() - 1, 0)
+                        }
+                    };
+                    if value.is_some_and(|(bytes, _)| bytes.iter().any(|byte| *byte != 0)) {
+                        fixed_bytes[position] |= 1 << bit;
+                    }
+                    packed_bits = Some((position, bit + 1));
+                }
+                column_type => match (column_type.fixed_length(), value) {
+                    (Some(length), Some((bytes, _))) => fixed_bytes.extend(
+                        expand_fixed_value(column_type, &bytes, length)
+                            .map_err(|err| (index, err))?,
+                    ),
+                    (Some(length), None) => fixed_bytes.resize(fixed_bytes.len() + length, 0),
+                    (None, Some((bytes, complex))) => {
+                        if is_unicode(column) && !complex && !bytes.len().is_multiple_of(2) {
+                            return Err((index, UNICODE_COMPRESSION));
+                        }
+                        variable_columns.push((index, bytes, complex));
+                    }
+                    (None, None) => variable_columns.push((index, Cow::Borrowed(&[][..]), false)),
+                },
+            }
+        }
+
+        let status = if variable_columns.is_empty() {
+            0x10
+        } else {
+            0x30
+        };
+        let mut bytes = vec![status, 0];
+        bytes.extend_from_slice(&(4 + fixed_bytes.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(&fixed_bytes);
+        bytes.extend_from_slice(&(columns.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(&null_bitmap);
+
+        if !variable_columns.is_empty() {
+            bytes.extend_from_slice(&(variable_columns.len() as u16).to_le_bytes());
+            let mut end_offset = bytes.len() + variable_columns.len() * 2;
+            for (index, column, complex) in &variable_columns {
+                end_offset += column.len();
+                if end_offset > 0x7fff {
+                    return Err((
+                        *index,
+                        "Decompressed record exceeds the maximum record length",
+                    ));
+                }
+                let flag = if *complex { 0x8000 } else { 0 };
+                bytes.extend_from_slice(&(end_offset as u16 | flag).to_le_bytes());
+            }
+            for (_, column, _) in &variable_columns {
+                bytes.extend_from_slice(column);
+            }
+        }
+
+        Ok(bytes)
+    }
+
+    /// Returns the bytes of the value of the column with the given index and whether it is a
+    /// complex column. Columns the record does not contain, e.g. because they were added after
+    /// the record was written, are NULL.
+    fn value(
+        &self,
+        index: usize,
+        compression_info: Option<&CompressionInfo<'a>>,
+    ) -> Result<Option<ColumnValue<'a>>, &'static str> {
+        let (bytes, complex) = match self.columns.get(index) {
+            None | Some(CompressedColumn::Null) => return Ok(None),
+            Some(CompressedColumn::BitTrue) => return Ok(Some((Cow::Borrowed(&[1][..]), false))),
+            Some(CompressedColumn::Data(bytes, complex)) => (*bytes, *complex),
+            Some(CompressedColumn::Symbol(symbol)) => {
+                let entry = compression_info
+                    .and_then(|compression_info| compression_info.dictionary.get(*symbol))
+                    .ok_or("Page symbol is missing in the page dictionary")?;
+                (*entry, false)
+            }
+        };
+
+        // Values that share a prefix with the anchor record start with the number of bytes
+        // of the prefix followed by the rest of the value
+        let prefix = compression_info
+            .and_then(|compression_info| compression_info.prefix(index))
+            .filter(|_| !complex && !bytes.is_empty());
+
+        match prefix {
+            Some(prefix) => {
+                let mut suffix = bytes;
+                let prefix_length = read_number(&mut suffix)?;
+                let prefix = prefix
+                    .get(..prefix_length)
+                    .ok_or("Column prefix exceeds the value of the anchor record")?;
+                Ok(Some((Cow::Owned([prefix, suffix].concat()), false)))
+            }
+            None => Ok(Some((Cow::Borrowed(bytes), complex))),
+        }
+    }
+}
+
+/// Converts the bytes into a `CompressedRecord`, which may be followed by other data.
+///
+/// ```text
+/// Bytes       Content
+/// -----       -------
+/// 0           Header: bit 0 marks the CD format, bits 2-4 the record type and bit 5 a long
+///             data region
+/// 1-          Number of columns
+///             Column descriptors, 4 bits each with the one of the first column in the low
+///             bits: 0 NULL, 1 empty, 2-9 1 to 8 bytes of short data, 10 long data, 11 bit
+///             with the value 1, 12 page symbol
+///             Short data cluster array, the length of the short data of each cluster but the
+///             last
+///             Short data, i.e. the values and page symbols in column order
+///             Long data region
+///
+/// Long data region
+/// 0           Header: bit 0 marks a long data cluster array
+/// 1-          Number of long values
+///             End offsets of the long values relative to the start of the long data, 2 bytes
+///             each, whose high bit marks complex columns
+///             Long data cluster array, the number of long values of each cluster but the last
+///             Long data
+/// ```
+///
+/// Numbers, page symbols and prefix lengths are stored in 1 byte or, if the high bit of the
+/// first byte is set, in 2 big-endian bytes.
+impl<'a> TryFrom<&'a [u8]> for CompressedRecord<'a> {
+    type Error = &'static str;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        let (header, mut remaining) = bytes.split_first().ok_or("Compressed record is empty")?;
+        if header & Self::CD_FORMAT == 0 {
+            return Err("Record is not in the CD format");
+        }
+
+        let number_of_columns = read_number(&mut remaining)?;
+        let descriptors = take(&mut remaining, number_of_columns.div_ceil(2))?;
+
+        // The lengths of the clusters follow from the column descriptors as well
+        let clusters = number_of_columns.saturating_sub(1) / CLUSTER_SIZE;
+        take(&mut remaining, clusters)?;
+
+        let mut columns = Vec::with_capacity(number_of_columns);
+        let mut long_columns = Vec::new();
+        for index in 0..number_of_columns {
+            let descriptor = (descriptors[index / 2] >> (4 * (index % 2))) & 0x0f;
+            let column = match descriptor {
+                Self::NULL => CompressedColumn::Null,
+                Self::EMPTY => CompressedColumn::Data(&[], false),
+                2..=9 => {
+                    CompressedColumn::Data(take(&mut remaining, descriptor as usize - 1)?, false)
+                }
+                Self::LONG => {
+                    // Replaced by the long data once the short data has been read
+                    long_columns.push(index);
+                    CompressedColumn::Null
+                }
+                Self::BIT_TRUE => CompressedColumn::BitTrue,
+                Self::PAGE_SYMBOL => CompressedColumn::Symbol(read_number(&mut remaining)?),
+                _ => return Err("Unknown column descriptor"),
+            };
+            columns.push(column);
+        }
+
+        if header & Self::HAS_LONG_DATA_REGION != 0 {
+            let (long_header, mut long_data) = remaining
+                .split_first()
+                .ok_or("Compressed record is shorter than its long data region")?;
+            let number_of_long_values = read_number(&mut long_data)?;
+            if number_of_long_values != long_columns.len() {
+                return Err("Number of long values differs from the column descriptors");
+            }
+
+            let mut end_offsets = take(&mut long_data, number_of_long_values * 2)?;
+            if long_header & Self::HAS_LONG_DATA_CLUSTERS != 0 {
+                take(&mut long_data, clusters)?;
+            }
+
+            let mut start = 0;
+            for index in long_columns {
+                let end_offset = end_offsets.read_u16::<LittleEndian>().unwrap();
+                let end = (end_offset & 0x7fff) as usize;
+                let value = long_data
+                    .get(start..end)
+                    .ok_or("Long value exceeds the compressed record")?;
+                columns[index] = CompressedColumn::Data(value, end_offset & 0x8000 != 0);
+                start = end;
+            }
+            remaining = &long_data[start..];
+        } else if !long_columns.is_empty() {
+            return Err("Compressed record with long values lacks the long data region");
+        }
+
+        Ok(Self {
+            columns,
+            length: bytes.len() - remaining.len(),
+        })
+    }
+}
+
+/// The compression information (CI) of a PAGE-compressed page, which follows the page header.
+/// Pages on which PAGE compression does not save space have none.
+#[derive(Debug, PartialEq)]
+pub(crate) struct CompressionInfo<'a> {
+    /// The record with the prefix of the values of each column.
+    anchor_record: Option<CompressedRecord<'a>>,
+    /// The values the page symbols of the records refer to.
+    dictionary: Vec<&'a [u8]>,
+}
+
+impl<'a> CompressionInfo<'a> {
+    const HAS_ANCHOR_RECORD: u8 = 0x02;
+    const HAS_DICTIONARY: u8 = 0x04;
+
+    /// Returns the compression information of the page or `None` if the page has none.
+    pub(crate) fn of_page(page: &'a Page) -> Result<Option<Self>, &'static str> {
+        page.compression_info_bytes()
+            .map(Self::try_from)
+            .transpose()
+    }
+
+    /// Returns the prefix of the values of the column with the given index, if any.
+    fn prefix(&self, index: usize) -> Option<&'a [u8]> {
+        match self.anchor_record.as_ref()?.columns.get(index)? {
+            CompressedColumn::Data(prefix, false) => Some(*prefix),
+            _ => None,
+        }
+    }
+}
+
+/// Converts the bytes following the page header into a `CompressionInfo`.
+///
+/// ```text
+/// Bytes       Content
+/// -----       -------
+/// 0           Header: bit 1 marks an anchor record and bit 2 a dictionary
+/// 1-2         Page modification count
+/// 3-4         Length of the compression information
+/// 5-          Anchor record in the CD format
+///             Dictionary
+///
+/// Dictionary
+/// 0-1         Number of entries
+/// 2-          End offsets of the entries relative to the start of the dictionary, 2 bytes each
+///             Entries
+/// ```
+impl<'a> TryFrom<&'a [u8]> for CompressionInfo<'a> {
+    type Error = &'static str;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        if bytes.len() < 5 {
+            return Err("Compression information is shorter than its header");
+        }
+
+        let header = bytes[0];
+        let length = (&bytes[3..5]).read_u16::<LittleEndian>().unwrap() as usize;
+        let mut remaining = bytes
+            .get(5..length)
+            .ok_or("Compression information length exceeds the page")?;
+
+        let anchor_record = if header & Self::HAS_ANCHOR_RECORD != 0 {
+            let anchor_record = CompressedRecord::try_from(remaining)?;
+            remaining = &remaining[anchor_record.length..];
+            Some(anchor_record)
+        } else {
+            None
+        };
+
+        let mut dictionary = Vec::new();
+        if header & Self::HAS_DICTIONARY != 0 {
+            let mut entries = remaining;
+            let number_of_entries = entries
+                .read_u16::<LittleEndian>()
+                .map_err(|_| "Page dictionary is shorter than its header")?
+                as usize;
+            let mut end_offsets = take(&mut entries, number_of_entries * 2)?;
+
+            let mut start = 2 + number_of_entries * 2;
+            for _ in 0..number_of_entries {
+                let end = end_offsets.read_u16::<LittleEndian>().unwrap() as usize;
+                let entry = remaining
+                    .get(start..end)
+                    .ok_or("Page dictionary entry exceeds the compression information")?;
+                dictionary.push(entry);
+                start = end;
+            }
+        }
+
+        Ok(Self {
+            anchor_record,
+            dictionary,
+        })
+    }
+}
+
+/// Returns `true` for the columns of Unicode strings, whose values may be Unicode compressed.
+fn is_unicode(column: &Column<'_>) -> bool {
+    matches!(column.base_type(), "nchar" | "nvarchar")
+}
+
+/// Converts the value of a fixed length column, of which only the significant bytes are stored,
+/// to the bytes of the uncompressed value.
+fn expand_fixed_value(
+    column_type: ColumnType,
+    bytes: &[u8],
+    length: usize,
+) -> Result<Vec<u8>, &'static str> {
+    if bytes.len() > length {
+        return Err("Compressed value exceeds the length of the column type");
+    }
+
+    match column_type {
+        ColumnType::TinyInt => Ok(padded(bytes, length, &[0])),
+        ColumnType::SmallInt
+        | ColumnType::Int
+        | ColumnType::BigInt
+        | ColumnType::SmallMoney
+        | ColumnType::Money => Ok(expand_integer(bytes, length)),
+        // Trailing spaces and zeros are not stored
+        ColumnType::Char { .. } => Ok(padded(bytes, length, b" ")),
+        ColumnType::NChar { .. } if !bytes.len().is_multiple_of(2) => Err(UNICODE_COMPRESSION),
+        ColumnType::NChar { .. } => Ok(padded(bytes, length, &[b' ', 0])),
+        ColumnType::FixedBinary { .. } => Ok(padded(bytes, length, &[0])),
+        ColumnType::Uuid | ColumnType::RowVersion if bytes.len() == length => Ok(bytes.to_vec()),
+        ColumnType::Uuid | ColumnType::RowVersion => {
+            Err("Compressed value is shorter than the column type")
+        }
+        _ => Err("Compressed values of the column type are not supported yet"),
+    }
+}
+
+/// Converts an integer that is stored in as few big-endian bytes as possible with the sign bit
+/// flipped, so that small negative numbers need few bytes as well, to `length` little-endian
+/// bytes.
+fn expand_integer(bytes: &[u8], length: usize) -> Vec<u8> {
+    if bytes.is_empty() {
+        return vec![0; length];
+    }
+
+    let bits = 8 * bytes.len() as u32;
+    let value = bytes
+        .iter()
+        .fold(0u64, |value, byte| (value << 8) | *byte as u64)
+        ^ (1 << (bits - 1));
+
+    // Sign extend the value
+    let unused_bits = 64 - bits;
+    let value = ((value << unused_bits) as i64) >> unused_bits;
+
+    value.to_le_bytes()[..length].to_vec()
+}
+
+fn padded(bytes: &[u8], length: usize, padding: &[u8]) -> Vec<u8> {
+    let mut value = bytes.to_vec();
+    value.extend(padding.iter().cycle().take(length - bytes.len()));
+    value
+}
+
+/// Reads a number stored in 1 byte or, if the high bit of the first byte is set, in 2
+/// big-endian bytes.
+fn read_number(bytes: &mut &[u8]) -> Result<usize, &'static str> {
+    let first = take(bytes, 1)?[0];
+    if first & 0x80 == 0 {
+        return Ok(first as usize);
+    }
+
+    let second = take(bytes, 1)?[0];
+    Ok(((first & 0x7f) as usize) << 8 | second as usize)
+}
+
+fn take<'a>(bytes: &mut &'a [u8], length: usize) -> Result<&'a [u8], &'static str> {
+    if bytes.len() < length {
+        return Err("Compressed data ends before its values");
+    }
+
+    let (taken, remaining) = bytes.split_at(length);
+    *bytes = remaining;
+    Ok(taken)
+}
+
+/// Builds the bytes of a record in the CD format for tests.
+#[cfg(test)]
+#[derive(Default)]
+pub(crate) struct CompressedRecordBuilder {
+    descriptors: Vec<u8>,
+    short_data: Vec<Vec<u8>>,
+    long_values: Vec<(Vec<u8>, bool)>,
+}
+
+#[cfg(test)]
+impl CompressedRecordBuilder {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn null(self) -> Self {
+        self.column(CompressedRecord::NULL, Vec::new())
+    }
+
+    /// Appends a column with 0 to 8 bytes of short data.
+    pub(crate) fn short(self, bytes: &[u8]) -> Self {
+        assert!(bytes.len() <= 8);
+        match bytes.len() {
+            0 => self.column(CompressedRecord::EMPTY, Vec::new()),
+            length => self.column(length as u8 + 1, bytes.to_vec()),
+        }
+    }
+
+    /// Appends a column with long data.
+    pub(crate) fn long(self, bytes: &[u8]) -> Self {
+        self.long_value(bytes, false)
+    }
+
+    /// Appends a complex column, e.g. a pointer to a value stored out of row.
+    pub(crate) fn complex(self, bytes: &[u8]) -> Self {
+        self.long_value(bytes, true)
+    }
+
+    pub(crate) fn bit(self, value: bool) -> Self {
+        match value {
+            true => self.column(CompressedRecord::BIT_TRUE, Vec::new()),
+            false => self.column(CompressedRecord::EMPTY, Vec::new()),
+        }
+    }
+
+    /// Appends a column whose value is the entry of the page dictionary with the given index.
+    pub(crate) fn symbol(self, symbol: usize) -> Self {
+        self.column(CompressedRecord::PAGE_SYMBOL, number(symbol))
+    }
+
+    fn long_value(mut self, bytes: &[u8], complex: bool) -> Self {
+        self.long_values.push((bytes.to_vec(), complex));
+        self.column(CompressedRecord::LONG, Vec::new())
+    }
+
+    fn column(mut self, descriptor: u8, short_data: Vec<u8>) -> Self {
+        self.descriptors.push(descriptor);
+        self.short_data.push(short_data);
+        self
+    }
+
+    pub(crate) fn build(self) -> Vec<u8> {
+        let long_data_region = !self.long_values.is_empty();
+        let mut bytes = vec![
+            CompressedRecord::CD_FORMAT
+                | if long_data_region {
+                    CompressedRecord::HAS_LONG_DATA_REGION
+                } else {
+                    0
+                },
+        ];
+
+        bytes.extend(number(self.descriptors.len()));
+        for descriptors in self.descriptors.chunks(2) {
+            bytes.push(descriptors[0] | descriptors.get(1).map_or(0, |d| d << 4));
+        }
+
+        let clusters = self.short_data.chunks(CLUSTER_SIZE).collect::<Vec<_>>();
+        for cluster in &clusters[..clusters.len().saturating_sub(1)] {
+            bytes.push(cluster.iter().map(Vec::len).sum::<usize>() as u8);
+        }
+        bytes.extend(self.short_data.concat());
+
+        if long_data_region {
+            let long_clusters = self.descriptors.chunks(CLUSTER_SIZE).collect::<Vec<_>>();
+            let has_long_clusters = long_clusters.len() > 1;
+            bytes.push(if has_long_clusters {
+                CompressedRecord::HAS_LONG_DATA_CLUSTERS
+            } else {
+                0
+            });
+            bytes.extend(number(self.long_values.len()));
+
+            let mut end_offset = 0;
+            for (value, complex) in &self.long_values {
+                end_offset += value.len() as u16;
+                let flag = if *complex { 0x8000 } else { 0 };
+                bytes.extend_from_slice(&(end_offset | flag).to_le_bytes());
+            }
+            if has_long_clusters {
+                for cluster in &long_clusters[..long_clusters.len() - 1] {
+                    let long_values = cluster
+                        .iter()
+                        .filter(|descriptor| **descriptor == CompressedRecord::LONG)
+                        .count();
+                    bytes.push(long_values as u8);
+                }
+            }
+            for (value, _) in &self.long_values {
+                bytes.extend_from_slice(value);
+            }
+        }
+
+        bytes
+    }
+}
+
+/// Builds the compression information of a PAGE-compressed page for tests.
+#[cfg(test)]
+pub(crate) fn compression_info(anchor_record: Option<&[u8]>, dictionary: &[&[u8]]) -> Vec<u8> {
+    let mut header = 0;
+    let mut bytes = Vec::new();
+
+    if let Some(anchor_record) = anchor_record {
+        header |= CompressionInfo::HAS_ANCHOR_RECORD;
+        bytes.extend_from_slice(anchor_record);
+    }
+
+    if !dictionary.is_empty() {
+        header |= CompressionInfo::HAS_DICTIONARY;
+        bytes.extend_from_slice(&(dictionary.len() as u16).to_le_bytes());
+        let mut end_offset = 2 + dictionary.len() * 2;
+        for entry in dictionary {
+            end_offset += entry.len();
+            bytes.extend_from_slice(&(end_offset as u16).to_le_bytes());
+        }
+        bytes.extend(dictionary.concat());
+    }
+
+    let length = 5 + bytes.len() as u16;
+    [&[header, 0, 0][..], &length.to_le_bytes(), &bytes].concat()
+}
+
+/// Stores a number in 1 or 2 bytes like compressed records do for tests.
+#[cfg(test)]
+pub(crate) fn number(number: usize) -> Vec<u8> {
+    match number {
+        0..=0x7f => vec![number as u8],
+        _ => vec![0x80 | (number >> 8) as u8, number as u8],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pages::{PageBuilder, DATA_PAGE};
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest(
+        bytes,
+        expected_value,
+        case(vec![], 0),
+        case(vec![0x81], 1),
+        case(vec![0x7e], -2),
+        case(vec![0x81, 0x2c], 300),
+        case(vec![0x7f, 0x7f], -129),
+        case(vec![0x81, 0x2a, 0x05, 0xf2, 0x00], 5_000_000_000),
+        case(vec![0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff], -1),
+        case(vec![0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff], i64::MAX)
+    )]
+    fn expand_compressed_integer(bytes: Vec<u8>, expected_value: i64) {
+        let value = expand_integer(&bytes, 8);
+
+        assert_eq!(
+            (&value[..]).read_i64::<LittleEndian>().unwrap(),
+            expected_value
+        );
+    }
+
+    #[test]
+    fn parse_compressed_record() {
+        let bytes = CompressedRecordBuilder::new()
+            .short(&[0x81])
+            .null()
+            .bit(true)
+            .long(b"a long value")
+            .symbol(2)
+            .short(b"")
+            .complex(&[7; 16])
+            .build();
+
+        let record = CompressedRecord::try_from(&bytes[..]).unwrap();
+
+        assert_eq!(
+            record,
+            CompressedRecord {
+                columns: vec![
+                    CompressedColumn::Data(&[0x81], false),
+                    CompressedColumn::Null,
+                    CompressedColumn::BitTrue,
+                    CompressedColumn::Data(b"a long value", false),
+                    CompressedColumn::Symbol(2),
+                    CompressedColumn::Data(&[], false),
+                    CompressedColumn::Data(&[7; 16], true),
+                ],
+                length: bytes.len(),
+            }
+        );
+        assert_eq!(record.complex_columns(), vec![&[7u8; 16][..]]);
+    }
+
+    #[rstest(column_count, case(30), case(31), case(65))]
+    fn parse_compressed_record_with_clusters(column_count: usize) {
+        let mut builder = CompressedRecordBuilder::new();
+        for column in 0..column_count {
+            builder = match column % 3 {
+                0 => builder.short(&[column as u8]),
+                1 => builder.long(&[column as u8; 9]),
+                _ => builder.null(),
+            };
+        }
+        // Data of the next record
+        let bytes = [builder.build(), vec![0xff; 4]].concat();
+
+        let record = CompressedRecord::try_from(&bytes[..]).unwrap();
+
+        assert_eq!(record.length, bytes.len() - 4);
+        assert_eq!(record.columns.len(), column_count);
+        for (index, column) in record.columns.iter().enumerate() {
+            let value = match column {
+                CompressedColumn::Data(bytes, false) => Some(bytes.to_vec()),
+                _ => None,
+            };
+            let expected_value = match index % 3 {
+                0 => Some(vec![index as u8]),
+                1 => Some(vec![index as u8; 9]),
+                _ => None,
+            };
+            assert_eq!(value, expected_value, "column {}", index);
+        }
+    }
+
+    #[rstest(
+        column,
+        expected_value,
+        // A prefix of 3 bytes of the anchor record followed by the rest of the value
+        case(0, Ok(Some(b"Germany".to_vec()))),
+        // The dictionary entry is prefix compressed as well
+        case(1, Ok(Some(b"Germany".to_vec()))),
+        // Columns without anchor value have no prefix
+        case(2, Ok(Some(b"Berlin".to_vec()))),
+        case(3, Ok(Some(vec![]))),
+        case(4, Ok(None)),
+        case(5, Err("Column prefix exceeds the value of the anchor record")),
+        case(6, Err("Page symbol is missing in the page dictionary"))
+    )]
+    fn resolve_column_prefixes_and_page_symbols(
+        column: usize,
+        expected_value: Result<Option<Vec<u8>>, &'static str>,
+    ) {
+        let anchor_record = CompressedRecordBuilder::new()
+            .short(b"Ger")
+            .short(b"Ger")
+            .null()
+            .short(b"Ger")
+            .null()
+            .short(b"Ger")
+            .build();
+        let bytes = PageBuilder::new(DATA_PAGE, 1)
+            .compression_info(&compression_info(Some(&anchor_record), &[b"\x03many"]))
+            .build();
+        let page = Page::try_from(bytes).unwrap();
+        let compression_info = CompressionInfo::of_page(&page).unwrap();
+        let record = CompressedRecordBuilder::new()
+            .short(b"\x03many")
+            .symbol(0)
+            .short(b"Berlin")
+            .short(b"")
+            .null()
+            .short(b"\x04y")
+            .symbol(1)
+            .build();
+
+        let value = CompressedRecord::try_from(&record[..])
+            .unwrap()
+            .value(column, compression_info.as_ref());
+
+        assert_eq!(
+            value.map(|value| value.map(|(bytes, _)| bytes.into_owned())),
+            expected_value
+        );
+    }
+
+    #[test]
+    fn no_compression_info_on_row_compressed_page() {
+        let page = Page::try_from(PageBuilder::new(DATA_PAGE, 1).build()).unwrap();
+
+        assert_eq!(CompressionInfo::of_page(&page), Ok(None));
+    }
+}
@@ -1,3 +1,5 @@
+use crate::schema::ColumnType;
+use crate::Value;
 use async_std::io::Error as IoError;
 use std::convert::From;
 use std::fmt::{Display, Formatter};
@@ -5,12 +7,121 @@ use std::fmt::{Display, Formatter};
 #[derive(Debug)]
 pub enum Error {
     IoError(IoError),
+    /// The database does not contain a table with the given name.
+    UnknownTable(String),
+    /// The file contains another database than expected, see
+    /// [`MdfDatabase::open_expecting`](crate::MdfDatabase::open_expecting).
+    UnexpectedDatabase {
+        found: String,
+        expected: String,
+    },
+    /// The length of the file is no multiple of the page size, e.g. because copying the file
+    /// was cut off. Only reported in strict mode, see
+    /// [`Options::strict`](crate::options::Options::strict).
+    TruncatedFile {
+        length: u64,
+    },
+    /// None of the first pages of the file is a boot page, i.e. the file is no MDF file.
+    BootPageNotFound,
+    /// The page at the location of the boot page is no valid boot page.
+    InvalidBootPage {
+        page_id: u32,
+        reason: &'static str,
+    },
+    /// The system tables describing the database are inconsistent, e.g. their page chain forms
+    /// a cycle.
+    CorruptMetadata(String),
+    /// No column of the table could be read from the system tables, which indicates corrupt or
+    /// unsupported metadata rather than a table without columns.
+    NoColumns(String),
+    /// The type of the column cannot be decoded. Only reported in strict mode before any row of
+    /// the table is read, see [`Options::strict`](crate::options::Options::strict).
+    UnsupportedColumnType {
+        table: String,
+        column: String,
+        type_name: String,
+    },
+    /// The records of the table are compressed in a format that cannot be decoded, i.e. with
+    /// columnstore compression or, without the `compression` feature, with ROW or PAGE
+    /// compression. Recovering deleted rows of compressed tables is not supported at all.
+    UnsupportedCompression {
+        table: String,
+        compression: &'static str,
+    },
+    /// A column value of a record could not be decoded.
+    ColumnParse {
+        table: String,
+        column: String,
+        page_id: u32,
+        /// The offset within the page at which the value starts.
+        offset: u16,
+        source: ParseError,
+    },
+    /// The value cannot be converted to the column type without losing information, see
+    /// [`Value::coerce_to`](crate::Value::coerce_to).
+    LossyCoercion {
+        value: Value,
+        target: ColumnType,
+    },
+    #[cfg(feature = "arrow")]
+    ArrowError(arrow::error::ArrowError),
+    #[cfg(feature = "parquet")]
+    ParquetError(parquet::errors::ParquetError),
 }
 
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             Error::IoError(err) => write!(f, "IO Error: {}", err),
+            Error::UnknownTable(table) => write!(f, "unknown table '{}'", table),
+            Error::UnexpectedDatabase { found, expected } => write!(
+                f,
+                "expected database '{}' but the file contains '{}'",
+                expected, found
+            ),
+            Error::TruncatedFile { length } => write!(
+                f,
+                "the file length {} is no multiple of the page size, the file is truncated",
+                length
+            ),
+            Error::BootPageNotFound => write!(f, "no boot page found, the file is no MDF file"),
+            Error::InvalidBootPage { page_id, reason } => {
+                write!(f, "invalid boot page {}: {}", page_id, reason)
+            }
+            Error::CorruptMetadata(reason) => write!(f, "corrupt metadata: {}", reason),
+            Error::NoColumns(table) => write!(f, "no columns of table '{}' found", table),
+            Error::UnsupportedColumnType {
+                table,
+                column,
+                type_name,
+            } => write!(
+                f,
+                "column '{}' of table '{}' has the unsupported type '{}'",
+                column, table, type_name
+            ),
+            Error::UnsupportedCompression { table, compression } => write!(
+                f,
+                "table '{}' uses {} compression, which is not supported",
+                table, compression
+            ),
+            Error::ColumnParse {
+                table,
+                column,
+                page_id,
+                offset,
+                source,
+            } => write!(
+                f,
+                "failed to parse column '{}' of table '{}' on page {} at offset {}: {}",
+                column, table, page_id, offset, source
+            ),
+            Error::LossyCoercion { value, target } => {
+                write!(f, "cannot convert {} to {:?} without loss", value, target)
+            }
+            #[cfg(feature = "arrow")]
+            Error::ArrowError(err) => write!(f, "Arrow Error: {}", err),
+            #[cfg(feature = "parquet")]
+            Error::ParquetError(err) => write!(f, "Parquet Error: {}", err),
         }
     }
 }
@@ -19,6 +130,21 @@ impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             Error::IoError(err) => Some(err),
+            Error::UnknownTable(_) => None,
+            Error::UnexpectedDatabase { .. } => None,
+            Error::TruncatedFile { .. } => None,
+            Error::BootPageNotFound => None,
+            Error::InvalidBootPage { .. } => None,
+            Error::CorruptMetadata(_) => None,
+            Error::NoColumns(_) => None,
+            Error::UnsupportedColumnType { .. } => None,
+            Error::UnsupportedCompression { .. } => None,
+            Error::ColumnParse { source, .. } => Some(source),
+            Error::LossyCoercion { .. } => None,
+            #[cfg(feature = "arrow")]
+            Error::ArrowError(err) => Some(err),
+            #[cfg(feature = "parquet")]
+            Error::ParquetError(err) => Some(err),
         }
     }
 }
@@ -28,3 +154,69 @@ impl From<IoError> for Error {
         Self::IoError(err)
     }
 }
+
+#[cfg(feature = "arrow")]
+impl From<arrow::error::ArrowError> for Error {
+    fn from(err: arrow::error::ArrowError) -> Self {
+        Self::ArrowError(err)
+    }
+}
+
+#[cfg(feature = "parquet")]
+impl From<parquet::errors::ParquetError> for Error {
+    fn from(err: parquet::errors::ParquetError) -> Self {
+        Self::ParquetError(err)
+    }
+}
+
+/// The low-level reason why bytes of a record could not be decoded.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ParseError(&'static str);
+
+impl ParseError {
+    pub fn reason(&self) -> &'static str {
+        self.0
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<&'static str> for ParseError {
+    fn from(reason: &'static str) -> Self {
+        Self(reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::error::Error as _;
+
+    #[test]
+    fn column_parse_error_display() {
+        let err = Error::ColumnParse {
+            table: String::from("Address"),
+            column: String::from("City"),
+            page_id: 42,
+            offset: 137,
+            source: ParseError::from("fixed data underflow"),
+        };
+
+        assert_eq!(
+            err.to_string(),
+            "failed to parse column 'City' of table 'Address' on page 42 at offset 137: \
+             fixed data underflow"
+        );
+        assert_eq!(
+            err.source().map(|source| source.to_string()),
+            Some(String::from("fixed data underflow"))
+        );
+    }
+}
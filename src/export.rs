@@ -0,0 +1,700 @@
+//! Exports of table data into other formats.
+
+pub(crate) mod csv {
+    use crate::format::{FormatOptions, NullFormat};
+    use crate::Value;
+    use std::borrow::Cow;
+    use std::io::Write;
+
+    /// Writes one line of comma separated fields, quoting fields as described in RFC 4180.
+    pub(crate) fn write_record<W, I, S>(writer: &mut W, fields: I) -> std::io::Result<()>
+    where
+        W: Write,
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for (index, field) in fields.into_iter().enumerate() {
+            if index > 0 {
+                writer.write_all(b",")?;
+            }
+            writer.write_all(escape(field.as_ref()).as_bytes())?;
+        }
+        writer.write_all(b"\r\n")
+    }
+
+    /// Renders the value as CSV field where NULL becomes an empty field and control characters
+    /// of strings are escaped, so that every row stays on a single line.
+    pub(crate) fn field(value: Option<&Value>) -> String {
+        let options = FormatOptions {
+            null: NullFormat::Empty,
+            escape_control_characters: true,
+            ..FormatOptions::default()
+        };
+
+        value
+            .map(|value| value.to_display_string(&options))
+            .unwrap_or_default()
+    }
+
+    fn escape(field: &str) -> Cow<'_, str> {
+        if field.contains(&[',', '"', '\r', '\n'][..]) {
+            Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+        } else {
+            Cow::Borrowed(field)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use pretty_assertions::assert_eq;
+
+        #[test]
+        fn write_escaped_record() {
+            let mut buffer = Vec::new();
+
+            write_record(
+                &mut buffer,
+                vec![
+                    field(Some(&Value::Int(42))),
+                    field(Some(&Value::Null)),
+                    field(Some(&Value::String(String::from(
+                        "Rebenring 56, Braunschweig",
+                    )))),
+                    field(Some(&Value::String(String::from("8\" Pipe")))),
+                ],
+            )
+            .unwrap();
+
+            assert_eq!(
+                String::from_utf8(buffer).unwrap(),
+                "42,,\"Rebenring 56, Braunschweig\",\"8\"\" Pipe\"\r\n"
+            );
+        }
+    }
+}
+
+pub(crate) mod json {
+    use crate::format::{DateFormat, FormatOptions};
+    use crate::schema::ColumnInfo;
+    use crate::{Row, Value};
+
+    /// Renders the row as JSON object with the columns in schema order.
+    pub(crate) fn object(columns: &[ColumnInfo], row: &Row) -> String {
+        let members = columns
+            .iter()
+            .map(|column| {
+                format!(
+                    "{}:{}",
+                    string(&column.name),
+                    row.value(&column.name)
+                        .map_or_else(|| String::from("null"), value)
+                )
+            })
+            .collect::<Vec<_>>();
+
+        format!("{{{}}}", members.join(","))
+    }
+
+    /// Renders the value as JSON number, `null` or string. Strings escape control characters
+    /// themselves, so the options leave them untouched.
+    pub(crate) fn value(value: &Value) -> String {
+        let options = FormatOptions {
+            date: DateFormat::Rfc3339,
+            ..FormatOptions::default()
+        };
+        let rendered = value.to_display_string(&options);
+
+        match value {
+            Value::Bit(_)
+            | Value::TinyInt(_)
+            | Value::SmallInt(_)
+            | Value::Int(_)
+            | Value::BigInt(_)
+            | Value::Decimal(_)
+            | Value::Money(_)
+            | Value::Null => rendered,
+            // JSON has no representation of NaN and the infinities
+            Value::Float(f) if f.is_finite() => rendered,
+            Value::Float(_)
+            | Value::String(_)
+            | Value::DateTime(_)
+            | Value::DateTimeOffset(_)
+            | Value::Date(_)
+            | Value::Time(_)
+            | Value::Uuid(_)
+            | Value::Binary(_)
+            | Value::RowVersion(_)
+            | Value::Xml(_)
+            | Value::Rid { .. } => string(&rendered),
+        }
+    }
+
+    fn string(s: &str) -> String {
+        let mut escaped = String::with_capacity(s.len() + 2);
+        escaped.push('"');
+        for c in s.chars() {
+            match c {
+                '"' => escaped.push_str("\\\""),
+                '\\' => escaped.push_str("\\\\"),
+                '\n' => escaped.push_str("\\n"),
+                '\r' => escaped.push_str("\\r"),
+                '\t' => escaped.push_str("\\t"),
+                c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+                c => escaped.push(c),
+            }
+        }
+        escaped.push('"');
+        escaped
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use pretty_assertions::assert_eq;
+        use rust_decimal::Decimal;
+
+        #[test]
+        fn row_as_object() {
+            let columns = vec![
+                ColumnInfo::with_type("ID", "int", 4),
+                ColumnInfo::with_type("Name", "nvarchar", 100),
+                ColumnInfo::with_type("Price", "decimal", 5).with_precision(10, 2),
+                ColumnInfo::with_type("Note", "nvarchar", 100),
+            ];
+            let row = Row {
+                columns: vec![
+                    (String::from("ID"), Value::Int(1)),
+                    (
+                        String::from("Name"),
+                        Value::String(String::from("\"Quoted\"\n")),
+                    ),
+                    (String::from("Price"), Value::Decimal(Decimal::new(1250, 2))),
+                    (String::from("Note"), Value::Null),
+                ]
+                .into_iter()
+                .collect(),
+            };
+
+            assert_eq!(
+                object(&columns, &row),
+                r#"{"ID":1,"Name":"\"Quoted\"\n","Price":12.50,"Note":null}"#
+            );
+        }
+    }
+}
+
+pub(crate) mod sql {
+    use crate::format::{DateFormat, FormatOptions, NullFormat};
+    use crate::Value;
+    use std::io::Write;
+
+    /// Writes an `INSERT` statement of the values into the columns of the table.
+    pub(crate) fn write_insert<W, C, V>(
+        writer: &mut W,
+        table_name: &str,
+        column_names: C,
+        values: V,
+    ) -> std::io::Result<()>
+    where
+        W: Write,
+        C: IntoIterator,
+        C::Item: AsRef<str>,
+        V: IntoIterator<Item = String>,
+    {
+        let column_names = column_names
+            .into_iter()
+            .map(|name| identifier(name.as_ref()))
+            .collect::<Vec<_>>();
+        let values = values.into_iter().collect::<Vec<_>>();
+
+        writeln!(
+            writer,
+            "INSERT INTO {} ({}) VALUES ({});",
+            identifier(table_name),
+            column_names.join(", "),
+            values.join(", ")
+        )
+    }
+
+    /// Writes the statement that allows or forbids inserting values into the identity column of
+    /// the table.
+    pub(crate) fn write_identity_insert<W: Write>(
+        writer: &mut W,
+        table_name: &str,
+        on: bool,
+    ) -> std::io::Result<()> {
+        writeln!(
+            writer,
+            "SET IDENTITY_INSERT {} {};",
+            identifier(table_name),
+            if on { "ON" } else { "OFF" }
+        )
+    }
+
+    /// Renders the value as T-SQL literal: `NULL`, numbers as they are, bits as `1` or `0` and
+    /// binary values as `0x` followed by their hex digits. All other values are quoted, e.g.
+    /// strings as `N'Bothell'` and dates as `'2004-06-01T00:00:00'`, which SQL Server reads
+    /// regardless of the date format of the session.
+    pub(crate) fn literal(value: Option<&Value>) -> String {
+        let options = FormatOptions {
+            null: NullFormat::Uppercase,
+            date: DateFormat::Iso8601,
+            ..FormatOptions::default()
+        };
+        let value = match value {
+            Some(value) => value,
+            None => return Value::Null.to_display_string(&options),
+        };
+        let rendered = value.to_display_string(&options);
+
+        match value {
+            Value::Bit(bit) => String::from(if *bit { "1" } else { "0" }),
+            Value::TinyInt(_)
+            | Value::SmallInt(_)
+            | Value::Int(_)
+            | Value::BigInt(_)
+            | Value::Decimal(_)
+            | Value::Money(_)
+            | Value::Binary(_)
+            | Value::RowVersion(_)
+            | Value::Xml(_)
+            | Value::Null => rendered,
+            // T-SQL has no literal of NaN and the infinities, inserting them fails
+            Value::Float(f) if f.is_finite() => rendered,
+            Value::String(_) => format!("N{}", quote(&rendered)),
+            Value::Float(_)
+            | Value::DateTime(_)
+            | Value::DateTimeOffset(_)
+            | Value::Date(_)
+            | Value::Time(_)
+            | Value::Uuid(_)
+            | Value::Rid { .. } => quote(&rendered),
+        }
+    }
+
+    fn quote(s: &str) -> String {
+        format!("'{}'", s.replace('\'', "''"))
+    }
+
+    fn identifier(name: &str) -> String {
+        format!("[{}]", name.replace(']', "]]"))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use chrono::{NaiveDate, TimeZone, Utc};
+        use pretty_assertions::assert_eq;
+        use rstest::rstest;
+
+        #[test]
+        fn write_insert_statement() {
+            let mut buffer = Vec::new();
+
+            write_insert(
+                &mut buffer,
+                "Order Details]",
+                vec!["ID", "Name"],
+                vec![
+                    literal(Some(&Value::Int(1))),
+                    literal(Some(&Value::String(String::from("O'Brien")))),
+                ],
+            )
+            .unwrap();
+
+            assert_eq!(
+                String::from_utf8(buffer).unwrap(),
+                "INSERT INTO [Order Details]]] ([ID], [Name]) VALUES (1, N'O''Brien');\n"
+            );
+        }
+
+        #[rstest(
+            value,
+            expected,
+            case(None, "NULL"),
+            case(Some(Value::Null), "NULL"),
+            case(Some(Value::Bit(true)), "1"),
+            case(Some(Value::Int(-42)), "-42"),
+            case(Some(Value::String(String::from("It's"))), "N'It''s'"),
+            case(Some(Value::Binary(vec![0xbe, 0xef])), "0xBEEF"),
+            case(Some(Value::Float(f64::NAN)), "'NaN'"),
+            case(
+                Some(Value::DateTime(Utc.from_utc_datetime(
+                    &NaiveDate::from_ymd_opt(2004, 6, 1)
+                        .unwrap()
+                        .and_hms_opt(13, 14, 15)
+                        .unwrap()
+                ))),
+                "'2004-06-01T13:14:15'"
+            )
+        )]
+        fn render_literal(value: Option<Value>, expected: &str) {
+            assert_eq!(literal(value.as_ref()), expected);
+        }
+    }
+}
+
+#[cfg(feature = "arrow")]
+pub(crate) mod record_batch {
+    use crate::schema::ColumnInfo;
+    use crate::{Row, Value};
+    use arrow::array::{
+        ArrayRef, BooleanBuilder, Date32Builder, Decimal128Builder, Float64Builder, Int16Builder,
+        Int32Builder, Int64Builder, Int8Builder, StringBuilder, TimestampMicrosecondBuilder,
+    };
+    use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+    use arrow::error::ArrowError;
+    use arrow::record_batch::RecordBatch;
+    use chrono::NaiveDate;
+    use std::sync::Arc;
+
+    /// Maps the SQL base type of the column to the corresponding Arrow data type.
+    pub(crate) fn data_type(column: &ColumnInfo) -> DataType {
+        match column.base_type_name.as_str() {
+            "bit" => DataType::Boolean,
+            "tinyint" => DataType::Int8,
+            "smallint" => DataType::Int16,
+            "int" => DataType::Int32,
+            "money" => DataType::Decimal128(19, 4),
+            "smallmoney" => DataType::Decimal128(10, 4),
+            "bigint" => DataType::Int64,
+            "real" | "float" => DataType::Float64,
+            "decimal" | "numeric" => DataType::Decimal128(column.precision, column.scale as i8),
+            "datetime" | "datetime2" => DataType::Timestamp(TimeUnit::Microsecond, None),
+            "date" => DataType::Date32,
+            _ => DataType::Utf8,
+        }
+    }
+
+    pub(crate) fn schema(columns: &[ColumnInfo]) -> Schema {
+        Schema::new(
+            columns
+                .iter()
+                .map(|column| Field::new(&column.name, data_type(column), true))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// Builds a record batch from the given rows, with one Arrow column per table column in
+    /// schema order. NULL values and values missing from a row become nulls of the array.
+    pub(crate) fn record_batch(
+        columns: &[ColumnInfo],
+        rows: &[Row],
+    ) -> Result<RecordBatch, ArrowError> {
+        let schema = Arc::new(schema(columns));
+
+        let arrays = columns
+            .iter()
+            .zip(schema.fields().iter())
+            .map(|(column, field)| {
+                let values = rows
+                    .iter()
+                    .map(|row| match row.value(&column.name) {
+                        Some(Value::Null) | None => None,
+                        Some(value) => Some(value),
+                    })
+                    .collect::<Vec<_>>();
+
+                array(&column.name, field.data_type(), &values)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        RecordBatch::try_new(schema, arrays)
+    }
+
+    macro_rules! build_array {
+        ( $builder:expr, $column:expr, $values:expr, $( $pattern:pat => $value:expr ),+ ) => {{
+            let mut builder = $builder;
+            for value in $values {
+                match value {
+                    None => builder.append_null(),
+                    $( Some($pattern) => builder.append_value($value), )+
+                    Some(value) => return Err(unexpected_value($column, value)),
+                }
+            }
+            Arc::new(builder.finish()) as ArrayRef
+        }};
+    }
+
+    fn array(
+        column: &str,
+        data_type: &DataType,
+        values: &[Option<&Value>],
+    ) -> Result<ArrayRef, ArrowError> {
+        let array = match data_type {
+            DataType::Boolean => {
+                build_array!(BooleanBuilder::new(), column, values, Value::Bit(b) => *b)
+            }
+            DataType::Int8 => {
+                build_array!(Int8Builder::new(), column, values, Value::TinyInt(i) => *i)
+            }
+            DataType::Int16 => {
+                build_array!(Int16Builder::new(), column, values, Value::SmallInt(i) => *i)
+            }
+            DataType::Int32 => {
+                build_array!(Int32Builder::new(), column, values, Value::Int(i) => *i)
+            }
+            DataType::Int64 => {
+                build_array!(Int64Builder::new(), column, values, Value::BigInt(i) => *i)
+            }
+            DataType::Float64 => {
+                build_array!(Float64Builder::new(), column, values, Value::Float(f) => *f)
+            }
+            DataType::Decimal128(precision, scale) => build_array!(
+                Decimal128Builder::new().with_precision_and_scale(*precision, *scale)?,
+                column,
+                values,
+                Value::Decimal(d) => {
+                    let mut d = *d;
+                    d.rescale(*scale as u32);
+                    d.mantissa()
+                },
+                Value::Money(d) => {
+                    let mut d = *d;
+                    d.rescale(*scale as u32);
+                    d.mantissa()
+                }
+            ),
+            DataType::Date32 => build_array!(
+                Date32Builder::new(),
+                column,
+                values,
+                Value::Date(d) => (*d - NaiveDate::from_ymd_opt(1970, 1, 1).unwrap()).num_days() as i32
+            ),
+            DataType::Timestamp(TimeUnit::Microsecond, None) => build_array!(
+                TimestampMicrosecondBuilder::new(),
+                column,
+                values,
+                Value::DateTime(d) => d.timestamp() * 1_000_000 + d.timestamp_subsec_micros() as i64
+            ),
+            _ => {
+                let mut builder = StringBuilder::new();
+                for value in values {
+                    builder.append_option(value.map(|value| value.to_string()));
+                }
+                Arc::new(builder.finish()) as ArrayRef
+            }
+        };
+
+        Ok(array)
+    }
+
+    fn unexpected_value(column: &str, value: &Value) -> ArrowError {
+        ArrowError::InvalidArgumentError(format!(
+            "Unexpected value {:?} for column {}",
+            value, column
+        ))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use arrow::array::{Array, Decimal128Array, Int32Array, StringArray};
+        use pretty_assertions::assert_eq;
+        use rust_decimal::Decimal;
+
+        #[test]
+        fn record_batch_of_rows() {
+            let columns = vec![
+                ColumnInfo::with_type("ID", "int", 4).with_precision(10, 0),
+                ColumnInfo::with_type("City", "nvarchar", 0),
+                ColumnInfo::with_type("Price", "decimal", 5).with_precision(10, 2),
+            ];
+
+            let rows = vec![
+                Row {
+                    columns: vec![
+                        (String::from("ID"), Value::Int(1)),
+                        (String::from("City"), Value::String(String::from("Bothell"))),
+                        (String::from("Price"), Value::Decimal(Decimal::new(15, 1))),
+                    ]
+                    .into_iter()
+                    .collect(),
+                },
+                Row {
+                    columns: vec![
+                        (String::from("ID"), Value::Int(2)),
+                        (String::from("City"), Value::Null),
+                        (String::from("Price"), Value::Decimal(Decimal::new(1234, 2))),
+                    ]
+                    .into_iter()
+                    .collect(),
+                },
+            ];
+
+            let batch = record_batch(&columns, &rows).unwrap();
+
+            assert_eq!(
+                batch
+                    .schema()
+                    .fields()
+                    .iter()
+                    .map(|f| f.data_type().clone())
+                    .collect::<Vec<_>>(),
+                vec![DataType::Int32, DataType::Utf8, DataType::Decimal128(10, 2)]
+            );
+
+            let ids = batch
+                .column(0)
+                .as_any()
+                .downcast_ref::<Int32Array>()
+                .unwrap();
+            assert_eq!(ids.value(1), 2);
+
+            let cities = batch
+                .column(1)
+                .as_any()
+                .downcast_ref::<StringArray>()
+                .unwrap();
+            assert_eq!(cities.value(0), "Bothell");
+            assert!(cities.is_null(1));
+
+            let prices = batch
+                .column(2)
+                .as_any()
+                .downcast_ref::<Decimal128Array>()
+                .unwrap();
+            assert_eq!(prices.value(0), 150);
+        }
+    }
+}
+
+#[cfg(feature = "parquet")]
+pub(crate) mod parquet_file {
+    use super::record_batch::{record_batch, schema};
+    use crate::error::Error;
+    use crate::schema::ColumnInfo;
+    use crate::Row;
+    use parquet::arrow::ArrowWriter;
+    use std::fs::File;
+    use std::sync::Arc;
+
+    /// The number of rows which are buffered before they are written as one record batch.
+    pub(crate) const BATCH_SIZE: usize = 8192;
+
+    /// Writes rows into a Parquet file in batches of [`BATCH_SIZE`] rows.
+    pub(crate) struct ParquetExport {
+        columns: Vec<ColumnInfo>,
+        writer: ArrowWriter<File>,
+        rows: Vec<Row>,
+    }
+
+    impl ParquetExport {
+        pub(crate) fn new(columns: Vec<ColumnInfo>, file: File) -> Result<Self, Error> {
+            let writer = ArrowWriter::try_new(file, Arc::new(schema(&columns)), None)?;
+
+            Ok(Self {
+                columns,
+                writer,
+                rows: Vec::with_capacity(BATCH_SIZE),
+            })
+        }
+
+        pub(crate) fn push(&mut self, row: Row) -> Result<(), Error> {
+            self.rows.push(row);
+
+            if self.rows.len() >= BATCH_SIZE {
+                self.flush()?;
+            }
+
+            Ok(())
+        }
+
+        fn flush(&mut self) -> Result<(), Error> {
+            if !self.rows.is_empty() {
+                let batch = record_batch(&self.columns, &self.rows)?;
+                self.writer.write(&batch)?;
+                self.rows.clear();
+            }
+
+            Ok(())
+        }
+
+        pub(crate) fn finish(mut self) -> Result<(), Error> {
+            self.flush()?;
+            self.writer.close()?;
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::Value;
+        use arrow::array::{Array, Int32Array};
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+        use pretty_assertions::assert_eq;
+
+        #[test]
+        fn write_rows_in_batches() {
+            let columns = vec![ColumnInfo::with_type("ID", "int", 4).with_precision(10, 0)];
+
+            let path = std::env::temp_dir().join("oxidized-mdf-write-rows-in-batches.parquet");
+
+            let mut export = ParquetExport::new(columns, File::create(&path).unwrap()).unwrap();
+            for id in 0..(BATCH_SIZE as i32 + 10) {
+                let row = Row {
+                    columns: std::iter::once((String::from("ID"), Value::Int(id))).collect(),
+                };
+                export.push(row).unwrap();
+            }
+            export.finish().unwrap();
+
+            let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(&path).unwrap())
+                .unwrap()
+                .build()
+                .unwrap();
+            let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+
+            assert_eq!(
+                batches.iter().map(|b| b.num_rows()).sum::<usize>(),
+                BATCH_SIZE + 10
+            );
+
+            let last_batch = batches.last().unwrap();
+            let ids = last_batch
+                .column(0)
+                .as_any()
+                .downcast_ref::<Int32Array>()
+                .unwrap();
+            assert_eq!(ids.value(ids.len() - 1), BATCH_SIZE as i32 + 9);
+
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{csv, json, sql};
+    use crate::Value;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use rust_decimal::Decimal;
+    use uuid::Uuid;
+
+    /// Removes the quotes that JSON and SQL put around strings.
+    fn unquote(s: &str) -> &str {
+        s.trim_start_matches('N')
+            .trim_matches(|c| c == '"' || c == '\'')
+    }
+
+    #[rstest(
+        value,
+        case(Value::Int(-42)),
+        case(Value::Decimal(Decimal::new(1250, 2))),
+        case(Value::Money(Decimal::new(15, 1))),
+        case(Value::Float(2.5)),
+        case(Value::Uuid(Uuid::from_u128(0x67e5504410b1426f9247bb680e5fe0c8))),
+        case(Value::Binary(vec![0xbe, 0xef])),
+        case(Value::String(String::from("Bothell")))
+    )]
+    fn render_values_alike_in_all_exporters(value: Value) {
+        let csv = csv::field(Some(&value));
+
+        assert_eq!(unquote(&json::value(&value)), csv);
+        assert_eq!(unquote(&sql::literal(Some(&value))), csv);
+        assert_eq!(csv, value.to_string());
+    }
+}
@@ -0,0 +1,341 @@
+//! Configurable rendering of [`Value`]s, e.g. for exporters that need a certain NULL or date
+//! representation.
+
+use crate::Value;
+use chrono::{DateTime, TimeZone, Utc};
+use core::fmt::{Display, Formatter};
+use rust_decimal::RoundingStrategy;
+
+/// Controls how [`Value::display_with`] renders values. The default renders values exactly
+/// like the `Display` implementation of `Value`.
+///
+/// ```rust
+/// use oxidized_mdf::format::{FormatOptions, NullFormat};
+/// use oxidized_mdf::Value;
+///
+/// let options = FormatOptions {
+///     null: NullFormat::Uppercase,
+///     ..FormatOptions::default()
+/// };
+///
+/// assert_eq!(Value::Null.display_with(&options).to_string(), "NULL");
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FormatOptions {
+    pub null: NullFormat,
+    /// Rounds or pads decimals to the given number of decimal places.
+    pub decimal_scale: Option<u32>,
+    pub date: DateFormat,
+    pub guid: GuidFormat,
+    /// Renders control characters of strings as escape sequences, e.g. `\n`, `\t` or `\u{0}`,
+    /// so that embedded newlines or NULs cannot corrupt line based output.
+    pub escape_control_characters: bool,
+}
+
+/// The rendering of `Value::Null`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum NullFormat {
+    /// Renders `null`.
+    #[default]
+    Lowercase,
+    /// Renders `NULL`, as expected by SQL.
+    Uppercase,
+    /// Renders nothing, as expected by CSV.
+    Empty,
+}
+
+/// The rendering of `Value::DateTime` and `Value::DateTimeOffset`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum DateFormat {
+    /// Renders the date like `2004-06-01 00:00:00 UTC`.
+    #[default]
+    Display,
+    /// Renders the date according to RFC 3339, e.g. `2004-06-01T00:00:00+00:00`.
+    Rfc3339,
+    /// Renders the date according to ISO 8601 without time zone, e.g. `2004-06-01T00:00:00`,
+    /// because `datetime` and `datetime2` values do not store one. Fractional seconds are only
+    /// rendered if present.
+    Iso8601,
+    /// Renders the date according to ISO 8601 with the offset of its time zone, e.g.
+    /// `2004-06-01T13:14:15+02:00`. `datetimeoffset` values keep their stored offset, all other
+    /// dates are in UTC.
+    Iso8601WithOffset,
+    /// Renders the milliseconds since the Unix epoch, e.g. `1086048000000`, see
+    /// [`Value::as_epoch_millis`].
+    EpochMillis,
+    /// Renders the date with the given `strftime`-like format of `chrono`.
+    Custom(String),
+}
+
+/// The rendering of `Value::Uuid`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum GuidFormat {
+    /// Renders `67e55044-10b1-426f-9247-bb680e5fe0c8`.
+    #[default]
+    Hyphenated,
+    /// Renders `67e5504410b1426f9247bb680e5fe0c8`.
+    Simple,
+    /// Renders `{67e55044-10b1-426f-9247-bb680e5fe0c8}`.
+    Braced,
+}
+
+/// A value that is rendered according to the [`FormatOptions`], see [`Value::display_with`].
+pub struct DisplayValue<'a> {
+    pub(crate) value: &'a Value,
+    pub(crate) options: &'a FormatOptions,
+}
+
+impl Display for DisplayValue<'_> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self.value {
+            Value::Null => match self.options.null {
+                NullFormat::Lowercase => write!(fmt, "null"),
+                NullFormat::Uppercase => write!(fmt, "NULL"),
+                NullFormat::Empty => Ok(()),
+            },
+            Value::Decimal(decimal) | Value::Money(decimal) => match self.options.decimal_scale {
+                Some(scale) => {
+                    let mut decimal = decimal
+                        .round_dp_with_strategy(scale, RoundingStrategy::MidpointAwayFromZero);
+                    decimal.rescale(scale);
+                    write!(fmt, "{}", decimal)
+                }
+                None => write!(fmt, "{}", self.value),
+            },
+            Value::DateTime(d) => write_date(fmt, d, &self.options.date),
+            Value::DateTimeOffset(d) => write_date(fmt, d, &self.options.date),
+            // Dates have no time, so only formats that render a date on its own apply
+            Value::Date(d) => match &self.options.date {
+                DateFormat::EpochMillis => write!(
+                    fmt,
+                    "{}",
+                    Utc.from_utc_datetime(&d.and_hms_opt(0, 0, 0).unwrap())
+                        .timestamp_millis()
+                ),
+                DateFormat::Custom(format) => write!(fmt, "{}", d.format(format)),
+                _ => write!(fmt, "{}", d),
+            },
+            Value::Time(t) => match &self.options.date {
+                DateFormat::Custom(format) => write!(fmt, "{}", t.format(format)),
+                _ => write!(fmt, "{}", t),
+            },
+            Value::Uuid(uuid) => match self.options.guid {
+                GuidFormat::Hyphenated => write!(fmt, "{}", uuid.to_hyphenated_ref()),
+                GuidFormat::Simple => write!(fmt, "{}", uuid.to_simple_ref()),
+                GuidFormat::Braced => write!(fmt, "{{{}}}", uuid.to_hyphenated_ref()),
+            },
+            Value::String(s) if self.options.escape_control_characters => {
+                write!(fmt, "{}", escape_control_characters(s))
+            }
+            value => write!(fmt, "{}", value),
+        }
+    }
+}
+
+fn write_date<Tz>(
+    fmt: &mut Formatter<'_>,
+    d: &DateTime<Tz>,
+    format: &DateFormat,
+) -> Result<(), std::fmt::Error>
+where
+    Tz: TimeZone,
+    Tz::Offset: Display,
+{
+    match format {
+        DateFormat::Display => write!(fmt, "{}", d),
+        DateFormat::Rfc3339 => write!(fmt, "{}", d.to_rfc3339()),
+        DateFormat::Iso8601 => write!(fmt, "{}", d.format("%Y-%m-%dT%H:%M:%S%.f")),
+        DateFormat::Iso8601WithOffset => write!(fmt, "{}", d.format("%Y-%m-%dT%H:%M:%S%.f%:z")),
+        DateFormat::EpochMillis => write!(fmt, "{}", d.timestamp_millis()),
+        DateFormat::Custom(format) => write!(fmt, "{}", d.format(format)),
+    }
+}
+
+fn escape_control_characters(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, FixedOffset, TimeZone, Utc};
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use rust_decimal::Decimal;
+    use uuid::Uuid;
+
+    fn options(null: NullFormat) -> FormatOptions {
+        FormatOptions {
+            null,
+            ..FormatOptions::default()
+        }
+    }
+
+    #[rstest(
+        null,
+        expected,
+        case(NullFormat::Lowercase, "null"),
+        case(NullFormat::Uppercase, "NULL"),
+        case(NullFormat::Empty, "")
+    )]
+    fn display_null(null: NullFormat, expected: &str) {
+        assert_eq!(
+            Value::Null.display_with(&options(null)).to_string(),
+            expected
+        );
+    }
+
+    #[rstest(
+        null,
+        case(NullFormat::Lowercase),
+        case(NullFormat::Uppercase),
+        case(NullFormat::Empty)
+    )]
+    fn display_non_null_independent_of_null_format(null: NullFormat) {
+        assert_eq!(
+            Value::Int(42).display_with(&options(null)).to_string(),
+            "42"
+        );
+    }
+
+    #[rstest(
+        decimal_scale,
+        expected,
+        case(None, "12.345"),
+        case(Some(2), "12.35"),
+        case(Some(5), "12.34500")
+    )]
+    fn display_decimal(decimal_scale: Option<u32>, expected: &str) {
+        let options = FormatOptions {
+            decimal_scale,
+            ..FormatOptions::default()
+        };
+
+        assert_eq!(
+            Value::Decimal(Decimal::new(12345, 3))
+                .display_with(&options)
+                .to_string(),
+            expected
+        );
+    }
+
+    #[rstest(
+        date,
+        expected,
+        case(DateFormat::Display, "2004-06-01 13:14:15 UTC"),
+        case(DateFormat::Rfc3339, "2004-06-01T13:14:15+00:00"),
+        case(DateFormat::Iso8601, "2004-06-01T13:14:15"),
+        case(DateFormat::Iso8601WithOffset, "2004-06-01T13:14:15+00:00"),
+        case(DateFormat::Custom(String::from("%d.%m.%Y")), "01.06.2004")
+    )]
+    fn display_date(date: DateFormat, expected: &str) {
+        let options = FormatOptions {
+            date,
+            ..FormatOptions::default()
+        };
+
+        assert_eq!(
+            Value::DateTime(Utc.with_ymd_and_hms(2004, 6, 1, 13, 14, 15).unwrap())
+                .display_with(&options)
+                .to_string(),
+            expected
+        );
+    }
+
+    #[rstest(
+        date,
+        expected,
+        case(DateFormat::Rfc3339, "2004-06-01T13:14:15.120+00:00"),
+        case(DateFormat::Iso8601, "2004-06-01T13:14:15.120"),
+        case(DateFormat::EpochMillis, "1086095655120")
+    )]
+    fn display_date_with_fractional_seconds(date: DateFormat, expected: &str) {
+        let options = FormatOptions {
+            date,
+            ..FormatOptions::default()
+        };
+
+        assert_eq!(
+            Value::DateTime(
+                Utc.with_ymd_and_hms(2004, 6, 1, 13, 14, 15).unwrap() + Duration::milliseconds(120)
+            )
+            .display_with(&options)
+            .to_string(),
+            expected
+        );
+    }
+
+    #[rstest(
+        date,
+        expected,
+        case(DateFormat::Display, "2004-06-01 13:14:15 +02:00"),
+        case(DateFormat::Rfc3339, "2004-06-01T13:14:15+02:00"),
+        case(DateFormat::Iso8601, "2004-06-01T13:14:15"),
+        case(DateFormat::Iso8601WithOffset, "2004-06-01T13:14:15+02:00"),
+        case(DateFormat::EpochMillis, "1086088455000")
+    )]
+    fn display_datetimeoffset_with_stored_offset(date: DateFormat, expected: &str) {
+        let options = FormatOptions {
+            date,
+            ..FormatOptions::default()
+        };
+        let offset = FixedOffset::east_opt(2 * 3600).unwrap();
+
+        assert_eq!(
+            Value::DateTimeOffset(offset.with_ymd_and_hms(2004, 6, 1, 13, 14, 15).unwrap())
+                .display_with(&options)
+                .to_string(),
+            expected
+        );
+    }
+
+    #[rstest(
+        guid,
+        expected,
+        case(GuidFormat::Hyphenated, "67e55044-10b1-426f-9247-bb680e5fe0c8"),
+        case(GuidFormat::Simple, "67e5504410b1426f9247bb680e5fe0c8"),
+        case(GuidFormat::Braced, "{67e55044-10b1-426f-9247-bb680e5fe0c8}")
+    )]
+    fn display_guid(guid: GuidFormat, expected: &str) {
+        let options = FormatOptions {
+            guid,
+            ..FormatOptions::default()
+        };
+        let uuid = Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+
+        assert_eq!(
+            Value::Uuid(uuid).display_with(&options).to_string(),
+            expected
+        );
+    }
+
+    #[rstest(
+        escape_control_characters,
+        expected,
+        case(false, "first\nsecond\0"),
+        case(true, "first\\nsecond\\u{0}")
+    )]
+    fn display_string_with_control_characters(escape_control_characters: bool, expected: &str) {
+        let options = FormatOptions {
+            escape_control_characters,
+            ..FormatOptions::default()
+        };
+
+        assert_eq!(
+            Value::String(String::from("first\nsecond\0"))
+                .display_with(&options)
+                .to_string(),
+            expected
+        );
+    }
+}
@@ -0,0 +1,173 @@
+//! Entry points for fuzzing the page and record parsers, see `fuzz/` for the `cargo-fuzz`
+//! targets. Not part of the public API.
+
+use crate::options::Options;
+use crate::pages::{BootPage, Page, Record};
+use crate::sys::Column;
+use crate::Value;
+use std::convert::TryFrom;
+
+const PAGE_SIZE: usize = 8192;
+
+/// The type name, max length, precision and scale of columns covering every decodable
+/// [`ColumnType`](crate::schema::ColumnType) and their differently stored variants.
+const COLUMNS: &[(&str, i16, u8, u8)] = &[
+    ("bit", 1, 0, 0),
+    ("tinyint", 1, 0, 0),
+    ("smallint", 2, 0, 0),
+    ("int", 4, 0, 0),
+    ("bigint", 8, 0, 0),
+    ("money", 8, 0, 0),
+    ("smallmoney", 4, 0, 0),
+    ("decimal", 5, 9, 2),
+    ("decimal", 9, 19, 4),
+    ("decimal", 13, 28, 4),
+    ("decimal", 17, 38, 4),
+    ("real", 4, 0, 0),
+    ("float", 8, 0, 0),
+    ("char", 2, 0, 0),
+    ("nchar", 4, 0, 0),
+    ("nvarchar", -1, 0, 0),
+    ("varchar", 50, 0, 0),
+    ("date", 3, 0, 0),
+    ("datetime", 8, 0, 0),
+    ("datetime2", 6, 0, 0),
+    ("datetime2", 8, 0, 7),
+    ("datetimeoffset", 10, 0, 7),
+    ("time", 3, 0, 0),
+    ("time", 5, 0, 7),
+    ("uniqueidentifier", 16, 0, 0),
+    ("binary", 4, 0, 0),
+    ("varbinary", -1, 0, 0),
+    ("timestamp", 8, 0, 0),
+    ("xml", -1, 0, 0),
+    ("RID", 8, 0, 0),
+];
+
+/// Parses the given bytes as page, boot page and IAM page and runs every record of the page
+/// through the column parsers. Input shorter than a page is padded with zeros, longer input is
+/// truncated.
+///
+/// Malformed input must result in errors, never in a panic.
+pub fn parse_page(data: &[u8]) {
+    let mut bytes = [0u8; PAGE_SIZE];
+    let len = std::cmp::min(data.len(), PAGE_SIZE);
+    bytes[..len].copy_from_slice(&data[..len]);
+
+    let _ = BootPage::try_from(bytes);
+
+    let page = match Page::try_from(bytes) {
+        Ok(page) => page,
+        Err(_) => return,
+    };
+
+    let _ = page.next_page_pointer();
+    let _ = page.iam_allocated_pages();
+
+    for record in page.records() {
+        parse_record(record);
+    }
+}
+
+/// Parses the given bytes as a single record and runs it through the column parsers.
+pub fn parse_record_bytes(data: &[u8]) {
+    if let Ok(record) = Record::try_from(data) {
+        parse_record(record);
+    }
+}
+
+fn parse_record(record: Record<'_>) {
+    let options = Options::default();
+
+    // Every column consumes a null bit, so a column count beyond the null bitmap is covered too
+    let mut record = record;
+    for index in 0..record.number_of_columns() + 1 {
+        let (r#type, max_length, precision, scale) = COLUMNS[index % COLUMNS.len()];
+        let mut column = Column {
+            name: "Value",
+            r#type,
+            xtype: 0,
+            max_length,
+            precision,
+            scale,
+            default_expr: None,
+            check_expr: None,
+            identity: None,
+            computed: false,
+            persisted: false,
+            leaf_offset: None,
+            leaf_bit_position: None,
+        };
+
+        // Every other round of bits is read at a leaf offset like packed bits of sysrscols
+        let current = if r#type == "bit" && index / COLUMNS.len() % 2 == 1 {
+            column.leaf_offset = Some(4);
+            column.leaf_bit_position = Some((index % 8) as u8);
+            record.seek_fixed_offset(4)
+        } else {
+            Ok(record)
+        };
+
+        record = match current.and_then(|r| Value::parse(&column, r, &options)) {
+            Ok((_, record)) => record,
+            Err(_) => return,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A xorshift generator to get reproducible pseudo-random pages without extra dependencies.
+    struct XorShift(u64);
+
+    impl XorShift {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+    }
+
+    #[test]
+    fn parse_random_pages_without_panic() {
+        let mut rng = XorShift(0x2545_F491_4F6C_DD1D);
+
+        for _ in 0..200 {
+            let mut bytes = vec![0u8; PAGE_SIZE];
+            bytes.iter_mut().for_each(|b| *b = rng.next() as u8);
+
+            // Plausible slot counts and offsets make it past the page header checks
+            let slot_count = (rng.next() % 64) as u16;
+            bytes[1] = [1, 10][(rng.next() % 2) as usize];
+            bytes[22..24].copy_from_slice(&slot_count.to_le_bytes());
+            for slot in 0..slot_count as usize {
+                let offset = 96 + (rng.next() % 8000) as u16;
+                let position = PAGE_SIZE - 2 * (slot + 1);
+                bytes[position..position + 2].copy_from_slice(&offset.to_le_bytes());
+            }
+
+            parse_page(&bytes);
+        }
+    }
+
+    #[test]
+    fn parse_random_records_without_panic() {
+        let mut rng = XorShift(0x9E37_79B9_7F4A_7C15);
+
+        for _ in 0..10_000 {
+            let len = (rng.next() % 64) as usize;
+            let mut bytes = (0..len).map(|_| rng.next() as u8).collect::<Vec<_>>();
+
+            // Keep the fixed length size within the record most of the time
+            if len >= 4 && rng.next() % 4 < 3 {
+                let fixed_length_size = 4 + (rng.next() % len as u64) as u16;
+                bytes[2..4].copy_from_slice(&fixed_length_size.to_le_bytes());
+            }
+
+            parse_record_bytes(&bytes);
+        }
+    }
+}
@@ -19,13 +19,60 @@
 
 #![warn(rust_2018_idioms)]
 
+// Without the log feature diagnostics are discarded
+#[cfg(not(feature = "log"))]
+macro_rules! warn {
+    ($($arg:tt)+) => {{
+        let _ = format_args!($($arg)+);
+    }};
+}
+
+#[cfg(not(feature = "log"))]
+macro_rules! debug {
+    ($($arg:tt)+) => {{
+        let _ = format_args!($($arg)+);
+    }};
+}
+
+#[cfg(not(feature = "log"))]
+macro_rules! error {
+    ($($arg:tt)+) => {{
+        let _ = format_args!($($arg)+);
+    }};
+}
+
+#[cfg(not(feature = "log"))]
+macro_rules! span {
+    ($name:expr, $arg:expr, $block:block) => {{
+        let _ = format_args!($name, $arg);
+        $block
+    }};
+}
+
+#[cfg(feature = "compression")]
+mod compression;
 pub mod error;
+mod export;
+pub mod format;
+#[cfg(any(test, feature = "fuzzing"))]
+#[doc(hidden)]
+pub mod fuzzing;
+pub mod options;
 mod pages;
+pub mod schema;
 mod sys;
 
+#[cfg(feature = "compression")]
+use crate::compression::{CompressedRecord, CompressionInfo};
 use crate::error::Error;
-use crate::pages::{BootPage, Page, PagePointer, Record};
-use crate::sys::{BaseTableData, Column};
+use crate::format::{DisplayValue, FormatOptions};
+use crate::options::{Options, ROW_INDEX_COLUMN};
+use crate::pages::{
+    lob_links, BootPage, LobPages, LobRecord, Page, PagePointer, Record, BOOT_PAGE, DATA_PAGE,
+};
+use crate::schema::{ColumnInfo, ColumnType, SchemaDiff, TableHandle};
+use crate::sys::{BaseTableData, Column, Table};
+#[cfg(feature = "log")]
 use async_log::span;
 use async_std::fs::File;
 use async_std::io::Read;
@@ -33,13 +80,17 @@ use async_std::path::{Path, PathBuf};
 use async_std::prelude::*;
 use async_std::stream::Stream;
 use async_std::task::{Context, Poll};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime, TimeZone, Utc};
 use core::fmt::{Display, Formatter};
 use futures_lite::stream::StreamExt;
-use log::error;
+#[cfg(feature = "log")]
+use log::{debug, warn};
+use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::convert::TryFrom;
+use std::future::Future;
+use std::hash::{Hash, Hasher};
 use std::pin::Pin;
 use std::rc::Rc;
 use uuid::Uuid;
@@ -48,10 +99,36 @@ pub struct MdfDatabase {
     page_reader: PageReader,
     boot_page: BootPage,
     pub(crate) base_table_data: BaseTableData,
+    options: Options,
 }
 
 impl MdfDatabase {
     pub async fn open<P>(p: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        Self::open_with_options(p, Options::default()).await
+    }
+
+    /// Opens the database file like [`open`](Self::open) but decodes the rows according to
+    /// the given options.
+    ///
+    /// ```rust
+    /// use oxidized_mdf::MdfDatabase;
+    /// use oxidized_mdf::options::{EmptyStringMode, Options};
+    ///
+    /// # #[async_std::main]
+    /// # async fn main() {
+    /// let options = Options {
+    ///     empty_string_mode: EmptyStringMode::AsEmptyString,
+    ///     ..Options::default()
+    /// };
+    /// let db = MdfDatabase::open_with_options("data/AWLT2005.mdf", options)
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    pub async fn open_with_options<P>(p: P, options: Options) -> Result<Self, Error>
     where
         P: AsRef<Path>,
     {
@@ -59,26 +136,70 @@ impl MdfDatabase {
         path.push(p);
 
         let file = File::open(&path).await?;
-        Ok(Self::from_read(Box::new(file)).await?)
+
+        // Files cut off while copying end within a page
+        let length = file.metadata().await?.len();
+        if length % 8192 != 0 {
+            if options.strict {
+                return Err(Error::TruncatedFile { length });
+            }
+            warn!(
+                "The length {} of {:?} is no multiple of the page size, the file might be truncated",
+                length, path
+            );
+        }
+
+        let mut db = Self::from_read(Box::new(file)).await?;
+        db.options = options;
+        Ok(db)
+    }
+
+    /// Opens the database file like [`open`](Self::open) and verifies that it contains the
+    /// database with the given name, e.g. to detect misconfigured paths in batch jobs.
+    ///
+    /// ```rust
+    /// use oxidized_mdf::error::Error;
+    /// use oxidized_mdf::MdfDatabase;
+    ///
+    /// # #[async_std::main]
+    /// # async fn main() {
+    /// let result = MdfDatabase::open_expecting("data/AWLT2005.mdf", "Northwind").await;
+    /// assert!(matches!(result, Err(Error::UnexpectedDatabase { .. })));
+    /// # }
+    /// ```
+    pub async fn open_expecting<P>(p: P, expected_name: &str) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let db = Self::open(p).await?;
+        if db.database_name() != expected_name {
+            return Err(Error::UnexpectedDatabase {
+                found: db.database_name().to_string(),
+                expected: expected_name.to_string(),
+            });
+        }
+        Ok(db)
     }
 
     pub async fn from_read(read: Box<dyn Read + Unpin>) -> Result<Self, Error> {
-        let mut buffer = [0u8; 8192];
         let mut page_reader = PageReader::new(read);
 
-        // Skipping first headers
-        for _i in 0u8..9u8 {
-            page_reader.read_next_page(&mut buffer).await?;
-        }
-        page_reader.read_next_page(&mut buffer).await?;
-
-        let boot_page = BootPage::try_from(buffer).unwrap();
+        let boot_page = page_reader.read_boot_page().await?;
         let base_table_data = BaseTableData::parse(&mut page_reader, &boot_page).await?;
 
+        let data_file_names = base_table_data.data_file_names();
+        if data_file_names.len() > 1 {
+            warn!(
+                "The database consists of the data files {:?} but only the primary file is read, pages of other files cannot be resolved",
+                data_file_names
+            );
+        }
+
         Ok(Self {
             page_reader,
             boot_page,
             base_table_data,
+            options: Options::default(),
         })
     }
 
@@ -86,6 +207,58 @@ impl MdfDatabase {
         &self.boot_page.database_name
     }
 
+    /// Returns the SQL Server release whose on-disk format the database uses, derived from the
+    /// internal database version of the boot page. Returns `None` for unknown versions.
+    ///
+    /// ```rust
+    /// # use oxidized_mdf::MdfDatabase;
+    /// # #[async_std::main]
+    /// # async fn main() {
+    /// let db = MdfDatabase::open("data/AWLT2005.mdf").await.unwrap();
+    /// assert_eq!(db.sql_server_version().as_deref(), Some("SQL Server 2005"));
+    /// # }
+    /// ```
+    pub fn sql_server_version(&self) -> Option<String> {
+        self.boot_page.sql_server_version().map(String::from)
+    }
+
+    /// Returns the number of data files of the database. Databases consisting of multiple data
+    /// files cannot be read completely because only the primary file is opened.
+    ///
+    /// ```rust
+    /// # use oxidized_mdf::MdfDatabase;
+    /// # #[async_std::main]
+    /// # async fn main() {
+    /// let db = MdfDatabase::open("data/AWLT2005.mdf").await.unwrap();
+    /// assert_eq!(db.file_count(), 1);
+    /// # }
+    /// ```
+    pub fn file_count(&self) -> usize {
+        self.base_table_data.data_file_names().len().max(1)
+    }
+
+    /// Returns the physical file names of the data files of the database as recorded at the
+    /// time the database was attached, starting with the primary file.
+    pub fn data_file_names(&self) -> Vec<String> {
+        self.base_table_data.data_file_names()
+    }
+
+    /// Returns the I/O counters of all reads from the database file since it has been opened,
+    /// e.g. to diagnose slow scans.
+    ///
+    /// ```rust
+    /// # use oxidized_mdf::MdfDatabase;
+    /// # #[async_std::main]
+    /// # async fn main() {
+    /// let db = MdfDatabase::open("data/AWLT2005.mdf").await.unwrap();
+    /// let io_stats = db.io_stats();
+    /// assert_eq!(io_stats.bytes_read, io_stats.pages_read * 8192);
+    /// # }
+    /// ```
+    pub fn io_stats(&self) -> IoStats {
+        self.page_reader.io_stats
+    }
+
     /// Returns the table names of this database file.
     ///
     /// ```rust
@@ -101,7 +274,45 @@ impl MdfDatabase {
         self.base_table_data.tables()
     }
 
-    /// Returns the column names of the given table name.
+    /// Returns the table names of this database file matching the given pattern. The pattern
+    /// is a glob supporting `*` and `?`; a pattern without wildcards matches as a prefix.
+    ///
+    /// ```rust
+    /// # use oxidized_mdf::MdfDatabase;
+    /// # #[async_std::main]
+    /// # async fn main() {
+    /// let db = MdfDatabase::open("data/AWLT2005.mdf").await.unwrap();
+    /// let table_names = db.table_names_matching("Product*");
+    /// assert!(table_names.contains(&String::from("ProductCategory")));
+    /// # }
+    /// ```
+    pub fn table_names_matching(&self, pattern: &str) -> Vec<String> {
+        self.table_names()
+            .into_iter()
+            .filter(|table_name| matches_pattern(table_name, pattern))
+            .collect()
+    }
+
+    /// Returns the estimated number of rows of all tables in this database file, based on the
+    /// row counts stored in the system tables.
+    ///
+    /// ```rust
+    /// # use oxidized_mdf::MdfDatabase;
+    /// # #[async_std::main]
+    /// # async fn main() {
+    /// let db = MdfDatabase::open("data/AWLT2005.mdf").await.unwrap();
+    /// println!("{} rows", db.estimated_total_rows());
+    /// # }
+    /// ```
+    pub fn estimated_total_rows(&self) -> i64 {
+        self.table_names()
+            .iter()
+            .filter_map(|table_name| self.base_table_data.row_count(table_name))
+            .sum()
+    }
+
+    /// Returns the column names of the given table name. Fails with [`Error::NoColumns`] if no
+    /// column of the table could be read.
     ///
     /// ```rust
     /// # use oxidized_mdf::MdfDatabase;
@@ -113,19 +324,163 @@ impl MdfDatabase {
     /// assert!(column_names.contains(&String::from("City")));
     /// # }
     /// ```
-    pub fn column_names(&self, table_name: &str) -> Option<Vec<String>> {
+    pub fn column_names(&self, table_name: &str) -> Result<Vec<String>, Error> {
+        let table = self
+            .base_table_data
+            .table(table_name)
+            .ok_or_else(|| Error::UnknownTable(table_name.to_string()))?;
+
+        if table.columns.is_empty() {
+            return Err(Error::NoColumns(table_name.to_string()));
+        }
+
+        Ok(table
+            .columns
+            .into_iter()
+            .map(|c| c.name.to_string())
+            .collect())
+    }
+
+    /// Returns the schema information of the columns of the given table name.
+    ///
+    /// ```rust
+    /// # use oxidized_mdf::MdfDatabase;
+    /// # #[async_std::main]
+    /// # async fn main() {
+    /// let db = MdfDatabase::open("data/AWLT2005.mdf").await.unwrap();
+    ///
+    /// let columns = db.columns("Address").unwrap();
+    /// let city = columns.iter().find(|c| c.name == "City").unwrap();
+    /// assert_eq!(city.type_name, "nvarchar");
+    /// # }
+    /// ```
+    pub fn columns(&self, table_name: &str) -> Option<Vec<ColumnInfo>> {
         Some(
             self.base_table_data
                 .table(table_name)?
                 .columns
-                .into_iter()
-                .map(|c| c.name.to_string())
+                .iter()
+                .map(ColumnInfo::from)
                 .collect(),
         )
     }
 
+    /// Returns the table name, the column name and the SQL type name of every column whose
+    /// values cannot be decoded, determined from the schema without reading any rows. Large
+    /// value columns, i.e. `(max)` and `xml` columns, are listed as well, because their values
+    /// cannot be decoded if they are stored out of row in a LOB tree with internal nodes, which
+    /// is not supported yet. Computed columns that are not persisted
+    /// are not read at all and therefore not listed.
+    ///
+    /// ```rust
+    /// # use oxidized_mdf::MdfDatabase;
+    /// # #[async_std::main]
+    /// # async fn main() {
+    /// let db = MdfDatabase::open("data/AWLT2005.mdf").await.unwrap();
+    ///
+    /// for (table_name, column_name, type_name) in db.unsupported_columns() {
+    ///     println!("{}.{} ({}) cannot be decoded", table_name, column_name, type_name);
+    /// }
+    /// # }
+    /// ```
+    pub fn unsupported_columns(&self) -> Vec<(String, String, String)> {
+        let mut unsupported_columns = Vec::new();
+
+        for table_name in self.table_names() {
+            let table = match self.base_table_data.table(&table_name) {
+                Some(table) => table,
+                None => continue,
+            };
+
+            unsupported_columns.extend(
+                table
+                    .columns
+                    .iter()
+                    .filter(|column| {
+                        // Large value columns have a maximum length of -1
+                        column.is_stored()
+                            && (column.column_type().is_none() || column.max_length == -1)
+                    })
+                    .map(|column| {
+                        (
+                            table_name.clone(),
+                            column.name.to_string(),
+                            column.r#type.to_string(),
+                        )
+                    }),
+            );
+        }
+
+        unsupported_columns
+    }
+
+    /// Compares the schema of this database with the schema of the other database, reporting
+    /// the tables and columns of the other database that were added, removed or changed.
+    ///
+    /// ```rust
+    /// # use oxidized_mdf::MdfDatabase;
+    /// # #[async_std::main]
+    /// # async fn main() {
+    /// let db = MdfDatabase::open("data/AWLT2005.mdf").await.unwrap();
+    /// let other = MdfDatabase::open("data/AWLT2005.mdf").await.unwrap();
+    ///
+    /// assert!(db.diff(&other).is_empty());
+    /// # }
+    /// ```
+    pub fn diff(&self, other: &MdfDatabase) -> SchemaDiff {
+        let schema = |db: &MdfDatabase| {
+            db.table_names()
+                .into_iter()
+                .filter_map(|table_name| {
+                    let columns = db.columns(&table_name)?;
+                    Some((table_name, columns))
+                })
+                .collect::<BTreeMap<_, _>>()
+        };
+
+        SchemaDiff::new(schema(self), schema(other))
+    }
+
+    /// Returns a handle of the given table bundling its metadata, which avoids passing the table
+    /// name to every method.
+    ///
+    /// ```rust
+    /// use oxidized_mdf::MdfDatabase;
+    /// use async_std::stream::StreamExt;
+    ///
+    /// # #[async_std::main]
+    /// # async fn main() {
+    /// let mut db = MdfDatabase::open("data/AWLT2005.mdf").await.unwrap();
+    /// let address = db.table("Address").unwrap();
+    ///
+    /// assert_eq!(address.row_count(), 450);
+    ///
+    /// let mut rows = address.rows(&mut db).unwrap();
+    /// while let Some(row) = rows.next().await {
+    ///     println!("{:?}", row.value("City"));
+    /// }
+    /// # }
+    /// ```
+    pub fn table(&self, table_name: &str) -> Option<TableHandle> {
+        let table = self.base_table_data.table(table_name)?;
+
+        Some(TableHandle {
+            name: table.name().to_string(),
+            columns: table.columns.iter().map(ColumnInfo::from).collect(),
+            row_count: table.row_count(),
+        })
+    }
+
     /// Returns a stream of the rows in the given table.
     ///
+    /// The rows are read from the data pages of the heap or the clustered index, which contain
+    /// all stored columns. Reading only a nonclustered index, which contains only the index key
+    /// and included columns, is not supported.
+    ///
+    /// Fails with [`Error::UnknownTable`] if the database does not contain the table, and
+    /// before any page is read if the table cannot be decoded, see
+    /// [`rows_with_rid`](Self::rows_with_rid).
+    ///
     /// ```rust
     /// use oxidized_mdf::{MdfDatabase, Value};
     /// use async_std::stream::StreamExt;
@@ -145,273 +500,3925 @@ impl MdfDatabase {
     pub fn rows<'a, 'b: 'a>(
         &'b mut self,
         table_name: &str,
-    ) -> Option<impl Stream<Item = Row> + 'a> {
-        let table = self.base_table_data.table(table_name)?;
+    ) -> Result<impl Stream<Item = Row> + 'a, Error> {
+        let options = self.options.clone();
+        Ok(self
+            .rows_with_rid(table_name)?
+            .filter_map(move |row| match row {
+                Ok((_rid, row)) => Some(row),
+                Err(err) => {
+                    options.report_record_error(&err);
+                    None
+                }
+            }))
+    }
+
+    /// Returns a stream of the rows in the given table, batched into chunks of `chunk_size` rows.
+    /// The last chunk contains the remaining rows and might be smaller.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is 0.
+    ///
+    /// ```rust
+    /// use oxidized_mdf::MdfDatabase;
+    /// use async_std::stream::StreamExt;
+    ///
+    /// # #[async_std::main]
+    /// # async fn main() {
+    /// let mut db = MdfDatabase::open("data/AWLT2005.mdf").await.unwrap();
+    /// let mut chunks = db.rows_chunked("Address", 100).unwrap();
+    /// let chunk = chunks.next().await.unwrap().unwrap();
+    ///
+    /// assert_eq!(chunk.len(), 100);
+    /// # }
+    /// ```
+    pub fn rows_chunked<'a, 'b: 'a>(
+        &'b mut self,
+        table_name: &str,
+        chunk_size: usize,
+    ) -> Result<impl Stream<Item = Result<Vec<Row>, Error>> + 'a, Error> {
+        assert!(chunk_size > 0, "chunk size must be greater than 0");
 
-        let page_pointers = table.page_pointers();
+        let rows = Box::pin(self.rows_with_rid(table_name)?);
 
-        span!("reading pages of {}", table_name, {
-            Some(
-                self.page_reader
-                    .read_pages_of_pointers(page_pointers)
-                    .flat_map(move |page| {
-                        let mut rows = Vec::new();
+        Ok(Box::pin(futures_lite::stream::unfold(
+            (rows, None),
+            move |(mut rows, pending_error)| async move {
+                if let Some(err) = pending_error {
+                    return Some((Err(err), (rows, None)));
+                }
 
-                        let page = match page {
-                            Ok(page) => page,
-                            Err(err) => {
-                                error!("Cannot read page: {}", err);
-                                return async_std::stream::from_iter(rows.into_iter());
-                            }
-                        };
+                let mut chunk = Vec::with_capacity(chunk_size);
+                while chunk.len() < chunk_size {
+                    match rows.next().await {
+                        Some(Ok((_rid, row))) => chunk.push(row),
+                        // Yield the rows read so far before the error
+                        Some(Err(err)) if !chunk.is_empty() => {
+                            return Some((Ok(chunk), (rows, Some(err))));
+                        }
+                        Some(Err(err)) => return Some((Err(err), (rows, None))),
+                        None => break,
+                    }
+                }
 
-                        span!("page header {:?}", page.header(), {
-                            for record in page.records().into_iter() {
-                                let mut columns = BTreeMap::new();
-
-                                let mut record = Some(record);
-                                for column in &table.columns {
-                                    let (value, r) =
-                                        match Value::parse(column, record.take().unwrap()) {
-                                            Ok((value, r)) => (value, r),
-                                            Err(e) => {
-                                                error!(
-                                                    "Error during parsing column {:?}: {}",
-                                                    column, e
-                                                );
-                                                break;
-                                            }
-                                        };
-
-                                    columns.insert(column.name.to_string(), value);
-
-                                    record = Some(r);
-                                }
-
-                                rows.push(Row { columns });
-                            }
-                        });
-                        async_std::stream::from_iter(rows.into_iter())
-                    }),
-            )
-        })
+                if chunk.is_empty() {
+                    None
+                } else {
+                    Some((Ok(chunk), (rows, None)))
+                }
+            },
+        )))
     }
-}
 
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub enum Value {
-    Bit(bool),
-    TinyInt(i8),
-    SmallInt(i16),
-    Int(i32),
-    BigInt(i64),
-    Decimal(Decimal),
-    String(String),
-    DateTime(DateTime<Utc>),
-    Uuid(Uuid),
-    Null,
-}
+    /// Reads all rows of the given table into memory.
+    ///
+    /// In contrast to [`rows`](Self::rows), the returned rows do not borrow the database, and
+    /// reading stops at the first row that cannot be read, e.g. due to a column parse error in
+    /// strict mode. Its error is returned instead of being passed to
+    /// [`Options::on_record_error`](crate::options::Options::on_record_error), so that no row is
+    /// silently missing from the result.
+    ///
+    /// ```rust
+    /// # use oxidized_mdf::MdfDatabase;
+    /// # #[async_std::main]
+    /// # async fn main() {
+    /// let mut db = MdfDatabase::open("data/AWLT2005.mdf").await.unwrap();
+    /// let rows = db.collect_rows("Address").await.unwrap();
+    ///
+    /// assert_eq!(rows.len(), 450);
+    /// # }
+    /// ```
+    pub async fn collect_rows(&mut self, table_name: &str) -> Result<Vec<Row>, Error> {
+        let mut rows = self.rows_with_rid(table_name)?;
 
-impl Display for Value {
-    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
-        match self {
-            Value::Bit(bit) => write!(fmt, "{}", bit),
-            Value::TinyInt(i) => write!(fmt, "{}", i),
-            Value::SmallInt(i) => write!(fmt, "{}", i),
-            Value::Int(i) => write!(fmt, "{}", i),
-            Value::BigInt(i) => write!(fmt, "{}", i),
-            Value::Decimal(decimal) => write!(fmt, "{}", decimal),
-            Value::String(s) => write!(fmt, "{}", s),
-            Value::DateTime(d) => write!(fmt, "{}", d),
-            Value::Uuid(uuid) => write!(fmt, "{}", uuid),
-            Value::Null => write!(fmt, "null"),
+        let mut collected_rows = Vec::new();
+        while let Some(row) = rows.next().await {
+            let (_rid, row) = row?;
+            collected_rows.push(row);
         }
-    }
-}
 
-impl Value {
-    fn parse<'a>(
-        column: &Column<'_>,
-        record: Record<'a>,
-    ) -> Result<(Self, Record<'a>), &'static str> {
-        match column.r#type {
-            "bit" => {
-                let (bit, r) = record.parse_bit()?;
-                Ok((Value::Bit(bit), r))
-            }
-            "datetime" => {
-                let (datetime, r) = record.parse_datetime_opt()?;
-                Ok((datetime.map_or(Value::Null, Value::DateTime), r))
-            }
-            "datetime2" => {
-                let (datetime, r) = record.parse_datetime2_opt(column.scale)?;
-                Ok((datetime.map_or(Value::Null, Value::DateTime), r))
-            }
-            "tinyint" => {
-                let (int, r) = record.parse_i8()?;
-                Ok((Value::TinyInt(int), r))
-            }
-            "smallint" => {
-                let (int, r) = record.parse_i16()?;
-                Ok((Value::SmallInt(int), r))
-            }
-            "int" | "money" => {
-                let (int, r) = record.parse_i32_opt()?;
-                Ok((int.map_or(Value::Null, Value::Int), r))
-            }
-            "bigint" => {
-                let (int, r) = record.parse_i64_opt()?;
-                Ok((int.map_or(Value::Null, Value::BigInt), r))
-            }
-            "nchar" => {
-                let (string, r) =
-                    record.parse_string_from_fixed_bytes(column.max_length as usize)?;
-                Ok((Value::String(string), r))
-            }
-            "nvarchar" | "varchar" => {
-                let (string, r) = record.parse_string()?;
-                Ok((string.map_or(Value::Null, Value::String), r))
-            }
-            "uniqueidentifier" => {
-                let (uuid, r) = record.parse_uuid()?;
-                Ok((Value::Uuid(uuid), r))
-            }
-            "decimal" => {
-                let (decimal, r) = record.parse_decimal_opt(column.precision, column.scale)?;
-                Ok((decimal.map_or(Value::Null, Value::Decimal), r))
-            }
-            _ => Err("Unknown column type"),
-        }
+        Ok(collected_rows)
     }
-}
 
-#[derive(Debug)]
-pub struct Row {
-    columns: BTreeMap<String, Value>,
-}
+    /// Reads the rows of all tables into memory and returns them by table name, in table name
+    /// order.
+    ///
+    /// The rows neither borrow nor reference the database, so the result can be sent to
+    /// another thread, e.g. a worker of an ETL pipeline. The whole content of the database is
+    /// held in memory, which takes a multiple of the file size due to the decoded values and
+    /// column names stored per row. Use [`rows`](Self::rows) to process large databases row by
+    /// row instead.
+    ///
+    /// ```rust
+    /// # use oxidized_mdf::MdfDatabase;
+    /// # #[async_std::main]
+    /// # async fn main() {
+    /// let db = MdfDatabase::open("data/AWLT2005.mdf").await.unwrap();
+    /// let tables = db.into_stream_all().await.unwrap();
+    ///
+    /// let worker = std::thread::spawn(move || tables.map(|(_, rows)| rows.len()).sum::<usize>());
+    /// assert!(worker.join().unwrap() > 0);
+    /// # }
+    /// ```
+    pub async fn into_stream_all(
+        mut self,
+    ) -> Result<impl Iterator<Item = (String, Vec<Row>)> + Send, Error> {
+        let mut tables = BTreeMap::new();
+        for table_name in self.table_names() {
+            let rows = self.collect_rows(&table_name).await?;
+            tables.insert(table_name, rows);
+        }
 
-impl Row {
-    pub fn value(&self, column_name: &str) -> Option<&Value> {
-        self.columns.get(column_name)
+        Ok(tables.into_iter())
     }
 
-    pub fn values(self) -> Vec<(String, Value)> {
-        self.columns.into_iter().collect()
+    /// Reads all rows of the given table into an Arrow `RecordBatch` with one column per table
+    /// column in schema order.
+    ///
+    /// ```rust
+    /// # use oxidized_mdf::MdfDatabase;
+    /// # #[async_std::main]
+    /// # async fn main() {
+    /// let mut db = MdfDatabase::open("data/AWLT2005.mdf").await.unwrap();
+    /// let batch = db.export_arrow("Address").await.unwrap();
+    ///
+    /// assert_eq!(batch.num_rows(), 450);
+    /// # }
+    /// ```
+    #[cfg(feature = "arrow")]
+    pub async fn export_arrow(
+        &mut self,
+        table_name: &str,
+    ) -> Result<arrow::record_batch::RecordBatch, Error> {
+        let rows = self.collect_rows(table_name).await?;
+        let columns = self
+            .columns(table_name)
+            .ok_or_else(|| Error::NoColumns(table_name.to_string()))?;
+
+        Ok(export::record_batch::record_batch(&columns, &rows)?)
     }
-}
 
-struct PageReader {
-    read: Box<dyn Read + Unpin>,
-    page_index: u16,
-    page_cache: HashMap<PagePointer, Rc<Page>>,
-}
+    /// Writes all rows of the given table into a Parquet file at the given path.
+    ///
+    /// The rows are written in batches so that large tables do not need to be kept in memory.
+    ///
+    /// ```rust,no_run
+    /// # use oxidized_mdf::MdfDatabase;
+    /// # #[async_std::main]
+    /// # async fn main() {
+    /// let mut db = MdfDatabase::open("data/AWLT2005.mdf").await.unwrap();
+    /// db.export_parquet("Address", "Address.parquet").await.unwrap();
+    /// # }
+    /// ```
+    #[cfg(feature = "parquet")]
+    pub async fn export_parquet<P>(&mut self, table_name: &str, path: P) -> Result<(), Error>
+    where
+        P: AsRef<std::path::Path>,
+    {
+        let columns = self
+            .columns(table_name)
+            .ok_or_else(|| Error::UnknownTable(table_name.to_string()))?;
+        let file = std::fs::File::create(path)?;
+        let mut export = export::parquet_file::ParquetExport::new(columns, file)?;
 
-impl PageReader {
-    fn new(read: Box<dyn Read + Unpin>) -> Self {
-        Self {
-            read,
-            page_index: 0,
-            page_cache: HashMap::new(),
+        let mut rows = self.rows_with_rid(table_name)?;
+        while let Some(row) = rows.next().await {
+            let (_rid, row) = row?;
+            export.push(row)?;
         }
-    }
 
-    async fn read_next_page(&mut self, buffer: &mut [u8; 8192]) -> Result<(), Error> {
-        self.read.read_exact(&mut buffer[..]).await?;
-        self.page_index += 1;
-        Ok(())
+        export.finish()
     }
 
-    async fn read_page(&mut self, page_pointer: &PagePointer) -> Result<Rc<Page>, Error> {
-        if let Some(page) = self.page_cache.get(page_pointer) {
-            return Ok(page.clone());
+    /// Writes all rows of the given table as CSV into the writer, starting with a header line of
+    /// the column names in schema order. NULL values become empty fields.
+    ///
+    /// ```rust
+    /// # use oxidized_mdf::MdfDatabase;
+    /// # #[async_std::main]
+    /// # async fn main() {
+    /// let mut db = MdfDatabase::open("data/AWLT2005.mdf").await.unwrap();
+    /// let mut csv = Vec::new();
+    /// db.write_table_csv("Address", &mut csv).await.unwrap();
+    ///
+    /// let csv = String::from_utf8(csv).unwrap();
+    /// assert_eq!(csv.lines().count(), 451);
+    /// # }
+    /// ```
+    pub async fn write_table_csv<W>(&mut self, table_name: &str, mut writer: W) -> Result<(), Error>
+    where
+        W: std::io::Write,
+    {
+        let columns = self
+            .columns(table_name)
+            .ok_or_else(|| Error::UnknownTable(table_name.to_string()))?;
+        export::csv::write_record(&mut writer, columns.iter().map(|column| &column.name))?;
+
+        let mut rows = self.rows_with_rid(table_name)?;
+        while let Some(row) = rows.next().await {
+            let (_rid, row) = row?;
+            export::csv::write_record(
+                &mut writer,
+                columns
+                    .iter()
+                    .map(|column| export::csv::field(row.value(&column.name))),
+            )?;
         }
 
-        assert!(self.page_index <= page_pointer.page_id, "Currently the database supports only forward reading and the requested page {} has been already read", page_pointer.page_id);
+        Ok(writer.flush()?)
+    }
 
-        for i in self.page_index..=page_pointer.page_id {
-            let mut buffer = [0u8; 8192];
-            self.read_next_page(&mut buffer).await?;
+    /// Writes all rows of the given table as SQL `INSERT` statements into the writer, one line
+    /// per row with the columns in schema order, e.g. to load the data into another database.
+    /// Computed and `rowversion` columns are left out, because values cannot be inserted into
+    /// them. The inserts into tables with an identity column are enclosed in
+    /// `SET IDENTITY_INSERT` statements to keep the values of the identity column.
+    ///
+    /// ```rust
+    /// # use oxidized_mdf::MdfDatabase;
+    /// # #[async_std::main]
+    /// # async fn main() {
+    /// let mut db = MdfDatabase::open("data/AWLT2005.mdf").await.unwrap();
+    /// let mut sql = Vec::new();
+    /// db.write_table_sql("Address", &mut sql).await.unwrap();
+    ///
+    /// let sql = String::from_utf8(sql).unwrap();
+    /// assert!(sql.starts_with("INSERT INTO [Address] ([AddressID], "));
+    /// # }
+    /// ```
+    pub async fn write_table_sql<W>(&mut self, table_name: &str, mut writer: W) -> Result<(), Error>
+    where
+        W: std::io::Write,
+    {
+        let columns = self
+            .columns(table_name)
+            .ok_or_else(|| Error::UnknownTable(table_name.to_string()))?
+            .into_iter()
+            .filter(|column| {
+                !column.is_computed && column.column_type() != Some(ColumnType::RowVersion)
+            })
+            .collect::<Vec<_>>();
+        let identity = columns.iter().any(|column| column.identity.is_some());
 
-            let page = Page::try_from(buffer).unwrap();
+        if identity {
+            export::sql::write_identity_insert(&mut writer, table_name, true)?;
+        }
 
-            self.page_cache
-                .insert(page_pointer.with_page_id(i), Rc::new(page));
+        let mut rows = self.rows_with_rid(table_name)?;
+        while let Some(row) = rows.next().await {
+            let (_rid, row) = row?;
+            export::sql::write_insert(
+                &mut writer,
+                table_name,
+                columns.iter().map(|column| &column.name),
+                columns
+                    .iter()
+                    .map(|column| export::sql::literal(row.value(&column.name))),
+            )?;
         }
 
-        let page = self.page_cache.get(page_pointer).unwrap();
-        Ok(page.clone())
+        if identity {
+            export::sql::write_identity_insert(&mut writer, table_name, false)?;
+        }
+
+        Ok(writer.flush()?)
     }
 
-    fn read_pages_of_pointers<'a, 'b: 'a>(
+    /// Returns a stream of the rows in the given table, each rendered as a single line JSON
+    /// object with the columns in schema order. Joining the lines with newlines yields NDJSON.
+    ///
+    /// ```rust
+    /// use oxidized_mdf::MdfDatabase;
+    /// use async_std::stream::StreamExt;
+    ///
+    /// # #[async_std::main]
+    /// # async fn main() {
+    /// let mut db = MdfDatabase::open("data/AWLT2005.mdf").await.unwrap();
+    /// let mut rows = db.rows_as_json("Address").unwrap();
+    /// let first_row = rows.next().await.unwrap().unwrap();
+    ///
+    /// assert!(first_row.contains(r#""AddressLine1":"8713 Yosemite Ct.""#));
+    /// # }
+    /// ```
+    pub fn rows_as_json<'a, 'b: 'a>(
         &'b mut self,
-        page_pointers: Vec<PagePointer>,
-    ) -> PageStream<'a> {
-        PageStream {
-            page_pointers: Box::new(page_pointers.into_iter()),
-            page_reader: self,
-            current_page: None,
-        }
+        table_name: &str,
+    ) -> Result<impl Stream<Item = Result<String, Error>> + 'a, Error> {
+        let columns = self
+            .columns(table_name)
+            .ok_or_else(|| Error::UnknownTable(table_name.to_string()))?;
+
+        Ok(self
+            .rows_with_rid(table_name)?
+            .map(move |row| row.map(|(_rid, row)| export::json::object(&columns, &row))))
     }
 
-    fn read_pages_of_pointer<'a, 'b: 'a>(
+    /// Returns a stream of the rows of the given table that are recovered from the space of the
+    /// data pages no slot refers to. Deleted rows remain there until the space is reused.
+    ///
+    /// The recovery is best-effort: only records that decode completely into the columns of the
+    /// table are returned, but rows may be outdated versions of live rows or coincidental
+    /// matches.
+    ///
+    /// Fails with [`Error::UnknownTable`] if the database does not contain the table, and like
+    /// [`MdfDatabase::rows_with_rid`] for tables without columns. Compressed tables fail with
+    /// [`Error::UnsupportedCompression`].
+    pub fn recover_deleted<'a, 'b: 'a>(
         &'b mut self,
-        page_pointer: PagePointer,
-    ) -> PageStream<'a> {
-        PageStream {
-            page_pointers: Box::new(std::iter::once(page_pointer)),
-            page_reader: self,
-            current_page: None,
+        table_name: &str,
+    ) -> Result<impl Stream<Item = RecoveredRow> + 'a, Error> {
+        let table = self
+            .base_table_data
+            .table(table_name)
+            .ok_or_else(|| Error::UnknownTable(table_name.to_string()))?;
+        check_readable(&table)?;
+
+        // Deleted records are only recognized in the FixedVar format of uncompressed tables
+        if let Some(compression) = table.compression() {
+            return Err(Error::UnsupportedCompression {
+                table: table.name().to_string(),
+                compression,
+            });
         }
+
+        // Only records that decode completely are plausible rows of the table
+        let options = Options {
+            strict: true,
+            ..self.options.clone()
+        };
+
+        Ok(self
+            .page_reader
+            .read_pages_of_table(&table)
+            .flat_map(move |page| {
+                let rows = match page {
+                    Ok(page) => recover_rows(&table, &page, &options),
+                    Err(err) => {
+                        warn!("Cannot read page to recover rows: {}", err);
+                        Vec::new()
+                    }
+                };
+                async_std::stream::from_iter(rows)
+            }))
+    }
+
+    /// Returns a stream of the rows in the given table together with their physical location.
+    ///
+    /// Fails with [`Error::UnknownTable`] if the database does not contain the table. Tables
+    /// without columns fail with [`Error::NoColumns`] before any page is read, as do tables with
+    /// columns of unsupported types in [strict mode](crate::options::Options::strict). Tables
+    /// with columnstore compression and, without the `compression` feature, ROW and PAGE
+    /// compression fail with [`Error::UnsupportedCompression`].
+    ///
+    /// ```rust
+    /// use oxidized_mdf::MdfDatabase;
+    /// use async_std::stream::StreamExt;
+    ///
+    /// # #[async_std::main]
+    /// # async fn main() {
+    /// let mut db = MdfDatabase::open("data/AWLT2005.mdf").await.unwrap();
+    /// let mut rows = db.rows_with_rid("Address").unwrap();
+    /// let (rid, _row) = rows.next().await.unwrap().unwrap();
+    ///
+    /// assert_eq!(rid.file_id, 1);
+    /// # }
+    /// ```
+    pub fn rows_with_rid<'a, 'b: 'a>(
+        &'b mut self,
+        table_name: &str,
+    ) -> Result<impl Stream<Item = Result<(Rid, Row), Error>> + 'a, Error> {
+        let table = self
+            .base_table_data
+            .table(table_name)
+            .ok_or_else(|| Error::UnknownTable(table_name.to_string()))?;
+
+        rows_of_table(&mut self.page_reader, table, self.options.clone())
+    }
+
+    /// Returns a stream of the values of the rows in the given table, in the order of
+    /// [`column_names`](Self::column_names). In contrast to [`rows`](Self::rows) the column
+    /// names are not stored per row, which makes extracting large tables cheaper. Like the
+    /// columns missing in a [`Row`], the values of columns that are not stored in the records,
+    /// i.e. computed columns that are not persisted, and of columns that could not be decoded
+    /// are `None`, in contrast to stored `NULL`s which are `Some(Value::Null)`. The option
+    /// [`row_index`](crate::options::Options::row_index) does not apply.
+    ///
+    /// ```rust
+    /// use oxidized_mdf::MdfDatabase;
+    /// use async_std::stream::StreamExt;
+    ///
+    /// # #[async_std::main]
+    /// # async fn main() {
+    /// let mut db = MdfDatabase::open("data/AWLT2005.mdf").await.unwrap();
+    /// let city = db
+    ///     .column_names("Address")
+    ///     .unwrap()
+    ///     .iter()
+    ///     .position(|name| name == "City")
+    ///     .unwrap();
+    ///
+    /// let mut rows = db.rows_tuples("Address").unwrap();
+    /// let values = rows.next().await.unwrap().unwrap();
+    /// if let Some(city) = &values[city] {
+    ///     println!("{}", city);
+    /// }
+    /// # }
+    /// ```
+    pub fn rows_tuples<'a, 'b: 'a>(
+        &'b mut self,
+        table_name: &str,
+    ) -> Result<impl Stream<Item = Result<Vec<Option<Value>>, Error>> + 'a, Error> {
+        let table = self
+            .base_table_data
+            .table(table_name)
+            .ok_or_else(|| Error::UnknownTable(table_name.to_string()))?;
+
+        Ok(records_of_table(
+            &mut self.page_reader,
+            table,
+            self.options.clone(),
+            Row::parse_values,
+        )?
+        .map(|values| values.map(|(_rid, values)| values)))
+    }
+
+    /// Returns a stream of the rows in the given partition of the table, e.g. to process large
+    /// partitioned tables one partition at a time. Partitions are addressed by their 0-based
+    /// index in partition number order, every table has at least the partition 0. The stream
+    /// is empty for indexes beyond the last partition.
+    ///
+    /// ```rust
+    /// use oxidized_mdf::MdfDatabase;
+    /// use futures_lite::stream::StreamExt;
+    ///
+    /// # #[async_std::main]
+    /// # async fn main() {
+    /// let mut db = MdfDatabase::open("data/AWLT2005.mdf").await.unwrap();
+    /// let rows = db.rows_partition("Address", 0).unwrap();
+    ///
+    /// assert_eq!(rows.collect::<Vec<_>>().await.len(), 450);
+    /// # }
+    /// ```
+    pub fn rows_partition<'a, 'b: 'a>(
+        &'b mut self,
+        table_name: &str,
+        partition: usize,
+    ) -> Result<impl Stream<Item = Row> + 'a, Error> {
+        let table = self
+            .base_table_data
+            .table(table_name)
+            .ok_or_else(|| Error::UnknownTable(table_name.to_string()))?
+            .with_partition(partition);
+
+        let options = self.options.clone();
+        Ok(
+            rows_of_table(&mut self.page_reader, table, options.clone())?.filter_map(move |row| {
+                match row {
+                    Ok((_rid, row)) => Some(row),
+                    Err(err) => {
+                        options.report_record_error(&err);
+                        None
+                    }
+                }
+            }),
+        )
     }
 }
 
-struct PageStream<'a> {
-    page_pointers: Box<dyn Iterator<Item = PagePointer>>,
+/// Returns a stream of the rows of the table, read from the pages of the table.
+fn rows_of_table<'a>(
     page_reader: &'a mut PageReader,
-    current_page: Option<Rc<Page>>,
+    table: Table<'a>,
+    options: Options,
+) -> Result<impl Stream<Item = Result<(Rid, Row), Error>> + 'a, Error> {
+    let row_index_column = options.row_index;
+    let mut row_index = 0i64;
+
+    Ok(
+        records_of_table(page_reader, table, options, Row::parse)?.map(move |row| {
+            let (rid, mut row) = row?;
+            if row_index_column {
+                row.columns
+                    .insert(ROW_INDEX_COLUMN.to_string(), Value::BigInt(row_index));
+            }
+            row_index += 1;
+
+            Ok((rid, row))
+        }),
+    )
 }
 
-impl<'a> PageStream<'a> {
-    async fn next_page(&mut self) -> Option<Result<Rc<Page>, Error>> {
-        let page_pointer = match self.current_page.take() {
-            Some(current_page) => current_page.next_page_pointer().cloned(),
-            None => self.page_pointers.next(),
-        };
+/// Returns a stream of the records of the table, read from the pages of the table and decoded
+/// with the given function.
+///
+/// In strict mode columns that cannot be decoded fail before any page is read, as do tables
+/// without any column and compressed tables in every mode.
+fn records_of_table<'a, T, F>(
+    page_reader: &'a mut PageReader,
+    table: Table<'a>,
+    options: Options,
+    parse: F,
+) -> Result<impl Stream<Item = Result<(Rid, T), Error>> + 'a, Error>
+where
+    T: 'a,
+    F: Fn(&Table<'_>, &Page, Record<'_>, &Options) -> Result<T, Error> + 'a,
+{
+    check_readable(&table)?;
 
-        match page_pointer {
-            Some(page_pointer) => {
-                let page = self.page_reader.read_page(&page_pointer).await;
+    if let Some(column) = table
+        .stored_columns()
+        .find(|column| options.strict && column.column_type().is_none())
+    {
+        return Err(Error::UnsupportedColumnType {
+            table: table.name().to_string(),
+            column: column.name.to_string(),
+            type_name: column.r#type.to_string(),
+        });
+    }
 
-                if let Ok(current_page) = &page {
-                    self.current_page = Some(current_page.clone());
+    // The values of compressed records are converted in storage order, the leaf offsets refer
+    // to the uncompressed format
+    #[cfg(feature = "compression")]
+    let table = if table.compression().is_some() {
+        table.without_leaf_offsets()
+    } else {
+        table
+    };
+
+    let yield_interval = options.yield_interval;
+
+    let records = span!("reading pages of {}", table.name(), {
+        PageStream::with_lob_pages(page_reader.scan_pages_of_table(&table)).flat_map(move |page| {
+            let mut records = Vec::new();
+
+            let (page, lob_pages) = match page {
+                Ok(page) => page,
+                Err(err) => {
+                    records.push(Err(err));
+                    return async_std::stream::from_iter(records.into_iter());
                 }
+            };
 
-                Some(page)
-            }
-            None => None,
+            span!("page header {:?}", page.header(), {
+                if table.compression().is_none() {
+                    for (slot, record) in page.records_with_slots().into_iter() {
+                        let record = record.with_lob_pages(&lob_pages);
+                        records.extend(parse_record(&table, &page, slot, record, &options, &parse));
+                    }
+                }
+
+                #[cfg(feature = "compression")]
+                if table.compression().is_some() {
+                    for (slot, offset, bytes) in decompress_records(&table, &page) {
+                        let bytes = match bytes {
+                            Ok(bytes) => bytes,
+                            Err(err) if options.strict => {
+                                records.push(Err(err));
+                                continue;
+                            }
+                            Err(err) => {
+                                options.report_record_error(&err);
+                                continue;
+                            }
+                        };
+
+                        match Record::try_from(&bytes[..]) {
+                            Ok(record) => {
+                                let record = record.at_offset(offset).with_lob_pages(&lob_pages);
+                                records.extend(parse_record(
+                                    &table, &page, slot, record, &options, &parse,
+                                ));
+                            }
+                            Err(err) => warn!(
+                                "Skipping slot {} of page {:?}: {}",
+                                slot,
+                                page.header().page_pointer,
+                                err
+                            ),
+                        }
+                    }
+                }
+            });
+            async_std::stream::from_iter(records.into_iter())
+        })
+    });
+
+    Ok(yield_every(records, yield_interval))
+}
+
+/// Parses the record in the given slot of the page, skipping records without columns.
+fn parse_record<T, F>(
+    table: &Table<'_>,
+    page: &Page,
+    slot: u16,
+    record: Record<'_>,
+    options: &Options,
+    parse: &F,
+) -> Option<Result<(Rid, T), Error>>
+where
+    F: Fn(&Table<'_>, &Page, Record<'_>, &Options) -> Result<T, Error>,
+{
+    let page_pointer = &page.header().page_pointer;
+    if record.number_of_columns() == 0 {
+        warn!(
+            "Skipping record without columns in slot {} of page {:?}",
+            slot, page_pointer
+        );
+        return None;
+    }
+
+    let rid = Rid {
+        page_id: page_pointer.page_id,
+        file_id: page_pointer.file_id,
+        slot,
+    };
+
+    Some(parse(table, page, record, options).map(|record| (rid, record)))
+}
+
+/// Yields to the executor after every `interval` items of the stream, unless `interval` is
+/// `0`, so that long scans do not monopolize the executor.
+fn yield_every<S: Stream>(stream: S, interval: usize) -> YieldEvery<S> {
+    YieldEvery {
+        stream: Box::pin(stream),
+        interval,
+        count: 0,
+    }
+}
+
+struct YieldEvery<S> {
+    stream: Pin<Box<S>>,
+    interval: usize,
+    count: usize,
+}
+
+impl<S: Stream> Stream for YieldEvery<S> {
+    type Item = S::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.interval > 0 && self.count >= self.interval {
+            self.count = 0;
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+
+        let item = self.stream.as_mut().poll_next(cx);
+        if let Poll::Ready(Some(_)) = item {
+            self.count += 1;
         }
+        item
     }
 }
 
-impl<'a> Stream for PageStream<'a> {
-    type Item = Result<Rc<Page>, Error>;
+/// A row recovered from the unused space of a data page, see
+/// [`MdfDatabase::recover_deleted`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveredRow {
+    pub page_id: u32,
+    pub file_id: u16,
+    /// The offset of the record within the page.
+    pub offset: u16,
+    pub row: Row,
+}
+
+/// Fails for tables whose records cannot be decoded at all, i.e. tables without any column and
+/// tables with columnstore compression or, without the `compression` feature, ROW and PAGE
+/// compression.
+fn check_readable(table: &Table<'_>) -> Result<(), Error> {
+    if table.columns.is_empty() {
+        return Err(Error::NoColumns(table.name().to_string()));
+    }
 
-    fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        let f = self.next_page();
-        futures_lite::pin!(f);
-        Poll::Ready(async_std::task::block_on(f))
+    match table.compression() {
+        #[cfg(feature = "compression")]
+        Some("ROW") | Some("PAGE") => Ok(()),
+        Some(compression) => Err(Error::UnsupportedCompression {
+            table: table.name().to_string(),
+            compression,
+        }),
+        None => Ok(()),
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Converts the compressed records of the page to the FixedVar format of uncompressed records,
+/// see [`CompressedRecord::decompress`]. Values that cannot be converted fail like values that
+/// cannot be parsed.
+#[cfg(feature = "compression")]
+fn decompress_records(table: &Table<'_>, page: &Page) -> Vec<(u16, usize, Result<Vec<u8>, Error>)> {
+    let page_pointer = &page.header().page_pointer;
+    let compression_info = match CompressionInfo::of_page(page) {
+        Ok(compression_info) => compression_info,
+        Err(err) => {
+            warn!(
+                "Skipping records of page {:?} with invalid compression information: {}",
+                page_pointer, err
+            );
+            return Vec::new();
+        }
+    };
+    let columns = table.stored_columns().collect::<Vec<_>>();
 
-    #[async_std::test]
-    async fn should_result_in_io_error_when_file_does_not_exists() {
-        match MdfDatabase::open("some-random-path").await {
-            Err(Error::IoError(err)) if err.kind() == async_std::io::ErrorKind::NotFound => {}
-            _ => panic!("Unexpected result"),
+    page.record_bytes_with_slots()
+        .into_iter()
+        .filter_map(|(slot, offset, bytes)| {
+            let record = match CompressedRecord::try_from(bytes) {
+                Ok(record) => record,
+                Err(err) => {
+                    warn!("Skipping slot {} of page {:?}: {}", slot, page_pointer, err);
+                    return None;
+                }
+            };
+
+            let bytes = record
+                .decompress(compression_info.as_ref(), &columns)
+                .map_err(|(index, err)| Error::ColumnParse {
+                    table: table.name().to_string(),
+                    column: columns[index].name.to_string(),
+                    page_id: page_pointer.page_id,
+                    offset: offset as u16,
+                    source: err.into(),
+                });
+            Some((slot, offset, bytes))
+        })
+        .collect()
+}
+
+/// Parses the records in the unused space of the page that decode completely into the columns
+/// of the table.
+fn recover_rows(table: &Table<'_>, page: &Page, options: &Options) -> Vec<RecoveredRow> {
+    let stored_columns = table.stored_columns().count();
+    let page_pointer = &page.header().page_pointer;
+
+    page.unreferenced_records()
+        .into_iter()
+        .filter(|(_, record)| record.number_of_columns() >= stored_columns)
+        .filter_map(|(offset, record)| {
+            let row = Row::parse(table, page, record, options).ok()?;
+            Some(RecoveredRow {
+                page_id: page_pointer.page_id,
+                file_id: page_pointer.file_id,
+                offset: offset as u16,
+                row,
+            })
+        })
+        .collect()
+}
+
+fn matches_pattern(name: &str, pattern: &str) -> bool {
+    if !pattern.contains(['*', '?']) {
+        return name.starts_with(pattern);
+    }
+
+    let name = name.chars().collect::<Vec<_>>();
+    let pattern = pattern.chars().collect::<Vec<_>>();
+
+    // Matches greedily and on a mismatch lets the last star consume one more character, which
+    // avoids the exponential backtracking of patterns with many stars
+    let (mut n, mut p) = (0, 0);
+    let mut last_star = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                last_star = Some((p, n));
+                p += 1;
+            }
+            Some('?') => {
+                n += 1;
+                p += 1;
+            }
+            Some(c) if *c == name[n] => {
+                n += 1;
+                p += 1;
+            }
+            _ => match last_star {
+                Some((star, consumed)) => {
+                    last_star = Some((star, consumed + 1));
+                    p = star + 1;
+                    n = consumed + 1;
+                }
+                None => return false,
+            },
         }
     }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Returns the offset within the page at which the value of the column starts in the record.
+fn read_offset(record: &Record<'_>, column: &Column<'_>) -> usize {
+    let column_type = column.column_type();
+    let fixed = column_type == Some(ColumnType::Bit)
+        || column_type
+            .and_then(|column_type| column_type.fixed_length())
+            .is_some();
+
+    match column.leaf_offset {
+        Some(offset) => record.offset() + offset as usize,
+        _ if fixed => record.fixed_read_offset(),
+        _ => record.variable_read_offset(),
+    }
+}
+
+/// Parses the values of the stored columns of the record and passes them together with the
+/// index of their column to `f`. If a value cannot be decoded, the remaining columns are
+/// skipped unless [`Options::strict`] is set, which results in an error.
+fn parse_columns<F>(
+    table: &Table<'_>,
+    page: &Page,
+    record: Record<'_>,
+    options: &Options,
+    mut f: F,
+) -> Result<(), Error>
+where
+    F: FnMut(usize, &Column<'_>, Value),
+{
+    let mut record = Some(record);
+    for (index, column) in table
+        .columns
+        .iter()
+        .enumerate()
+        .filter(|(_, column)| column.is_stored())
+    {
+        let offset = read_offset(record.as_ref().unwrap(), column);
+        let current = match column.leaf_offset {
+            Some(offset) => record.take().unwrap().seek_fixed_offset(offset as usize),
+            None => Ok(record.take().unwrap()),
+        };
+
+        let fixed_length = column.column_type().and_then(|t| t.fixed_length());
+        let parsed = current.and_then(|r| {
+            let start = r.fixed_read_offset();
+            let (value, r) = Value::parse(column, r, options)?;
+
+            // NULL values occupy their fixed length as well but are not read, other widths
+            // would misalign all following columns
+            match fixed_length {
+                Some(length) => {
+                    let read = r.fixed_read_offset() - start;
+                    if read != 0 && read != length {
+                        return Err(
+                            "Value length differs from the fixed length of the column type",
+                        );
+                    }
+                    let end = start - r.offset() + length;
+                    Ok((value, r.seek_fixed_offset(end)?))
+                }
+                None => Ok((value, r)),
+            }
+        });
+
+        let (value, r) = match parsed {
+            Ok((value, r)) => (value, r),
+            Err(e) => {
+                let err = Error::ColumnParse {
+                    table: table.name().to_string(),
+                    column: column.name.to_string(),
+                    page_id: page.header().page_pointer.page_id,
+                    offset: offset as u16,
+                    source: e.into(),
+                };
+                if options.strict {
+                    return Err(err);
+                }
+                options.report_record_error(&err);
+                break;
+            }
+        };
+
+        f(index, column, value);
+
+        record = Some(r);
+    }
+
+    // Left over fixed length data hints at a missing column or a wrong column width
+    if let Some(remaining) = record.map(|r| r.remaining_fixed_bytes().len()) {
+        if remaining > 0 {
+            debug!(
+                "{} fixed length bytes of a record of {} on page {:?} are not consumed",
+                remaining,
+                table.name(),
+                page.header().page_pointer
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// The physical location of a row: the page and the slot within that page.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Rid {
+    pub page_id: u32,
+    pub file_id: u16,
+    pub slot: u16,
+}
+
+/// A decoded column value.
+///
+/// Equality is structural: values of different variants, e.g. `Int(1)` and `BigInt(1)`, are
+/// not equal. Use [`Value::numeric_eq`] to compare numbers by their value. Floats are equal if
+/// their bits are, so that `Eq` and `Hash` hold for `NaN`, while `0.0` and `-0.0` differ.
+#[derive(Clone, Debug)]
+pub enum Value {
+    Bit(bool),
+    TinyInt(i8),
+    SmallInt(i16),
+    Int(i32),
+    BigInt(i64),
+    Decimal(Decimal),
+    /// The value of a `money` or `smallmoney` column, which SQL Server stores in ten-thousandths
+    /// and displays with 4 decimal places.
+    Money(Decimal),
+    /// The value of a `float` or `real` column. `real` values are widened to `f64` losslessly.
+    Float(f64),
+    String(String),
+    DateTime(DateTime<Utc>),
+    /// The value of a `datetimeoffset` column with the offset of its time zone.
+    DateTimeOffset(DateTime<FixedOffset>),
+    /// The value of a `date` column.
+    Date(NaiveDate),
+    /// The value of a `time` column.
+    Time(NaiveTime),
+    Uuid(Uuid),
+    /// The value of a `binary` or `varbinary` column.
+    Binary(Vec<u8>),
+    /// The value of a `rowversion` (formerly `timestamp`) column.
+    RowVersion([u8; 8]),
+    /// The value of an `xml` column in SQL Server's binary XML format, also if it is stored out
+    /// of row in the LOB data allocation unit. Decoding the tokens into XML text is not
+    /// supported yet.
+    Xml(Vec<u8>),
+    /// A reference to a row by its physical location, e.g. stored in the rows of non-clustered
+    /// indexes on heaps.
+    Rid {
+        page_id: u32,
+        file_id: u16,
+        slot: u16,
+    },
+    Null,
+}
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Value::Bit(a), Value::Bit(b)) => a == b,
+            (Value::TinyInt(a), Value::TinyInt(b)) => a == b,
+            (Value::SmallInt(a), Value::SmallInt(b)) => a == b,
+            (Value::Int(a), Value::Int(b)) => a == b,
+            (Value::BigInt(a), Value::BigInt(b)) => a == b,
+            (Value::Decimal(a), Value::Decimal(b)) => a == b,
+            (Value::Money(a), Value::Money(b)) => a == b,
+            (Value::Float(a), Value::Float(b)) => a.to_bits() == b.to_bits(),
+            (Value::String(a), Value::String(b)) => a == b,
+            (Value::DateTime(a), Value::DateTime(b)) => a == b,
+            // Equal instants with different offsets are distinct values
+            (Value::DateTimeOffset(a), Value::DateTimeOffset(b)) => {
+                a == b && a.offset() == b.offset()
+            }
+            (Value::Date(a), Value::Date(b)) => a == b,
+            (Value::Time(a), Value::Time(b)) => a == b,
+            (Value::Uuid(a), Value::Uuid(b)) => a == b,
+            (Value::Binary(a), Value::Binary(b)) => a == b,
+            (Value::RowVersion(a), Value::RowVersion(b)) => a == b,
+            (Value::Xml(a), Value::Xml(b)) => a == b,
+            (
+                Value::Rid {
+                    page_id,
+                    file_id,
+                    slot,
+                },
+                Value::Rid {
+                    page_id: other_page_id,
+                    file_id: other_file_id,
+                    slot: other_slot,
+                },
+            ) => (page_id, file_id, slot) == (other_page_id, other_file_id, other_slot),
+            (Value::Null, Value::Null) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Value {}
+
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Value::Bit(bit) => bit.hash(state),
+            Value::TinyInt(i) => i.hash(state),
+            Value::SmallInt(i) => i.hash(state),
+            Value::Int(i) => i.hash(state),
+            Value::BigInt(i) => i.hash(state),
+            Value::Decimal(decimal) | Value::Money(decimal) => decimal.hash(state),
+            Value::Float(f) => f.to_bits().hash(state),
+            Value::String(s) => s.hash(state),
+            Value::DateTime(d) => d.hash(state),
+            Value::DateTimeOffset(d) => d.hash(state),
+            Value::Date(d) => d.hash(state),
+            Value::Time(t) => t.hash(state),
+            Value::Uuid(uuid) => uuid.hash(state),
+            Value::Binary(bytes) | Value::Xml(bytes) => bytes.hash(state),
+            Value::RowVersion(bytes) => bytes.hash(state),
+            Value::Rid {
+                page_id,
+                file_id,
+                slot,
+            } => (page_id, file_id, slot).hash(state),
+            Value::Null => {}
+        }
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            Value::Bit(bit) => write!(fmt, "{}", bit),
+            Value::TinyInt(i) => write!(fmt, "{}", i),
+            Value::SmallInt(i) => write!(fmt, "{}", i),
+            Value::Int(i) => write!(fmt, "{}", i),
+            Value::BigInt(i) => write!(fmt, "{}", i),
+            Value::Decimal(decimal) => write!(fmt, "{}", decimal),
+            Value::Money(money) => {
+                let mut money = *money;
+                money.rescale(4);
+                write!(fmt, "{}", money)
+            }
+            Value::Float(f) => write!(fmt, "{}", f),
+            Value::String(s) => write!(fmt, "{}", s),
+            Value::DateTime(d) => write!(fmt, "{}", d),
+            Value::DateTimeOffset(d) => write!(fmt, "{}", d),
+            Value::Date(d) => write!(fmt, "{}", d),
+            Value::Time(t) => write!(fmt, "{}", t),
+            Value::Uuid(uuid) => write!(fmt, "{}", uuid),
+            Value::Binary(bytes) => write_hex(fmt, bytes),
+            Value::RowVersion(bytes) => write_hex(fmt, bytes),
+            Value::Xml(bytes) => write_hex(fmt, bytes),
+            Value::Rid {
+                page_id,
+                file_id,
+                slot,
+            } => write!(fmt, "({}:{}:{})", file_id, page_id, slot),
+            Value::Null => write!(fmt, "null"),
+        }
+    }
+}
+
+/// Rescales the decimal to the given scale if neither fractional digits get lost nor the
+/// precision is exceeded.
+fn coerce_decimal(decimal: Decimal, precision: u8, scale: u8) -> Option<Decimal> {
+    let scale = scale as u32;
+    if decimal.scale() > scale && decimal.round_dp(scale) != decimal {
+        return None;
+    }
+
+    let mut decimal = decimal;
+    decimal.rescale(scale);
+    if decimal.scale() != scale {
+        return None;
+    }
+
+    let max_mantissa = 10i128.checked_pow(precision as u32).unwrap_or(i128::MAX);
+    if decimal.mantissa().abs() < max_mantissa {
+        Some(decimal)
+    } else {
+        None
+    }
+}
+
+/// Writes the bytes like SQL Server renders binary values, e.g. `0x0A1B`.
+fn write_hex(fmt: &mut Formatter<'_>, bytes: &[u8]) -> Result<(), std::fmt::Error> {
+    write!(fmt, "0x")?;
+    for byte in bytes {
+        write!(fmt, "{:02X}", byte)?;
+    }
+    Ok(())
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::String(s.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::String(s)
+    }
+}
+
+impl From<i32> for Value {
+    fn from(i: i32) -> Self {
+        Value::Int(i)
+    }
+}
+
+impl From<i64> for Value {
+    fn from(i: i64) -> Self {
+        Value::BigInt(i)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(bit: bool) -> Self {
+        Value::Bit(bit)
+    }
+}
+
+impl From<Uuid> for Value {
+    fn from(uuid: Uuid) -> Self {
+        Value::Uuid(uuid)
+    }
+}
+
+impl From<Decimal> for Value {
+    fn from(decimal: Decimal) -> Self {
+        Value::Decimal(decimal)
+    }
+}
+
+/// Converts `None` into `Value::Null`, e.g. `Value::from(None::<i32>)`.
+impl<T> From<Option<T>> for Value
+where
+    T: Into<Value>,
+{
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Value::Null, Into::into)
+    }
+}
+
+impl Value {
+    /// Returns the raw bytes of binary and XML values and `None` for all other values.
+    ///
+    /// ```rust
+    /// use oxidized_mdf::Value;
+    ///
+    /// assert_eq!(Value::Binary(vec![1, 2]).as_bytes(), Some(&[1u8, 2][..]));
+    /// assert_eq!(Value::Int(1).as_bytes(), None);
+    /// ```
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Value::Binary(bytes) => Some(bytes),
+            Value::RowVersion(bytes) => Some(bytes),
+            Value::Xml(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    /// Returns the milliseconds since the Unix epoch of date values and `None` for all other
+    /// values. Finer fractions of a second, e.g. of `datetime2` values, are truncated.
+    ///
+    /// ```rust
+    /// use chrono::{Duration, TimeZone, Utc};
+    /// use oxidized_mdf::Value;
+    ///
+    /// let date = Utc.with_ymd_and_hms(2004, 6, 1, 13, 14, 15).unwrap();
+    /// let value = Value::DateTime(date + Duration::milliseconds(120));
+    /// assert_eq!(value.as_epoch_millis(), Some(1_086_095_655_120));
+    /// assert_eq!(Value::Int(1).as_epoch_millis(), None);
+    /// ```
+    pub fn as_epoch_millis(&self) -> Option<i64> {
+        match self {
+            Value::DateTime(d) => Some(d.timestamp_millis()),
+            Value::DateTimeOffset(d) => Some(d.timestamp_millis()),
+            Value::Date(d) => Some(
+                Utc.from_utc_datetime(&d.and_hms_opt(0, 0, 0).unwrap())
+                    .timestamp_millis(),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of integer values and `None` for all other values, including decimals.
+    ///
+    /// ```rust
+    /// use oxidized_mdf::Value;
+    ///
+    /// assert_eq!(Value::SmallInt(300).as_i64(), Some(300));
+    /// assert_eq!(Value::String(String::from("300")).as_i64(), None);
+    /// ```
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Value::TinyInt(i) => Some(i64::from(*i)),
+            Value::SmallInt(i) => Some(i64::from(*i)),
+            Value::Int(i) => Some(i64::from(*i)),
+            Value::BigInt(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    /// Returns integer values that fit into the target type and `None` for all other values,
+    /// so that values are never truncated.
+    ///
+    /// ```rust
+    /// use oxidized_mdf::Value;
+    ///
+    /// assert_eq!(Value::BigInt(200).as_u8_checked(), Some(200));
+    /// assert_eq!(Value::BigInt(300).as_u8_checked(), None);
+    /// assert_eq!(Value::Int(-1).as_u8_checked(), None);
+    /// ```
+    pub fn as_u8_checked(&self) -> Option<u8> {
+        self.as_integer_checked()
+    }
+
+    /// Returns integer values that fit into `i8`, see [`as_u8_checked`](Self::as_u8_checked).
+    pub fn as_i8_checked(&self) -> Option<i8> {
+        self.as_integer_checked()
+    }
+
+    /// Returns integer values that fit into `i16`, see [`as_u8_checked`](Self::as_u8_checked).
+    pub fn as_i16_checked(&self) -> Option<i16> {
+        self.as_integer_checked()
+    }
+
+    /// Returns integer values that fit into `i32`, see [`as_u8_checked`](Self::as_u8_checked).
+    pub fn as_i32_checked(&self) -> Option<i32> {
+        self.as_integer_checked()
+    }
+
+    /// Returns integer values that fit into `u16`, see [`as_u8_checked`](Self::as_u8_checked).
+    pub fn as_u16_checked(&self) -> Option<u16> {
+        self.as_integer_checked()
+    }
+
+    /// Returns integer values that fit into `u32`, see [`as_u8_checked`](Self::as_u8_checked).
+    pub fn as_u32_checked(&self) -> Option<u32> {
+        self.as_integer_checked()
+    }
+
+    /// Returns integer values that fit into `u64`, see [`as_u8_checked`](Self::as_u8_checked).
+    pub fn as_u64_checked(&self) -> Option<u64> {
+        self.as_integer_checked()
+    }
+
+    fn as_integer_checked<T>(&self) -> Option<T>
+    where
+        T: TryFrom<i64>,
+    {
+        self.as_i64().and_then(|i| T::try_from(i).ok())
+    }
+
+    /// Returns a wrapper rendering the value according to the given options, e.g. to render
+    /// NULL as `NULL` for SQL or as empty string for CSV.
+    ///
+    /// ```rust
+    /// use oxidized_mdf::format::{FormatOptions, NullFormat};
+    /// use oxidized_mdf::Value;
+    ///
+    /// let options = FormatOptions {
+    ///     null: NullFormat::Empty,
+    ///     ..FormatOptions::default()
+    /// };
+    ///
+    /// assert_eq!(Value::Null.display_with(&options).to_string(), "");
+    /// assert_eq!(Value::Int(42).display_with(&options).to_string(), "42");
+    /// ```
+    pub fn display_with<'a>(&'a self, options: &'a FormatOptions) -> DisplayValue<'a> {
+        DisplayValue {
+            value: self,
+            options,
+        }
+    }
+
+    /// Renders the value according to the given options. The exporters render all values
+    /// through this method and only differ in their options.
+    ///
+    /// ```rust
+    /// use oxidized_mdf::format::{FormatOptions, GuidFormat};
+    /// use oxidized_mdf::Value;
+    /// use uuid::Uuid;
+    ///
+    /// let options = FormatOptions {
+    ///     guid: GuidFormat::Braced,
+    ///     ..FormatOptions::default()
+    /// };
+    ///
+    /// assert_eq!(
+    ///     Value::Uuid(Uuid::nil()).to_display_string(&options),
+    ///     "{00000000-0000-0000-0000-000000000000}"
+    /// );
+    /// ```
+    pub fn to_display_string(&self, options: &FormatOptions) -> String {
+        self.display_with(options).to_string()
+    }
+
+    /// Compares numeric values by their value regardless of their width, e.g. `Int(1)` equals
+    /// `BigInt(1)` and `Decimal(1.00)`, and regardless of their scale, e.g. `Decimal(1.50)`
+    /// equals `Decimal(1.5)`. In contrast, `==` compares structurally and considers different
+    /// variants as unequal.
+    ///
+    /// Non-numeric values, including `Null`, are never numerically equal.
+    ///
+    /// ```rust
+    /// use oxidized_mdf::Value;
+    ///
+    /// assert_ne!(Value::Int(1), Value::BigInt(1));
+    /// assert!(Value::Int(1).numeric_eq(&Value::BigInt(1)));
+    /// ```
+    pub fn numeric_eq(&self, other: &Value) -> bool {
+        match (self.as_decimal(), other.as_decimal()) {
+            (Some(lhs), Some(rhs)) => lhs == rhs,
+            _ => false,
+        }
+    }
+
+    /// Converts the value to the given column type, e.g. to insert it into a column of another
+    /// type. Conversions that keep the value, like widening `TinyInt` to `Int` or rendering any
+    /// value as string, succeed. Conversions that would lose information, like narrowing a
+    /// `BigInt` that exceeds the range of `Int`, fail with [`Error::LossyCoercion`]. `Null` is
+    /// kept as `Null` for every type.
+    ///
+    /// ```rust
+    /// use oxidized_mdf::schema::ColumnType;
+    /// use oxidized_mdf::Value;
+    ///
+    /// assert_eq!(Value::TinyInt(7).coerce_to(ColumnType::Int).unwrap(), Value::Int(7));
+    /// assert!(Value::BigInt(1 << 40).coerce_to(ColumnType::Int).is_err());
+    /// ```
+    pub fn coerce_to(&self, target: ColumnType) -> Result<Value, Error> {
+        let coerced = match (self, target) {
+            (Value::Null, _) => Some(Value::Null),
+            (Value::Bit(bit), ColumnType::Bit) => Some(Value::Bit(*bit)),
+            (Value::DateTime(d), ColumnType::DateTime | ColumnType::DateTime2 { .. }) => {
+                Some(Value::DateTime(*d))
+            }
+            (Value::DateTimeOffset(d), ColumnType::DateTimeOffset { .. }) => {
+                Some(Value::DateTimeOffset(*d))
+            }
+            (Value::Date(d), ColumnType::Date) => Some(Value::Date(*d)),
+            (Value::Date(d), ColumnType::DateTime | ColumnType::DateTime2 { .. }) => Some(
+                Value::DateTime(Utc.from_utc_datetime(&d.and_hms_opt(0, 0, 0).unwrap())),
+            ),
+            // Only midnight converts to a date without losing the time
+            (Value::DateTime(d), ColumnType::Date)
+                if d.time() == NaiveTime::from_hms_opt(0, 0, 0).unwrap() =>
+            {
+                Some(Value::Date(d.naive_utc().date()))
+            }
+            (Value::Time(t), ColumnType::Time { .. }) => Some(Value::Time(*t)),
+            (Value::Uuid(uuid), ColumnType::Uuid) => Some(Value::Uuid(*uuid)),
+            (Value::Binary(bytes) | Value::Xml(bytes), ColumnType::Binary) => {
+                Some(Value::Binary(bytes.clone()))
+            }
+            (Value::Binary(bytes) | Value::Xml(bytes), ColumnType::FixedBinary { length })
+                if bytes.len() <= length as usize =>
+            {
+                Some(Value::Binary(bytes.clone()))
+            }
+            (Value::RowVersion(bytes), ColumnType::RowVersion) => Some(Value::RowVersion(*bytes)),
+            (Value::RowVersion(bytes), ColumnType::Binary) => Some(Value::Binary(bytes.to_vec())),
+            (Value::RowVersion(bytes), ColumnType::FixedBinary { length }) if length >= 8 => {
+                Some(Value::Binary(bytes.to_vec()))
+            }
+            (Value::Xml(bytes), ColumnType::Xml) => Some(Value::Xml(bytes.clone())),
+            (Value::Float(f), ColumnType::Float) => Some(Value::Float(*f)),
+            // Narrowing to real keeps values that are exactly representable with 4 bytes
+            (Value::Float(f), ColumnType::Real) if (*f as f32) as f64 == *f || f.is_nan() => {
+                Some(Value::Float(*f))
+            }
+            (value, ColumnType::String) => Some(Value::String(value.to_string())),
+            (value, ColumnType::Char { length }) => {
+                let s = value.to_string();
+                let (bytes, _, unmappable) = encoding_rs::WINDOWS_1252.encode(&s);
+                if !unmappable && bytes.len() <= length as usize {
+                    Some(Value::String(s))
+                } else {
+                    None
+                }
+            }
+            (value, ColumnType::NChar { length }) => {
+                let s = value.to_string();
+                if s.encode_utf16().count() * 2 <= length as usize {
+                    Some(Value::String(s))
+                } else {
+                    None
+                }
+            }
+            (value, ColumnType::TinyInt) => value
+                .as_integer()
+                .and_then(|i| i8::try_from(i).ok())
+                .map(Value::TinyInt),
+            (value, ColumnType::SmallInt) => value
+                .as_integer()
+                .and_then(|i| i16::try_from(i).ok())
+                .map(Value::SmallInt),
+            (value, ColumnType::Int) => value
+                .as_integer()
+                .and_then(|i| i32::try_from(i).ok())
+                .map(Value::Int),
+            (value, ColumnType::BigInt) => value.as_integer().map(Value::BigInt),
+            (value, ColumnType::Decimal { precision, scale }) => value
+                .as_decimal()
+                .and_then(|decimal| coerce_decimal(decimal, precision, scale))
+                .map(Value::Decimal),
+            // money has a fixed precision of 19 and scale of 4
+            (value, ColumnType::Money) => value
+                .as_decimal()
+                .and_then(|decimal| coerce_decimal(decimal, 19, 4))
+                .map(Value::Money),
+            // smallmoney has a fixed precision of 10 and scale of 4
+            (value, ColumnType::SmallMoney) => value
+                .as_decimal()
+                .and_then(|decimal| coerce_decimal(decimal, 10, 4))
+                .filter(|decimal| {
+                    (i32::MIN as i64..=i32::MAX as i64).contains(&(decimal.mantissa() as i64))
+                })
+                .map(Value::Money),
+            _ => None,
+        };
+
+        coerced.ok_or_else(|| Error::LossyCoercion {
+            value: self.clone(),
+            target,
+        })
+    }
+
+    /// Returns integers and decimals without fractional digits as `i64`.
+    fn as_integer(&self) -> Option<i64> {
+        let decimal = self.as_decimal()?;
+        if decimal.fract().is_zero() {
+            decimal.trunc().to_i64()
+        } else {
+            None
+        }
+    }
+
+    fn as_decimal(&self) -> Option<Decimal> {
+        match self {
+            Value::TinyInt(i) => Some(Decimal::from(*i)),
+            Value::SmallInt(i) => Some(Decimal::from(*i)),
+            Value::Int(i) => Some(Decimal::from(*i)),
+            Value::BigInt(i) => Some(Decimal::from(*i)),
+            Value::Decimal(decimal) | Value::Money(decimal) => Some(*decimal),
+            _ => None,
+        }
+    }
+
+    fn parse<'a>(
+        column: &Column<'_>,
+        record: Record<'a>,
+        options: &Options,
+    ) -> Result<(Self, Record<'a>), &'static str> {
+        let column_type = column.column_type().ok_or("Unknown column type")?;
+
+        match column_type {
+            ColumnType::Bit => {
+                // Without a location the bits are tracked by the record, see Record::parse_bit
+                let (bit, r) = match (column.leaf_offset, column.leaf_bit_position) {
+                    (Some(_), Some(position)) => record.parse_bit_at(position)?,
+                    _ => record.parse_bit()?,
+                };
+                Ok((bit.map_or(Value::Null, Value::Bit), r))
+            }
+            ColumnType::Date => {
+                let (date, r) = record.parse_date_opt()?;
+                Ok((date.map_or(Value::Null, Value::Date), r))
+            }
+            ColumnType::DateTime => {
+                let (datetime, r) = record.parse_datetime_opt()?;
+                Ok((datetime.map_or(Value::Null, Value::DateTime), r))
+            }
+            ColumnType::DateTime2 { scale } => {
+                let (datetime, r) = record.parse_datetime2_opt(scale)?;
+                Ok((datetime.map_or(Value::Null, Value::DateTime), r))
+            }
+            ColumnType::DateTimeOffset { scale } => {
+                let (datetime, r) = record.parse_datetimeoffset_opt(scale)?;
+                Ok((datetime.map_or(Value::Null, Value::DateTimeOffset), r))
+            }
+            ColumnType::Time { scale } => {
+                let (time, r) = record.parse_time_opt(scale)?;
+                Ok((time.map_or(Value::Null, Value::Time), r))
+            }
+            ColumnType::TinyInt => {
+                let (int, r) = record.parse_i8()?;
+                Ok((Value::TinyInt(int), r))
+            }
+            ColumnType::SmallInt => {
+                let (int, r) = record.parse_i16()?;
+                Ok((Value::SmallInt(int), r))
+            }
+            ColumnType::Int => {
+                let (int, r) = record.parse_i32_opt()?;
+                Ok((int.map_or(Value::Null, Value::Int), r))
+            }
+            ColumnType::Money => {
+                let (money, r) = record.parse_money_opt()?;
+                Ok((money.map_or(Value::Null, Value::Money), r))
+            }
+            ColumnType::SmallMoney => {
+                let (money, r) = record.parse_smallmoney_opt()?;
+                Ok((money.map_or(Value::Null, Value::Money), r))
+            }
+            ColumnType::BigInt => {
+                let (int, r) = record.parse_i64_opt()?;
+                Ok((int.map_or(Value::Null, Value::BigInt), r))
+            }
+            ColumnType::Char { length } => {
+                let (string, r) =
+                    record.parse_char_opt(length as usize, options.trim_char_padding)?;
+                Ok((string.map_or(Value::Null, Value::String), r))
+            }
+            ColumnType::NChar { length } => {
+                // Reads use the length in bytes, see Column::max_characters for the characters
+                let (string, r) = record.parse_nchar_opt(length as usize)?;
+                Ok((string.map_or(Value::Null, Value::String), r))
+            }
+            ColumnType::String => {
+                let (string, r) = record.parse_string_as(options.empty_string_mode)?;
+                Ok((string.map_or(Value::Null, Value::String), r))
+            }
+            ColumnType::FixedBinary { length } => {
+                let (bytes, r) = record.parse_bytes_opt(length as usize)?;
+                Ok((bytes.map_or(Value::Null, |b| Value::Binary(b.to_vec())), r))
+            }
+            ColumnType::Binary => {
+                // (max) columns and row-overflow data only store a pointer in the record
+                let (bytes, r) = record.parse_lob_bytes_opt()?;
+                Ok((
+                    bytes.map_or(Value::Null, |b| Value::Binary(b.into_owned())),
+                    r,
+                ))
+            }
+            ColumnType::RowVersion => {
+                let (bytes, r) = record.parse_bytes(8)?;
+                let mut row_version = [0u8; 8];
+                row_version.copy_from_slice(bytes);
+                Ok((Value::RowVersion(row_version), r))
+            }
+            ColumnType::Xml => {
+                let (bytes, r) = record.parse_lob_bytes_opt()?;
+                Ok((bytes.map_or(Value::Null, |b| Value::Xml(b.into_owned())), r))
+            }
+            ColumnType::Uuid => {
+                let (uuid, r) = record.parse_uuid_opt()?;
+                Ok((uuid.map_or(Value::Null, Value::Uuid), r))
+            }
+            ColumnType::Real => {
+                let (float, r) = record.parse_f32_opt()?;
+                Ok((float.map_or(Value::Null, |f| Value::Float(f as f64)), r))
+            }
+            ColumnType::Float => {
+                let (float, r) = record.parse_f64_opt()?;
+                Ok((float.map_or(Value::Null, Value::Float), r))
+            }
+            ColumnType::Decimal { precision, scale } => {
+                let (decimal, r) = record.parse_decimal_opt(precision, scale)?;
+                Ok((decimal.map_or(Value::Null, Value::Decimal), r))
+            }
+            ColumnType::Rid => {
+                let ((page_id, file_id, slot), r) = record.parse_rid()?;
+                Ok((
+                    Value::Rid {
+                        page_id,
+                        file_id,
+                        slot,
+                    },
+                    r,
+                ))
+            }
+        }
+    }
+}
+
+/// A row of a table, mapping each column name to its value.
+///
+/// Rows implement `Hash` and `Eq` over all of their columns so that they can be deduplicated
+/// with a `HashSet`.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Row {
+    columns: BTreeMap<String, Value>,
+}
+
+impl Row {
+    /// Parses the values of the stored columns. If a value cannot be decoded, the row contains
+    /// the values decoded so far unless [`Options::strict`] is set, which results in an error.
+    fn parse(
+        table: &Table<'_>,
+        page: &Page,
+        record: Record<'_>,
+        options: &Options,
+    ) -> Result<Self, Error> {
+        let mut columns = BTreeMap::new();
+        parse_columns(table, page, record, options, |_, column, value| {
+            columns.insert(column.name.to_string(), value);
+        })?;
+
+        Ok(Self { columns })
+    }
+
+    /// Parses the values of the columns in schema order like [`Row::parse`], without their
+    /// names. Columns whose values are not stored or could not be decoded are `None`, which
+    /// includes all columns after the first column that could not be decoded.
+    fn parse_values(
+        table: &Table<'_>,
+        page: &Page,
+        record: Record<'_>,
+        options: &Options,
+    ) -> Result<Vec<Option<Value>>, Error> {
+        let mut values = vec![None; table.columns.len()];
+        parse_columns(table, page, record, options, |index, _, value| {
+            values[index] = Some(value);
+        })?;
+
+        Ok(values)
+    }
+
+    pub fn value(&self, column_name: &str) -> Option<&Value> {
+        self.columns.get(column_name)
+    }
+
+    pub fn values(self) -> Vec<(String, Value)> {
+        self.columns.into_iter().collect()
+    }
+
+    /// Keeps only the given columns. Names of columns that the row does not contain are
+    /// ignored.
+    pub fn project(mut self, column_names: &[&str]) -> Row {
+        self.columns
+            .retain(|name, _| column_names.contains(&name.as_str()));
+        self
+    }
+
+    /// Renames the column `old` to `new`, replacing a column that is already named `new`. The
+    /// row is unchanged if it does not contain the column `old`.
+    pub fn rename(mut self, old: &str, new: &str) -> Row {
+        if let Some(value) = self.columns.remove(old) {
+            self.columns.insert(new.to_string(), value);
+        }
+        self
+    }
+}
+
+/// Counters of the reads from the database file, see [`MdfDatabase::io_stats`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct IoStats {
+    /// The number of pages read from the file.
+    pub pages_read: u64,
+    /// The number of bytes read from the file.
+    pub bytes_read: u64,
+    /// The number of page requests served from the page cache.
+    pub cache_hits: u64,
+    /// The number of page requests that had to read the page from the file.
+    pub cache_misses: u64,
+}
+
+struct PageReader {
+    read: Box<dyn Read + Unpin>,
+    page_index: u32,
+    page_cache: HashMap<PagePointer, Rc<Page>>,
+    io_stats: IoStats,
+}
+
+impl PageReader {
+    fn new(read: Box<dyn Read + Unpin>) -> Self {
+        Self {
+            read,
+            page_index: 0,
+            page_cache: HashMap::new(),
+            io_stats: IoStats::default(),
+        }
+    }
+
+    async fn read_next_page(&mut self, buffer: &mut [u8; 8192]) -> Result<(), Error> {
+        self.read.read_exact(&mut buffer[..]).await?;
+        self.page_index += 1;
+        self.io_stats.pages_read += 1;
+        self.io_stats.bytes_read += buffer.len() as u64;
+        Ok(())
+    }
+
+    /// The boot page is page 9 by convention, scanning the first pages for it tolerates files
+    /// with a different layout.
+    const MAX_BOOT_PAGE_ID: u32 = 16;
+
+    /// Reads the pages from the start of the file until the boot page is found.
+    async fn read_boot_page(&mut self) -> Result<BootPage, Error> {
+        while self.page_index <= Self::MAX_BOOT_PAGE_ID {
+            let page_id = self.page_index;
+            let mut buffer = [0u8; 8192];
+            self.read_next_page(&mut buffer).await?;
+
+            let page = Page::try_from(buffer).unwrap();
+            let is_boot_page = page.header().page_type() == BOOT_PAGE;
+            self.page_cache.insert(
+                PagePointer {
+                    page_id,
+                    file_id: 1,
+                },
+                Rc::new(page),
+            );
+
+            if is_boot_page {
+                if page_id != 9 {
+                    warn!("Found the boot page at page {} instead of page 9", page_id);
+                }
+
+                let boot_page = BootPage::try_from(buffer).unwrap();
+                boot_page
+                    .validate(page_id)
+                    .map_err(|reason| Error::InvalidBootPage { page_id, reason })?;
+                return Ok(boot_page);
+            }
+        }
+
+        Err(Error::BootPageNotFound)
+    }
+
+    async fn read_page(&mut self, page_pointer: &PagePointer) -> Result<Rc<Page>, Error> {
+        if let Some(page) = self.page_cache.get(page_pointer) {
+            self.io_stats.cache_hits += 1;
+            return Ok(page.clone());
+        }
+        self.io_stats.cache_misses += 1;
+
+        // Every page read so far is cached, so errors of earlier streams cannot lead to this
+        if self.page_index > page_pointer.page_id {
+            return Err(Error::IoError(async_std::io::Error::other(format!(
+                    "Currently the database supports only forward reading and the requested page {} has been already read",
+                page_pointer.page_id
+            ))));
+        }
+
+        for i in self.page_index..=page_pointer.page_id {
+            let mut buffer = [0u8; 8192];
+            self.read_next_page(&mut buffer).await?;
+
+            let page = Page::try_from(buffer).unwrap();
+
+            self.page_cache
+                .insert(page_pointer.with_page_id(i), Rc::new(page));
+        }
+
+        let page = self.page_cache.get(page_pointer).unwrap();
+        Ok(page.clone())
+    }
+
+    fn read_pages_of_table<'a, 'b: 'a>(&'b mut self, table: &Table<'_>) -> PageStream<'a> {
+        PageStream::new(self.scan_pages_of_table(table))
+    }
+
+    fn scan_pages_of_table<'a, 'b: 'a>(&'b mut self, table: &Table<'_>) -> PageScan<'a> {
+        let iam_fallback = IamFallback {
+            iam_page_pointers: table.iam_page_pointers(),
+            allocation_unit_ids: table.allocation_unit_ids(),
+            expected_records: table.row_count().max(0) as usize,
+            heap: table.is_heap(),
+        };
+
+        // The data pages of clustered tables form the leaf level of the index and are linked in
+        // key order. The first page of heaps has no successor, so all pages after it are read in
+        // allocation order.
+        PageScan {
+            iam_fallback: Some(iam_fallback),
+            ..self.scan_pages_of_pointers(table.page_pointers())
+        }
+    }
+
+    /// Reads the pages of the LOB data stored out of row that the complex columns of the records
+    /// of the page point to, following the roots of the values to their fragments. Pages that
+    /// cannot be read are skipped, reading the values stored on them fails later on.
+    async fn read_lob_pages(&mut self, page: &Page) -> LobPages {
+        let mut lob_pages = LobPages::new();
+
+        let mut links = page
+            .complex_columns()
+            .into_iter()
+            .filter_map(|pointer| lob_links(pointer).ok())
+            .flatten()
+            .collect::<Vec<_>>();
+        let mut visited_links = HashSet::new();
+
+        while let Some(link) = links.pop() {
+            if !visited_links.insert((link.page_pointer.clone(), link.slot)) {
+                continue;
+            }
+
+            let lob_page = match self.read_page(&link.page_pointer).await {
+                Ok(lob_page) => lob_page,
+                Err(err) => {
+                    warn!("Cannot read LOB page {:?}: {}", link.page_pointer, err);
+                    continue;
+                }
+            };
+
+            if let Ok(LobRecord::Root(child_links)) = lob_page.lob_record(link.slot) {
+                links.extend(child_links);
+            }
+            lob_pages.insert(link.page_pointer, lob_page);
+        }
+
+        lob_pages
+    }
+
+    fn read_pages_of_pointers<'a, 'b: 'a>(
+        &'b mut self,
+        page_pointers: Vec<PagePointer>,
+    ) -> PageStream<'a> {
+        PageStream::new(self.scan_pages_of_pointers(page_pointers))
+    }
+
+    fn scan_pages_of_pointers<'a, 'b: 'a>(
+        &'b mut self,
+        page_pointers: Vec<PagePointer>,
+    ) -> PageScan<'a> {
+        PageScan {
+            page_pointers: Box::new(page_pointers.into_iter()),
+            page_reader: self,
+            current_page: None,
+            visited_pages: HashSet::new(),
+            read_records: 0,
+            iam_fallback: None,
+            allocated_pages: None,
+            cycle_is_error: false,
+        }
+    }
+
+    /// Reads the pages of a system table, whose page chain must not form a cycle because the
+    /// database cannot be opened with incomplete metadata.
+    fn read_system_pages_of_pointer<'a, 'b: 'a>(
+        &'b mut self,
+        page_pointer: PagePointer,
+    ) -> PageStream<'a> {
+        PageStream::new(PageScan {
+            cycle_is_error: true,
+            ..self.scan_pages_of_pointers(vec![page_pointer])
+        })
+    }
+}
+
+/// Describes how to enumerate the pages of a table via its IAM chains when the linked list of
+/// pages ends before all rows of the table have been read, e.g. for heaps.
+struct IamFallback {
+    iam_page_pointers: Vec<PagePointer>,
+    allocation_unit_ids: Vec<i64>,
+    expected_records: usize,
+    /// Heaps have no page chain, so their pages are always enumerated via IAM, regardless of
+    /// the expected records.
+    heap: bool,
+}
+
+/// The state of reading the pages of a table, which [`PageStream`] advances page by page.
+struct PageScan<'a> {
+    page_pointers: Box<dyn Iterator<Item = PagePointer>>,
+    page_reader: &'a mut PageReader,
+    current_page: Option<Rc<Page>>,
+    visited_pages: HashSet<PagePointer>,
+    read_records: usize,
+    iam_fallback: Option<IamFallback>,
+    allocated_pages: Option<(std::vec::IntoIter<PagePointer>, Vec<i64>)>,
+    /// Yields [`Error::CorruptMetadata`] instead of stopping when the page chain forms a cycle.
+    cycle_is_error: bool,
+}
+
+impl<'a> PageScan<'a> {
+    async fn next_page(&mut self) -> Option<Result<Rc<Page>, Error>> {
+        if self.allocated_pages.is_some() {
+            return self.next_allocated_page().await;
+        }
+
+        // The page chain of each partition is followed until its end before continuing with
+        // the first page of the next partition
+        let mut next_page_pointer = self
+            .current_page
+            .take()
+            .and_then(|current_page| current_page.next_page_pointer().cloned());
+
+        let page_pointer = loop {
+            let page_pointer = match next_page_pointer.take() {
+                Some(page_pointer) => Some(page_pointer),
+                None => match self.page_pointers.next() {
+                    Some(page_pointer) => match self.leaf_page_pointer(page_pointer).await {
+                        Ok(Some(page_pointer)) => Some(page_pointer),
+                        Ok(None) => continue,
+                        Err(err) => return Some(Err(err)),
+                    },
+                    None => None,
+                },
+            };
+
+            match page_pointer {
+                Some(page_pointer) if self.visited_pages.contains(&page_pointer) => {
+                    if self.cycle_is_error {
+                        return Some(Err(Error::CorruptMetadata(format!(
+                            "page {:?} has been already read, the page chain forms a cycle",
+                            page_pointer
+                        ))));
+                    }
+
+                    warn!(
+                        "Page {:?} has been already read, the page chain forms a cycle and the scan continues with the next partition",
+                        page_pointer
+                    );
+                }
+                page_pointer => break page_pointer,
+            }
+        };
+
+        match page_pointer {
+            Some(page_pointer) => {
+                let page = self.page_reader.read_page(&page_pointer).await;
+
+                if let Ok(current_page) = &page {
+                    self.visited_pages.insert(page_pointer);
+                    self.read_records += current_page.header().slot_count as usize;
+                    self.current_page = Some(current_page.clone());
+                }
+
+                Some(page)
+            }
+            None => match self.iam_fallback.take() {
+                Some(iam_fallback)
+                    if iam_fallback.heap || self.read_records < iam_fallback.expected_records =>
+                {
+                    if !iam_fallback.heap {
+                        warn!(
+                            "Read {} of {} expected records by following the page chain, enumerating the remaining pages via IAM",
+                            self.read_records, iam_fallback.expected_records
+                        );
+                    }
+
+                    let allocated_pages = match self
+                        .read_allocated_pages(&iam_fallback.iam_page_pointers)
+                        .await
+                    {
+                        Ok(allocated_pages) => allocated_pages,
+                        Err(err) => return Some(Err(err)),
+                    };
+
+                    self.allocated_pages = Some((
+                        allocated_pages.into_iter(),
+                        iam_fallback.allocation_unit_ids,
+                    ));
+                    self.next_allocated_page().await
+                }
+                _ => None,
+            },
+        }
+    }
+
+    /// Resolves the given page to the leftmost leaf page of its B-tree if it is an index page,
+    /// e.g. the root page of a clustered index, by following the first record of each level.
+    ///
+    /// If the index records cannot be followed, the leaf pages of the allocation unit are
+    /// enumerated via IAM and the one without a previous page is taken.
+    async fn leaf_page_pointer(
+        &mut self,
+        page_pointer: PagePointer,
+    ) -> Result<Option<PagePointer>, Error> {
+        let root_page = self.page_reader.read_page(&page_pointer).await?;
+
+        let mut page_pointer = page_pointer;
+        let mut page = root_page.clone();
+        while page.header().level() > 0 {
+            let child_page = match page.first_child_page_pointer() {
+                Ok(child_page_pointer) => {
+                    let child_page = self.page_reader.read_page(&child_page_pointer).await?;
+                    // Each step has to descend one level, which also rules out cycles
+                    if child_page.header().level() + 1 == page.header().level() {
+                        Some((child_page_pointer, child_page))
+                    } else {
+                        None
+                    }
+                }
+                Err(err) => {
+                    debug!("Cannot follow index page {:?}: {}", page_pointer, err);
+                    None
+                }
+            };
+
+            match child_page {
+                Some((child_page_pointer, child_page)) => {
+                    page_pointer = child_page_pointer;
+                    page = child_page;
+                }
+                None => return self.leftmost_allocated_leaf_page(&root_page).await,
+            }
+        }
+
+        Ok(Some(page_pointer))
+    }
+
+    /// Finds the leaf page without a previous page among the allocated pages of the allocation
+    /// unit of the given root page.
+    async fn leftmost_allocated_leaf_page(
+        &mut self,
+        root_page: &Page,
+    ) -> Result<Option<PagePointer>, Error> {
+        let iam_page_pointers = match &self.iam_fallback {
+            Some(iam_fallback) => iam_fallback.iam_page_pointers.clone(),
+            None => Vec::new(),
+        };
+
+        for allocated_page in self.read_allocated_pages(&iam_page_pointers).await? {
+            let leaf_page = self.page_reader.read_page(&allocated_page).await?;
+            let header = leaf_page.header();
+            if header.page_type() == DATA_PAGE
+                && header.level() == 0
+                && header.alloc_unit_id == root_page.header().alloc_unit_id
+                && header.previous_page_pointer.is_none()
+            {
+                return Ok(Some(allocated_page));
+            }
+        }
+
+        warn!(
+            "Cannot find the leftmost leaf page below the root page {:?}",
+            root_page.header().page_pointer
+        );
+        Ok(None)
+    }
+
+    async fn read_allocated_pages(
+        &mut self,
+        iam_page_pointers: &[PagePointer],
+    ) -> Result<Vec<PagePointer>, Error> {
+        let mut allocated_pages = Vec::new();
+
+        for iam_page_pointer in iam_page_pointers {
+            let mut iam_page_pointer = Some(iam_page_pointer.clone());
+            let mut visited_iam_pages = HashSet::new();
+
+            while let Some(page_pointer) = iam_page_pointer.take() {
+                if !visited_iam_pages.insert(page_pointer.clone()) {
+                    break;
+                }
+
+                let iam_page = self.page_reader.read_page(&page_pointer).await?;
+                match iam_page.iam_allocated_pages() {
+                    Ok(pages) => allocated_pages.extend(pages),
+                    Err(err) => warn!("Cannot read IAM page {:?}: {}", page_pointer, err),
+                }
+
+                iam_page_pointer = iam_page.next_page_pointer().cloned();
+            }
+        }
+
+        allocated_pages.sort_by_key(|p| (p.file_id, p.page_id));
+        allocated_pages.dedup();
+
+        Ok(allocated_pages)
+    }
+
+    async fn next_allocated_page(&mut self) -> Option<Result<Rc<Page>, Error>> {
+        let (allocated_pages, allocation_unit_ids) = self.allocated_pages.as_mut()?;
+
+        for page_pointer in allocated_pages {
+            if self.visited_pages.contains(&page_pointer) {
+                continue;
+            }
+
+            let page = match self.page_reader.read_page(&page_pointer).await {
+                Ok(page) => page,
+                Err(err) => return Some(Err(err)),
+            };
+            self.visited_pages.insert(page_pointer);
+
+            let header = page.header();
+            if header.page_type() == DATA_PAGE
+                && header.level() == 0
+                && allocation_unit_ids.contains(&header.alloc_unit_id)
+            {
+                return Some(Ok(page));
+            }
+        }
+
+        None
+    }
+}
+
+type NextPage<'a, T> = Pin<Box<dyn Future<Output = (Option<Result<T, Error>>, PageScan<'a>)> + 'a>>;
+
+/// Streams the pages of a [`PageScan`]. The scan is moved into the future reading the next page
+/// while it is pending and restored once the page has been read.
+struct PageStream<'a, T = Rc<Page>> {
+    scan: Option<PageScan<'a>>,
+    next_page: Option<NextPage<'a, T>>,
+    read_next_page: fn(PageScan<'a>) -> NextPage<'a, T>,
+}
+
+impl<'a> PageStream<'a> {
+    fn new(scan: PageScan<'a>) -> Self {
+        Self {
+            scan: Some(scan),
+            next_page: None,
+            read_next_page: |mut scan| {
+                Box::pin(async move {
+                    let page = scan.next_page().await;
+                    (page, scan)
+                })
+            },
+        }
+    }
+}
+
+impl<'a> PageStream<'a, (Rc<Page>, LobPages)> {
+    /// Streams the pages together with the pages of the LOB data stored out of row that their
+    /// records point to.
+    fn with_lob_pages(scan: PageScan<'a>) -> Self {
+        Self {
+            scan: Some(scan),
+            next_page: None,
+            read_next_page: |mut scan| {
+                Box::pin(async move {
+                    let page = match scan.next_page().await {
+                        Some(Ok(page)) => {
+                            let lob_pages = scan.page_reader.read_lob_pages(&page).await;
+                            Some(Ok((page, lob_pages)))
+                        }
+                        Some(Err(err)) => Some(Err(err)),
+                        None => None,
+                    };
+                    (page, scan)
+                })
+            },
+        }
+    }
+}
+
+impl<'a, T> Stream for PageStream<'a, T> {
+    type Item = Result<T, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.next_page.is_none() {
+            let scan = match self.scan.take() {
+                Some(scan) => scan,
+                None => return Poll::Ready(None),
+            };
+            self.next_page = Some((self.read_next_page)(scan));
+        }
+
+        let (page, scan) = futures_lite::ready!(self.next_page.as_mut().unwrap().as_mut().poll(cx));
+        self.next_page = None;
+        self.scan = Some(scan);
+
+        Poll::Ready(page)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "compression")]
+    use crate::compression::{compression_info, CompressedRecordBuilder};
+    use crate::pages::{lob_link, lob_record, PageBuilder, RecordBuilder, IAM_PAGE, TEXT_MIX_PAGE};
+    use rstest::rstest;
+    use std::cell::Cell;
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    #[async_std::test]
+    async fn should_result_in_io_error_when_file_does_not_exists() {
+        match MdfDatabase::open("some-random-path").await {
+            Err(Error::IoError(err)) if err.kind() == async_std::io::ErrorKind::NotFound => {}
+            _ => panic!("Unexpected result"),
+        }
+    }
+
+    #[test]
+    fn deduplicate_rows() {
+        let row = |id: i32, city: &str| Row {
+            columns: vec![
+                (String::from("AddressID"), Value::Int(id)),
+                (String::from("City"), Value::String(city.to_string())),
+            ]
+            .into_iter()
+            .collect(),
+        };
+
+        let rows = vec![
+            row(1, "Bothell"),
+            row(2, "Surrey"),
+            row(1, "Bothell"),
+            row(1, "Surrey"),
+        ];
+
+        let unique_rows = rows.into_iter().collect::<HashSet<_>>();
+
+        assert_eq!(unique_rows.len(), 3);
+        assert!(unique_rows.contains(&row(1, "Bothell")));
+        assert!(unique_rows.contains(&row(2, "Surrey")));
+        assert!(unique_rows.contains(&row(1, "Surrey")));
+    }
+
+    #[async_std::test]
+    async fn stop_collecting_rows_at_first_error() {
+        // The fixed length data of the second record ends in the middle of the order id
+        let mut bytes = vec![0u8; 2 * 8192];
+        PageBuilder::new(DATA_PAGE, 1)
+            .record(&int_record(&[1]))
+            .record(&RecordBuilder::new(1).fixed(&[2, 0]).build())
+            .record(&int_record(&[3]))
+            .write_to(&mut bytes);
+        let reported_errors = Arc::new(Mutex::new(0));
+        let errors = reported_errors.clone();
+        let mut db = MdfDatabase {
+            page_reader: PageReader::new(Box::new(async_std::io::Cursor::new(bytes))),
+            boot_page: BootPage::try_from(PageBuilder::new(BOOT_PAGE, 9).build()).unwrap(),
+            base_table_data: BaseTableData::with_table("Orders", vec![("OrderID", "int", 4, 0)])
+                .with_clustered_index(1, 0),
+            options: Options {
+                on_record_error: Some(Arc::new(move |_| *errors.lock().unwrap() += 1)),
+                ..strict_options()
+            },
+        };
+
+        let result = db.collect_rows("Orders").await;
+
+        assert!(matches!(
+            result,
+            Err(Error::ColumnParse { column, .. }) if column == "OrderID"
+        ));
+        assert_eq!(*reported_errors.lock().unwrap(), 0);
+    }
+
+    #[async_std::test]
+    async fn rows_after_strict_error_in_other_table() {
+        // A single data page with two records of Orders
+        let mut bytes = vec![0u8; 2 * 8192];
+        PageBuilder::new(DATA_PAGE, 1)
+            .record(&int_record(&[1]))
+            .record(&int_record(&[2]))
+            .write_to(&mut bytes);
+        let documents = BaseTableData::with_table(
+            "Documents",
+            vec![
+                ("DocumentID", "int", 4, 0),
+                ("Payload", "sql_variant", 8016, 0),
+            ],
+        );
+        let orders = BaseTableData::with_table("Orders", vec![("OrderID", "int", 4, 0)])
+            .with_clustered_index(1, 0);
+        let options = strict_options();
+        let mut page_reader = PageReader::new(Box::new(async_std::io::Cursor::new(bytes)));
+
+        let document_rows = rows_of_table(
+            &mut page_reader,
+            documents.table("Documents").unwrap(),
+            options.clone(),
+        );
+        assert!(matches!(
+            document_rows.err(),
+            Some(Error::UnsupportedColumnType { column, .. }) if column == "Payload"
+        ));
+
+        let order_ids = rows_of_table(&mut page_reader, orders.table("Orders").unwrap(), options)
+            .unwrap()
+            .map(|row| row.unwrap().1.value("OrderID").cloned())
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(order_ids, vec![Some(Value::Int(1)), Some(Value::Int(2))]);
+    }
+
+    #[async_std::test]
+    async fn fail_scan_of_table_without_columns() {
+        let base_table_data = BaseTableData::with_table("Orders", vec![]);
+        let table = base_table_data.table("Orders").unwrap();
+        // Reading any page of the empty file would fail
+        let mut page_reader = PageReader::new(Box::new(async_std::io::Cursor::new(Vec::new())));
+
+        let rows = rows_of_table(&mut page_reader, table, Options::default());
+
+        assert!(matches!(
+            rows.err(),
+            Some(Error::NoColumns(table)) if table == "Orders"
+        ));
+    }
+
+    #[async_std::test]
+    async fn fail_scan_of_columnstore_table() {
+        let base_table_data =
+            BaseTableData::with_table("Orders", vec![("OrderID", "int", 4, 0)]).with_compression(3);
+        let table = base_table_data.table("Orders").unwrap();
+        // Reading any page of the empty file would fail
+        let mut page_reader = PageReader::new(Box::new(async_std::io::Cursor::new(Vec::new())));
+
+        let rows = rows_of_table(&mut page_reader, table, Options::default());
+
+        assert!(matches!(
+            rows.err(),
+            Some(Error::UnsupportedCompression {
+                compression: "columnstore",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn recover_deleted_rows() {
+        let base_table_data = BaseTableData::with_table(
+            "Orders",
+            vec![("OrderID", "int", 4, 0), ("Quantity", "int", 4, 0)],
+        );
+        let table = base_table_data.table("Orders").unwrap();
+
+        // The live order 1 in slot 0, the deleted order 2 and a record of another table after it
+        let bytes = PageBuilder::new(DATA_PAGE, 7)
+            .record(&int_record(&[1, 5]))
+            .bytes(&int_record(&[2, 3]))
+            .bytes(&[0x10, 0, 5, 0, 9, 1, 0, 0])
+            .build();
+
+        let page = Page::try_from(bytes).unwrap();
+        let recovered = recover_rows(&table, &page, &strict_options());
+
+        assert_eq!(recovered.len(), 1);
+        assert_eq!((recovered[0].page_id, recovered[0].offset), (7, 111));
+        assert_eq!(
+            recovered[0].row.clone().values(),
+            vec![
+                (String::from("OrderID"), Value::Int(2)),
+                (String::from("Quantity"), Value::Int(3)),
+            ]
+        );
+    }
+
+    #[rstest(
+        base_table_data,
+        table_name,
+        expected_error,
+        case(
+            BaseTableData::with_table("Orders", vec![]),
+            "Customers",
+            "unknown table 'Customers'"
+        ),
+        case(
+            BaseTableData::with_table("Orders", vec![]),
+            "Orders",
+            "no columns of table 'Orders' found"
+        ),
+        case(
+            BaseTableData::with_table("Orders", vec![("OrderID", "int", 4, 0)]).with_compression(2),
+            "Orders",
+            "table 'Orders' uses PAGE compression, which is not supported"
+        )
+    )]
+    fn fail_recovery_of_unreadable_table(
+        base_table_data: BaseTableData,
+        table_name: &str,
+        expected_error: &str,
+    ) {
+        let mut db = MdfDatabase {
+            // Reading any page of the empty file would fail
+            page_reader: PageReader::new(Box::new(async_std::io::Cursor::new(Vec::new()))),
+            boot_page: BootPage::try_from(PageBuilder::new(BOOT_PAGE, 9).build()).unwrap(),
+            base_table_data,
+            options: Options::default(),
+        };
+
+        let error = db.recover_deleted(table_name).err().unwrap();
+
+        assert_eq!(error.to_string(), expected_error);
+    }
+
+    fn address_row() -> Row {
+        Row {
+            columns: vec![
+                (String::from("AddressID"), Value::Int(1)),
+                (String::from("City"), Value::String(String::from("Bothell"))),
+                (
+                    String::from("PostalCode"),
+                    Value::String(String::from("98011")),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        }
+    }
+
+    #[rstest(
+        name,
+        pattern,
+        expected,
+        case("tbl_Mitglied", "tbl_", true),
+        case("tblImportMitglied", "tbl_", false),
+        case("tbl_H_Mitglied", "tbl_*_Mitglied", true),
+        case("tbl_Mitglied", "tbl_*_Mitglied", false),
+        case("tbl_PLZ", "tbl_P?Z", true),
+        case("tbl_PLZ_", "tbl_P?Z", false),
+        case("CustomerAddress", "*Address", true),
+        case("Customer", "", true),
+        case("CustomerAddress", "C*m*r?d*s", true),
+        case("CustomerAddress", "*Address*", true),
+        case("CustomerAddress", "*Addresses", false),
+        case("Customer", "Customer?", false)
+    )]
+    fn match_table_name_pattern(name: &str, pattern: &str, expected: bool) {
+        assert_eq!(matches_pattern(name, pattern), expected);
+    }
+
+    #[test]
+    fn match_pathological_table_name_pattern_in_linear_time() {
+        // Backtracking over every star would take ages for this name
+        let name = "a".repeat(10_000);
+
+        assert!(!matches_pattern(&name, "*a*a*a*a*a*a*a*a*b"));
+        assert!(matches_pattern(&name, "*a*a*a*a*a*a*a*a*"));
+    }
+
+    #[rstest(
+        column_names,
+        expected,
+        case(vec!["City", "AddressID"], vec!["AddressID", "City"]),
+        case(vec!["City", "CountryRegion"], vec!["City"]),
+        case(vec![], vec![])
+    )]
+    fn project_row(column_names: Vec<&str>, expected: Vec<&str>) {
+        let row = address_row().project(&column_names);
+
+        assert_eq!(
+            row.values()
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect::<Vec<_>>(),
+            expected
+        );
+    }
+
+    #[test]
+    fn rename_row_column() {
+        let row = address_row().rename("City", "Town");
+
+        assert_eq!(row.value("City"), None);
+        assert_eq!(
+            row.value("Town"),
+            Some(&Value::String(String::from("Bothell")))
+        );
+    }
+
+    #[test]
+    fn rename_missing_row_column() {
+        assert_eq!(
+            address_row().rename("CountryRegion", "Country"),
+            address_row()
+        );
+    }
+
+    fn column<'a>(r#type: &'a str, max_length: i16, precision: u8, scale: u8) -> Column<'a> {
+        Column {
+            name: "Value",
+            r#type,
+            xtype: 0,
+            max_length,
+            precision,
+            scale,
+            default_expr: None,
+            check_expr: None,
+            identity: None,
+            computed: false,
+            persisted: false,
+            leaf_offset: None,
+            leaf_bit_position: None,
+        }
+    }
+
+    /// Parses the record as row of the table on an empty page.
+    fn parse_row(table: &Table<'_>, bytes: &[u8], options: &Options) -> Result<Row, Error> {
+        let page = Page::try_from([0u8; 8192]).unwrap();
+        Row::parse(table, &page, Record::try_from(bytes).unwrap(), options)
+    }
+
+    /// Builds a record of int columns with the given values.
+    fn int_record(values: &[i32]) -> Vec<u8> {
+        values
+            .iter()
+            .fold(RecordBuilder::new(values.len() as u16), |record, value| {
+                record.fixed(&value.to_le_bytes())
+            })
+            .build()
+    }
+
+    fn strict_options() -> Options {
+        Options {
+            strict: true,
+            ..Options::default()
+        }
+    }
+
+    #[test]
+    fn parse_numeric_value() {
+        let bytes = [0x10, 0, 13, 0, 1, 0x87, 0xd6, 0x12, 0, 0, 0, 0, 0, 1, 0, 0];
+        let record = Record::try_from(&bytes[..]).unwrap();
+
+        let (value, _record) =
+            Value::parse(&column("numeric", 9, 10, 2), record, &Options::default()).unwrap();
+
+        assert_eq!(value, Value::Decimal(Decimal::new(1234567, 2)));
+    }
+
+    #[test]
+    fn parse_sysname_value() {
+        let bytes = RecordBuilder::new(1)
+            .variable(&utf16_bytes("Address"))
+            .build();
+        let record = Record::try_from(&bytes[..]).unwrap();
+
+        let (value, _record) =
+            Value::parse(&column("sysname", 256, 0, 0), record, &Options::default()).unwrap();
+
+        assert_eq!(value, Value::String(String::from("Address")));
+    }
+
+    #[test]
+    fn parse_rid_value() {
+        let bytes = [0x10, 0, 12, 0, 0x58, 0x1, 0, 0, 1, 0, 3, 0, 1, 0, 0];
+        let record = Record::try_from(&bytes[..]).unwrap();
+
+        let (value, _record) =
+            Value::parse(&column("RID", 8, 0, 0), record, &Options::default()).unwrap();
+
+        assert_eq!(
+            value,
+            Value::Rid {
+                page_id: 344,
+                file_id: 1,
+                slot: 3
+            }
+        );
+        assert_eq!(value.to_string(), "(1:344:3)");
+    }
+
+    #[rstest(
+        lhs,
+        rhs,
+        case(Value::Int(1), Value::BigInt(1)),
+        case(Value::TinyInt(-3), Value::SmallInt(-3)),
+        case(Value::SmallInt(42), Value::Decimal(Decimal::new(4200, 2))),
+        case(Value::BigInt(i64::MAX), Value::BigInt(i64::MAX)),
+        case(Value::Money(Decimal::new(15000, 4)), Value::Decimal(Decimal::new(15, 1))),
+        case(Value::Money(Decimal::new(20000, 4)), Value::Int(2)),
+        case(Value::Decimal(Decimal::new(150, 2)), Value::Decimal(Decimal::new(15, 1)))
+    )]
+    fn numeric_eq_across_widths(lhs: Value, rhs: Value) {
+        assert!(lhs.numeric_eq(&rhs));
+        assert!(rhs.numeric_eq(&lhs));
+    }
+
+    #[rstest(
+        lhs,
+        rhs,
+        case(Value::Int(1), Value::BigInt(2)),
+        case(Value::Int(1), Value::Bit(true)),
+        case(Value::Int(1), Value::String(String::from("1"))),
+        case(Value::Null, Value::Null),
+        case(Value::String(String::from("1")), Value::String(String::from("1")))
+    )]
+    fn numeric_eq_of_non_numeric_or_different_values(lhs: Value, rhs: Value) {
+        assert!(!lhs.numeric_eq(&rhs));
+    }
+
+    #[rstest(
+        value,
+        expected,
+        case(Value::from("Bothell"), Value::String(String::from("Bothell"))),
+        case(Value::from(String::from("Bothell")), Value::String(String::from("Bothell"))),
+        case(Value::from(42), Value::Int(42)),
+        case(Value::from(42i64), Value::BigInt(42)),
+        case(Value::from(true), Value::Bit(true)),
+        case(Value::from(Uuid::nil()), Value::Uuid(Uuid::nil())),
+        case(Value::from(Decimal::new(12345, 2)), Value::Decimal(Decimal::new(12345, 2))),
+        case(Value::from(Some(42)), Value::Int(42)),
+        case(Value::from(Some("Bothell")), Value::String(String::from("Bothell"))),
+        case(Value::from(None::<i32>), Value::Null)
+    )]
+    fn value_from(value: Value, expected: Value) {
+        assert_eq!(value, expected);
+    }
+
+    #[rstest(
+        value,
+        expected,
+        case(
+            Value::DateTime(
+                Utc.with_ymd_and_hms(2004, 6, 1, 13, 14, 15).unwrap()
+                    + chrono::Duration::milliseconds(120)
+            ),
+            Some(1_086_095_655_120)
+        ),
+        case(Value::DateTime(Utc.with_ymd_and_hms(1900, 1, 1, 0, 0, 0).unwrap()), Some(-2_208_988_800_000)),
+        case(Value::Null, None)
+    )]
+    fn epoch_millis(value: Value, expected: Option<i64>) {
+        assert_eq!(value.as_epoch_millis(), expected);
+    }
+
+    #[rstest(
+        value,
+        expected_u8,
+        expected_i16,
+        expected_i32,
+        expected_u32,
+        case(Value::TinyInt(7), Some(7), Some(7), Some(7), Some(7)),
+        case(Value::TinyInt(-7), None, Some(-7), Some(-7), None),
+        case(Value::SmallInt(300), None, Some(300), Some(300), Some(300)),
+        case(Value::Int(-70000), None, None, Some(-70000), None),
+        case(Value::BigInt(1 << 32), None, None, None, None),
+        case(Value::BigInt(255), Some(255), Some(255), Some(255), Some(255)),
+        case(Value::Decimal(Decimal::new(7, 0)), None, None, None, None),
+        case(Value::Null, None, None, None, None)
+    )]
+    fn checked_integer_downcasts(
+        value: Value,
+        expected_u8: Option<u8>,
+        expected_i16: Option<i16>,
+        expected_i32: Option<i32>,
+        expected_u32: Option<u32>,
+    ) {
+        assert_eq!(value.as_u8_checked(), expected_u8);
+        assert_eq!(value.as_i16_checked(), expected_i16);
+        assert_eq!(value.as_i32_checked(), expected_i32);
+        assert_eq!(value.as_u32_checked(), expected_u32);
+    }
+
+    #[test]
+    fn parse_real_and_float_values() {
+        let mut bytes = vec![0x10, 0, 16, 0];
+        bytes.extend_from_slice(&0.1f32.to_le_bytes());
+        bytes.extend_from_slice(&(-2.5f64).to_le_bytes());
+        bytes.extend_from_slice(&[2, 0, 0]);
+        let record = Record::try_from(&bytes[..]).unwrap();
+
+        let (real, record) =
+            Value::parse(&column("real", 4, 0, 0), record, &Options::default()).unwrap();
+        let (float, _record) =
+            Value::parse(&column("float", 8, 0, 0), record, &Options::default()).unwrap();
+
+        assert_eq!(real, Value::Float(0.1f32 as f64));
+        assert_eq!(float, Value::Float(-2.5));
+        assert_eq!(float.to_string(), "-2.5");
+    }
+
+    #[test]
+    fn parse_time_value_followed_by_int() {
+        // 13:14:15.1234567 as ticks of 100ns since midnight followed by 42
+        let bytes = [
+            0x10, 0, 13, 0, 0x07, 0xc4, 0xaa, 0xf4, 0x6e, 42, 0, 0, 0, 2, 0, 0,
+        ];
+        let record = Record::try_from(&bytes[..]).unwrap();
+
+        let (time, record) =
+            Value::parse(&column("time", 5, 16, 7), record, &Options::default()).unwrap();
+        let (int, _record) =
+            Value::parse(&column("int", 4, 0, 0), record, &Options::default()).unwrap();
+
+        assert_eq!(
+            time,
+            Value::Time(NaiveTime::from_hms_nano_opt(13, 14, 15, 123_456_700).unwrap())
+        );
+        assert_eq!(time.to_string(), "13:14:15.123456700");
+        assert_eq!(int, Value::Int(42));
+    }
+
+    #[test]
+    fn parse_date_value_followed_by_int() {
+        // 2004-06-01 as 731732 days since 0001-01-01 followed by 42
+        let bytes = [0x10, 0, 11, 0, 0x54, 0x2a, 0x0b, 42, 0, 0, 0, 2, 0, 0];
+        let record = Record::try_from(&bytes[..]).unwrap();
+
+        let (date, record) =
+            Value::parse(&column("date", 3, 0, 0), record, &Options::default()).unwrap();
+        let (int, _record) =
+            Value::parse(&column("int", 4, 0, 0), record, &Options::default()).unwrap();
+
+        assert_eq!(
+            date,
+            Value::Date(NaiveDate::from_ymd_opt(2004, 6, 1).unwrap())
+        );
+        assert_eq!(date.to_string(), "2004-06-01");
+        assert_eq!(date.as_epoch_millis(), Some(1_086_048_000_000));
+        assert_eq!(
+            date.coerce_to(ColumnType::DateTime).unwrap(),
+            Value::DateTime(
+                Utc.from_utc_datetime(
+                    &NaiveDate::from_ymd_opt(2004, 6, 1)
+                        .unwrap()
+                        .and_hms_opt(0, 0, 0)
+                        .unwrap()
+                )
+            )
+        );
+        assert_eq!(int, Value::Int(42));
+    }
+
+    #[test]
+    fn float_equality_and_hash() {
+        let values = vec![
+            Value::Float(f64::NAN),
+            Value::Float(f64::NAN),
+            Value::Float(0.0),
+            Value::Float(-0.0),
+            Value::Float(1.5),
+        ];
+
+        let unique_values = values.into_iter().collect::<HashSet<_>>();
+
+        assert_eq!(unique_values.len(), 4);
+        assert_eq!(Value::Float(f64::NAN), Value::Float(f64::NAN));
+        assert_ne!(Value::Float(0.0), Value::Float(-0.0));
+        assert_ne!(Value::Float(1.0), Value::Int(1));
+    }
+
+    #[test]
+    fn money_equality_and_display() {
+        let money = Value::Money(Decimal::new(15000, 4));
+
+        assert_eq!(money, Value::Money(Decimal::new(15, 1)));
+        assert_ne!(money, Value::Decimal(Decimal::new(15, 1)));
+        assert_eq!(money.to_string(), "1.5000");
+        assert_eq!(Value::Money(Decimal::new(15, 1)).to_string(), "1.5000");
+    }
+
+    #[test]
+    fn parse_nchar_value() {
+        let bytes = RecordBuilder::new(1)
+            .fixed(&utf16_bytes("Bothell   "))
+            .build();
+        let record = Record::try_from(&bytes[..]).unwrap();
+        let column = column("nchar", 20, 0, 0);
+
+        let (value, _record) = Value::parse(&column, record, &Options::default()).unwrap();
+
+        assert_eq!(value, Value::String(String::from("Bothell   ")));
+        assert_eq!(column.max_characters(), Some(10));
+        assert_eq!(ColumnInfo::from(&column).max_characters, Some(10));
+    }
+
+    #[rstest(trim_char_padding, expected, case(false, "Köln "), case(true, "Köln"))]
+    fn parse_char_value(trim_char_padding: bool, expected: &str) {
+        // "Köln " in Windows-1252, where ö is the single byte 0xf6
+        let bytes = RecordBuilder::new(1)
+            .fixed(&[b'K', 0xf6, b'l', b'n', b' '])
+            .build();
+        let record = Record::try_from(&bytes[..]).unwrap();
+        let column = column("char", 5, 0, 0);
+        let options = Options {
+            trim_char_padding,
+            ..Options::default()
+        };
+
+        let (value, _record) = Value::parse(&column, record, &options).unwrap();
+
+        assert_eq!(value, Value::String(String::from(expected)));
+        assert_eq!(column.max_characters(), Some(5));
+    }
+
+    #[test]
+    fn parse_binary_values() {
+        let bytes = RecordBuilder::new(2)
+            .fixed(&[0xca, 0xfe])
+            .variable(&[0xbe, 0xef])
+            .build();
+        let record = Record::try_from(&bytes[..]).unwrap();
+
+        let (fixed, record) =
+            Value::parse(&column("binary", 2, 0, 0), record, &Options::default()).unwrap();
+        let (variable, _record) =
+            Value::parse(&column("varbinary", 50, 0, 0), record, &Options::default()).unwrap();
+
+        assert_eq!(fixed.as_bytes(), Some(&[0xca, 0xfe][..]));
+        assert_eq!(variable.as_bytes(), Some(&[0xbe, 0xef][..]));
+        assert_eq!(variable.to_string(), "0xBEEF");
+        assert_eq!(Value::Int(1).as_bytes(), None);
+    }
+
+    #[rstest(type_name, case("nvarchar"), case("varbinary"), case("xml"))]
+    fn reject_value_with_corrupt_length_at_end_of_record(type_name: &str) {
+        // The end offset 0x7fff of the only variable column points far beyond the record
+        let mut bytes = vec![0x30, 0, 4, 0, 1, 0, 0, 1, 0, 0xff, 0x7f];
+        bytes.extend(utf16_bytes("corrupt"));
+        let column = column(type_name, 50, 0, 0);
+
+        let parsed = Value::parse(
+            &column,
+            Record::try_from(&bytes[..]).unwrap(),
+            &strict_options(),
+        );
+
+        assert_eq!(parsed.err(), Some("variable column exceeds record"));
+    }
+
+    fn utf16_bytes(s: &str) -> Vec<u8> {
+        s.encode_utf16().flat_map(u16::to_le_bytes).collect()
+    }
+
+    #[rstest(
+        end_offset,
+        expected,
+        case(17, Ok(Value::Binary(b"GIF89a".to_vec()))),
+        // The high bit marks a pointer to the value stored out of row
+        case(0x8000 | 17, Err("The pages of LOB values stored out of row have not been read"))
+    )]
+    fn parse_varbinary_max_value(end_offset: u16, expected: Result<Value, &'static str>) {
+        let mut bytes = vec![0x30, 0, 4, 0, 1, 0, 0, 1, 0];
+        bytes.extend_from_slice(&end_offset.to_le_bytes());
+        bytes.extend_from_slice(b"GIF89a");
+        let record = Record::try_from(&bytes[..]).unwrap();
+        let column = column("varbinary", -1, 0, 0);
+
+        let value = Value::parse(&column, record, &Options::default()).map(|(value, _)| value);
+
+        assert_eq!(column.column_type(), Some(ColumnType::Binary));
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn parse_value_of_alias_type_via_xtype() {
+        let bytes = int_record(&[42]);
+        let record = Record::try_from(&bytes[..]).unwrap();
+        let column = Column {
+            xtype: 56,
+            ..column("Quantity", 4, 10, 0)
+        };
+
+        let (value, _record) = Value::parse(&column, record, &Options::default()).unwrap();
+
+        assert_eq!(value, Value::Int(42));
+    }
+
+    #[test]
+    fn parse_row_with_non_persisted_computed_column() {
+        let base_table_data = BaseTableData::with_table(
+            "Orders",
+            vec![
+                ("OrderID", "int", 4, 0),
+                ("OrderNumber", "nvarchar", 50, 0x10),
+                ("Quantity", "int", 4, 0),
+            ],
+        );
+        let table = base_table_data.table("Orders").unwrap();
+        let bytes = int_record(&[7, 3]);
+
+        let row = parse_row(&table, &bytes, &Options::default()).unwrap();
+
+        assert_eq!(
+            row.values(),
+            vec![
+                (String::from("OrderID"), Value::Int(7)),
+                (String::from("Quantity"), Value::Int(3)),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_row_at_leaf_offsets() {
+        let base_table_data = BaseTableData::with_table(
+            "Orders",
+            vec![("OrderID", "int", 4, 0), ("Quantity", "int", 4, 0)],
+        );
+        let mut table = base_table_data.table("Orders").unwrap();
+        table.columns[0].leaf_offset = Some(4);
+        table.columns[1].leaf_offset = Some(12);
+        // The bytes 8 to 11 belong to a dropped column
+        let bytes = RecordBuilder::new(2)
+            .fixed(&7i32.to_le_bytes())
+            .fixed(&[0xff; 4])
+            .fixed(&3i32.to_le_bytes())
+            .build();
+
+        let row = parse_row(&table, &bytes, &Options::default()).unwrap();
+
+        assert_eq!(
+            row.values(),
+            vec![
+                (String::from("OrderID"), Value::Int(7)),
+                (String::from("Quantity"), Value::Int(3)),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_bit_columns_at_leaf_offsets() {
+        let base_table_data = BaseTableData::with_table(
+            "Orders",
+            vec![
+                ("Shipped", "bit", 1, 0),
+                ("Quantity", "int", 4, 0),
+                ("Paid", "bit", 1, 0),
+            ],
+        );
+        let mut table = base_table_data.table("Orders").unwrap();
+        table.columns[0].leaf_offset = Some(8);
+        table.columns[0].leaf_bit_position = Some(1);
+        table.columns[1].leaf_offset = Some(4);
+        table.columns[2].leaf_offset = Some(8);
+        table.columns[2].leaf_bit_position = Some(0);
+        // The bits are stored after the quantity, the bit of the first column at position 1
+        let bytes = RecordBuilder::new(3)
+            .fixed(&3i32.to_le_bytes())
+            .fixed(&[0b10])
+            .build();
+
+        let row = parse_row(&table, &bytes, &strict_options()).unwrap();
+
+        assert_eq!(
+            row.values(),
+            vec![
+                (String::from("Paid"), Value::Bit(false)),
+                (String::from("Quantity"), Value::Int(3)),
+                (String::from("Shipped"), Value::Bit(true)),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_null_bit_column_at_leaf_offset() {
+        let base_table_data = BaseTableData::with_table(
+            "Orders",
+            vec![("Shipped", "bit", 1, 0), ("Paid", "bit", 1, 0)],
+        );
+        let mut table = base_table_data.table("Orders").unwrap();
+        table.columns[0].leaf_offset = Some(4);
+        table.columns[0].leaf_bit_position = Some(0);
+        table.columns[1].leaf_offset = Some(4);
+        table.columns[1].leaf_bit_position = Some(1);
+        // The bit of the NULL column is set to make sure it is not read
+        let bytes = RecordBuilder::new(2).fixed(&[0b11]).null(0).build();
+
+        let row = parse_row(&table, &bytes, &strict_options()).unwrap();
+
+        assert_eq!(
+            row.values(),
+            vec![
+                (String::from("Paid"), Value::Bit(true)),
+                (String::from("Shipped"), Value::Null),
+            ]
+        );
+    }
+
+    #[rstest(
+        value,
+        target,
+        expected,
+        case(Value::TinyInt(7), ColumnType::Int, Value::Int(7)),
+        case(Value::Int(-42), ColumnType::BigInt, Value::BigInt(-42)),
+        case(Value::BigInt(300), ColumnType::SmallInt, Value::SmallInt(300)),
+        case(
+            Value::Int(12),
+            ColumnType::Decimal { precision: 5, scale: 2 },
+            Value::Decimal(Decimal::new(1200, 2))
+        ),
+        case(Value::Decimal(Decimal::new(1200, 2)), ColumnType::Int, Value::Int(12)),
+        case(Value::Int(42), ColumnType::String, Value::String(String::from("42"))),
+        case(Value::Null, ColumnType::Uuid, Value::Null),
+        case(Value::Float(1.5), ColumnType::Real, Value::Float(1.5))
+    )]
+    fn coerce_value(value: Value, target: ColumnType, expected: Value) {
+        assert_eq!(value.coerce_to(target).unwrap(), expected);
+    }
+
+    #[rstest(
+        value,
+        target,
+        case(Value::BigInt(1 << 40), ColumnType::Int),
+        case(Value::SmallInt(300), ColumnType::TinyInt),
+        case(Value::Decimal(Decimal::new(1250, 2)), ColumnType::Int),
+        case(Value::Decimal(Decimal::new(12345, 3)), ColumnType::Decimal { precision: 5, scale: 2 }),
+        case(Value::Int(12345), ColumnType::Decimal { precision: 5, scale: 2 }),
+        case(Value::String(String::from("42")), ColumnType::Int),
+        case(Value::String(String::from("Köln")), ColumnType::Char { length: 3 }),
+        case(Value::Float(0.1), ColumnType::Real)
+    )]
+    fn reject_lossy_coercion(value: Value, target: ColumnType) {
+        assert!(matches!(
+            value.coerce_to(target),
+            Err(Error::LossyCoercion { .. })
+        ));
+    }
+
+    #[rstest(
+        type_name,
+        max_length,
+        fixed_length,
+        case("tinyint", 1, 1),
+        case("smallint", 2, 2),
+        case("int", 4, 4),
+        case("bigint", 8, 8),
+        case("money", 8, 8),
+        case("smallmoney", 4, 4),
+        case("real", 4, 4),
+        case("float", 8, 8),
+        case("decimal", 5, 5),
+        case("char", 5, 5),
+        case("nchar", 10, 10),
+        case("date", 3, 3),
+        case("datetime", 8, 8),
+        case("datetime2", 6, 6),
+        case("time", 3, 3),
+        case("uniqueidentifier", 16, 16),
+        case("binary", 3, 3),
+        case("timestamp", 8, 8),
+        case("RID", 8, 8)
+    )]
+    fn parse_fixed_length_column_followed_by_int(
+        type_name: &str,
+        max_length: i16,
+        fixed_length: usize,
+    ) {
+        let base_table_data = BaseTableData::with_table(
+            "Orders",
+            vec![
+                ("Value", type_name, max_length, 0),
+                ("Quantity", "int", 4, 0),
+            ],
+        );
+        let table = base_table_data.table("Orders").unwrap();
+        let column_type = table.columns[0].column_type().unwrap();
+        let bytes = RecordBuilder::new(2)
+            .fixed(&vec![0; fixed_length])
+            .fixed(&3i32.to_le_bytes())
+            .build();
+
+        let row = parse_row(&table, &bytes, &strict_options()).unwrap();
+
+        assert_eq!(column_type.fixed_length(), Some(fixed_length));
+        assert_eq!(row.value("Quantity"), Some(&Value::Int(3)));
+    }
+
+    #[rstest(type_name, max_length, case("int", 4), case("date", 3))]
+    fn parse_null_fixed_length_column_followed_by_int(type_name: &str, max_length: i16) {
+        let base_table_data = BaseTableData::with_table(
+            "Orders",
+            vec![
+                ("Value", type_name, max_length, 0),
+                ("Quantity", "int", 4, 0),
+            ],
+        );
+        let table = base_table_data.table("Orders").unwrap();
+        // The NULL value still occupies its bytes in the fixed length data
+        let bytes = RecordBuilder::new(2)
+            .fixed(&vec![0xff; max_length as usize])
+            .fixed(&3i32.to_le_bytes())
+            .null(0)
+            .build();
+
+        let row = parse_row(&table, &bytes, &strict_options()).unwrap();
+
+        assert_eq!(row.value("Value"), Some(&Value::Null));
+        assert_eq!(row.value("Quantity"), Some(&Value::Int(3)));
+    }
+
+    #[test]
+    fn parse_row_with_bigint_column() {
+        let base_table_data = BaseTableData::with_table(
+            "Orders",
+            vec![("OrderID", "bigint", 8, 0), ("Quantity", "int", 4, 0)],
+        );
+        let table = base_table_data.table("Orders").unwrap();
+        // The order id uses the upper half of the 8 bytes, which an int would not read
+        let bytes = RecordBuilder::new(2)
+            .fixed(&5_000_000_001i64.to_le_bytes())
+            .fixed(&3i32.to_le_bytes())
+            .build();
+
+        let row = parse_row(&table, &bytes, &strict_options()).unwrap();
+
+        assert_eq!(row.value("OrderID"), Some(&Value::BigInt(5_000_000_001)));
+        assert_eq!(row.value("Quantity"), Some(&Value::Int(3)));
+    }
+
+    #[rstest(
+        sign,
+        expected,
+        case(1, Decimal::from_i128_with_scale(123_456_789_012_345_678_901, 4)),
+        case(0, Decimal::from_i128_with_scale(-123_456_789_012_345_678_901, 4))
+    )]
+    fn parse_row_with_decimal_22_4_column(sign: u8, expected: Decimal) {
+        let base_table_data = BaseTableData::with_table(
+            "Orders",
+            vec![("Total", "decimal", 13, 0), ("Quantity", "int", 4, 0)],
+        )
+        .with_precision("Total", 22, 4);
+        let table = base_table_data.table("Orders").unwrap();
+        // A decimal of precision 20 to 28 occupies a sign byte and a 12 byte magnitude
+        let bytes = RecordBuilder::new(2)
+            .fixed(&[sign])
+            .fixed(&123_456_789_012_345_678_901u128.to_le_bytes()[..12])
+            .fixed(&3i32.to_le_bytes())
+            .build();
+
+        let row = parse_row(&table, &bytes, &strict_options()).unwrap();
+
+        assert_eq!(row.value("Total"), Some(&Value::Decimal(expected)));
+        assert_eq!(
+            row.value("Total").unwrap().to_string(),
+            expected.to_string()
+        );
+        assert_eq!(row.value("Quantity"), Some(&Value::Int(3)));
+    }
+
+    #[test]
+    fn parse_row_with_money_column() {
+        let base_table_data = BaseTableData::with_table(
+            "Orders",
+            vec![("Price", "money", 8, 0), ("Quantity", "int", 4, 0)],
+        );
+        let table = base_table_data.table("Orders").unwrap();
+        // 12.3456 as 123456 ten-thousandths followed by the quantity 3
+        let bytes = RecordBuilder::new(2)
+            .fixed(&123_456i64.to_le_bytes())
+            .fixed(&3i32.to_le_bytes())
+            .build();
+
+        let row = parse_row(&table, &bytes, &Options::default()).unwrap();
+
+        assert_eq!(
+            row.values(),
+            vec![
+                (String::from("Price"), Value::Money(Decimal::new(123456, 4))),
+                (String::from("Quantity"), Value::Int(3)),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_values_in_schema_order() {
+        let base_table_data = BaseTableData::with_table(
+            "Orders",
+            vec![("OrderID", "int", 4, 0), ("Quantity", "int", 4, 0)],
+        );
+        let table = base_table_data.table("Orders").unwrap();
+        let page = Page::try_from([0u8; 8192]).unwrap();
+        let bytes = int_record(&[7, 3]);
+
+        let values = Row::parse_values(
+            &table,
+            &page,
+            Record::try_from(&bytes[..]).unwrap(),
+            &Options::default(),
+        )
+        .unwrap();
+        let row = parse_row(&table, &bytes, &Options::default()).unwrap();
+
+        assert_eq!(values, vec![Some(Value::Int(7)), Some(Value::Int(3))]);
+        assert_eq!(
+            table
+                .columns
+                .iter()
+                .map(|column| row.value(column.name))
+                .collect::<Vec<_>>(),
+            values.iter().map(Option::as_ref).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn parse_values_of_undecodable_columns_as_none() {
+        let base_table_data = BaseTableData::with_table(
+            "Orders",
+            vec![
+                ("OrderID", "int", 4, 0),
+                ("Document", "sql_variant", 8016, 0),
+                ("Quantity", "int", 4, 0),
+            ],
+        );
+        let table = base_table_data.table("Orders").unwrap();
+        let page = Page::try_from([0u8; 8192]).unwrap();
+        let bytes = RecordBuilder::new(3).fixed(&7i32.to_le_bytes()).build();
+
+        let values = Row::parse_values(
+            &table,
+            &page,
+            Record::try_from(&bytes[..]).unwrap(),
+            &Options::default(),
+        )
+        .unwrap();
+
+        assert_eq!(values, vec![Some(Value::Int(7)), None, None]);
+    }
+
+    #[test]
+    fn report_record_error_to_hook() {
+        let base_table_data = BaseTableData::with_table(
+            "Orders",
+            vec![
+                ("OrderID", "int", 4, 0),
+                ("Document", "sql_variant", 8016, 0),
+            ],
+        );
+        let table = base_table_data.table("Orders").unwrap();
+        let bytes = RecordBuilder::new(2).fixed(&7i32.to_le_bytes()).build();
+        let errors = Arc::new(Mutex::new(Vec::new()));
+        let reported_errors = errors.clone();
+
+        let row = parse_row(
+            &table,
+            &bytes,
+            &Options {
+                on_record_error: Some(Arc::new(move |err| {
+                    reported_errors.lock().unwrap().push(err.to_string())
+                })),
+                ..Options::default()
+            },
+        );
+
+        assert!(row.is_ok());
+        assert_eq!(
+            *errors.lock().unwrap(),
+            vec![String::from(
+                "failed to parse column 'Document' of table 'Orders' on page 0 at offset 8: \
+                 Unknown column type"
+            )]
+        );
+    }
+
+    #[test]
+    fn parse_row_with_unknown_column_type() {
+        let base_table_data = BaseTableData::with_table(
+            "Orders",
+            vec![
+                ("OrderID", "int", 4, 0),
+                ("Document", "sql_variant", 8016, 0),
+            ],
+        );
+        let table = base_table_data.table("Orders").unwrap();
+        let bytes = RecordBuilder::new(2).fixed(&7i32.to_le_bytes()).build();
+
+        let lenient = parse_row(&table, &bytes, &Options::default());
+        let strict = parse_row(&table, &bytes, &strict_options());
+
+        assert_eq!(
+            lenient.unwrap().values(),
+            vec![(String::from("OrderID"), Value::Int(7))]
+        );
+        assert!(matches!(
+            strict,
+            Err(Error::ColumnParse { column, .. }) if column == "Document"
+        ));
+    }
+
+    #[test]
+    fn report_page_offset_of_column_parse_error() {
+        let base_table_data = BaseTableData::with_table(
+            "Orders",
+            vec![("OrderID", "int", 4, 0), ("Quantity", "int", 4, 0)],
+        );
+        let table = base_table_data.table("Orders").unwrap();
+        // The fixed length data ends in the middle of the quantity
+        let bytes = PageBuilder::new(DATA_PAGE, 0)
+            .record(&RecordBuilder::new(2).fixed(&[7, 0, 0, 0, 1, 0]).build())
+            .build();
+        let page = Page::try_from(bytes).unwrap();
+        let record = page.records().remove(0);
+
+        let row = Row::parse(&table, &page, record, &strict_options());
+
+        let err = row.unwrap_err();
+        assert!(matches!(err, Error::ColumnParse { offset: 104, .. }));
+        assert_eq!(
+            err.to_string(),
+            "failed to parse column 'Quantity' of table 'Orders' on page 0 at offset 104: \
+             fixed data underflow"
+        );
+    }
+
+    #[rstest(
+        complex,
+        expected,
+        case(false, Some(Value::Xml(vec![0xdf, 0xff, 0x01]))),
+        // The complex column bit marks a pointer to the value stored out of row, whose LOB pages
+        // are only read when scanning the table
+        case(true, None)
+    )]
+    fn parse_row_with_xml_column(complex: bool, expected: Option<Value>) {
+        let base_table_data = BaseTableData::with_table(
+            "Products",
+            vec![("ProductID", "int", 4, 0), ("Description", "xml", -1, 0)],
+        );
+        let table = base_table_data.table("Products").unwrap();
+        let record = RecordBuilder::new(2).fixed(&7i32.to_le_bytes());
+        let bytes = if complex {
+            record.complex(&[0xdf, 0xff, 0x01])
+        } else {
+            record.variable(&[0xdf, 0xff, 0x01])
+        }
+        .build();
+
+        let row = parse_row(&table, &bytes, &strict_options());
+
+        match expected {
+            Some(value) => assert_eq!(row.unwrap().value("Description"), Some(&value)),
+            None => assert!(matches!(
+                row,
+                Err(Error::ColumnParse { column, .. }) if column == "Description"
+            )),
+        }
+    }
+
+    #[rstest(
+        pointer,
+        case(
+            // An inline root linking to the data
+            [&[0u8; 12][..], &lob_link(4, 2, 1)].concat()
+        ),
+        case(
+            // An inline root linking to a root, which links to the fragments of the data
+            [&[0u8; 12][..], &lob_link(4, 2, 0)].concat()
+        ),
+        case(
+            // A text pointer to a small root, which stores the data itself
+            [&[0u8; 8][..], &lob_link(0, 3, 1)[4..]].concat()
+        )
+    )]
+    #[async_std::test]
+    async fn read_xml_column_stored_out_of_row(pointer: Vec<u8>) {
+        let mut bytes = vec![0u8; 4 * 8192];
+        PageBuilder::new(DATA_PAGE, 1)
+            .record(
+                &RecordBuilder::new(2)
+                    .fixed(&7i32.to_le_bytes())
+                    .complex(&pointer)
+                    .build(),
+            )
+            .write_to(&mut bytes);
+        PageBuilder::new(TEXT_MIX_PAGE, 2)
+            .record(&lob_record(
+                LobRecord::LARGE_ROOT_YUKON,
+                &[
+                    &[5, 0, 2, 0, 0, 0, 0, 0, 0, 0][..],
+                    &lob_link(2, 2, 1),
+                    &lob_link(4, 3, 0),
+                ]
+                .concat(),
+            ))
+            .record(&lob_record(LobRecord::DATA, &[0xdf, 0xff, 0x01, 0xb0]))
+            .write_to(&mut bytes);
+        PageBuilder::new(TEXT_MIX_PAGE, 3)
+            .record(&lob_record(LobRecord::DATA, &[0x01, 0xb0]))
+            .record(&lob_record(
+                LobRecord::SMALL_ROOT,
+                &[4, 0, 0, 0, 0, 0, 0xdf, 0xff, 0x01, 0xb0],
+            ))
+            .write_to(&mut bytes);
+        let base_table_data = BaseTableData::with_table(
+            "Products",
+            vec![("ProductID", "int", 4, 0), ("Description", "xml", -1, 0)],
+        )
+        .with_clustered_index(1, 0);
+        let mut page_reader = PageReader::new(Box::new(async_std::io::Cursor::new(bytes)));
+
+        let rows = rows_of_table(
+            &mut page_reader,
+            base_table_data.table("Products").unwrap(),
+            strict_options(),
+        )
+        .unwrap()
+        .map(|row| row.unwrap().1)
+        .collect::<Vec<_>>()
+        .await;
+
+        assert_eq!(
+            rows[0].value("Description"),
+            Some(&Value::Xml(vec![0xdf, 0xff, 0x01, 0xb0]))
+        );
+    }
+
+    #[cfg(feature = "compression")]
+    #[rstest(cmprlevel, case(1), case(2))]
+    #[async_std::test]
+    async fn read_compressed_table_like_uncompressed_table(cmprlevel: i8) {
+        let page_compression = cmprlevel == 2;
+        // PAGE compression stores the prefix "Ger" in the anchor record and "Germany" in the
+        // page dictionary, prefixes are counted in bytes
+        let country = |record: CompressedRecordBuilder, country: &str| match country {
+            _ if !page_compression => record.long(&utf16_bytes(country)),
+            "Germany" => record.symbol(0),
+            _ => record.long(&[&[2], &utf16_bytes(&country[1..])[..]].concat()),
+        };
+
+        let mut bytes = vec![0u8; 3 * 8192];
+        PageBuilder::new(DATA_PAGE, 1)
+            .record(
+                &RecordBuilder::new(7)
+                    .fixed(&1i32.to_le_bytes())
+                    .fixed(&(-2i64).to_le_bytes())
+                    .fixed(&[0b01])
+                    .null(3)
+                    .fixed(b"AB    ")
+                    .variable(&utf16_bytes("Germany"))
+                    .variable(&utf16_bytes("Grüße"))
+                    .build(),
+            )
+            .record(
+                &RecordBuilder::new(7)
+                    .fixed(&300i32.to_le_bytes())
+                    .fixed(&5_000_000_000i64.to_le_bytes())
+                    .fixed(&[0b10])
+                    .null(4)
+                    .fixed(&[0; 6])
+                    .variable(&utf16_bytes("Germany"))
+                    .variable(&utf16_bytes("Hallo"))
+                    .build(),
+            )
+            .record(
+                &RecordBuilder::new(7)
+                    .fixed(&(-129i32).to_le_bytes())
+                    .fixed(&0i64.to_le_bytes())
+                    .fixed(&[0b01])
+                    .fixed(b"ABCDEF")
+                    .variable(&utf16_bytes("Ghana"))
+                    .null(6)
+                    .variable(&[])
+                    .build(),
+            )
+            .write_to(&mut bytes);
+
+        let mut page = PageBuilder::new(DATA_PAGE, 2);
+        if page_compression {
+            let mut anchor_record = CompressedRecordBuilder::new();
+            for _ in 0..5 {
+                anchor_record = anchor_record.null();
+            }
+            let anchor_record = anchor_record.short(&utf16_bytes("Ger")).null().build();
+            let dictionary = [&[6][..], &utf16_bytes("many")].concat();
+            page = page.compression_info(&compression_info(Some(&anchor_record), &[&dictionary]));
+        }
+        let first_record = CompressedRecordBuilder::new()
+            .short(&[0x81])
+            .short(&[0x7e])
+            .bit(true)
+            .null()
+            .short(b"AB");
+        let second_record = CompressedRecordBuilder::new()
+            .short(&[0x81, 0x2c])
+            .short(&[0x81, 0x2a, 0x05, 0xf2, 0x00])
+            .bit(false)
+            .bit(true)
+            .null();
+        let third_record = CompressedRecordBuilder::new()
+            .short(&[0x7f, 0x7f])
+            .short(&[])
+            .bit(true)
+            .bit(false)
+            .short(b"ABCDEF");
+        page.record(
+            &country(first_record, "Germany")
+                .long(&utf16_bytes("Grüße"))
+                .build(),
+        )
+        .record(
+            &country(second_record, "Germany")
+                .long(&utf16_bytes("Hallo"))
+                .build(),
+        )
+        .record(&country(third_record, "Ghana").null().build())
+        .write_to(&mut bytes);
+
+        let columns = vec![
+            ("OrderID", "int", 4, 0),
+            ("CustomerID", "bigint", 8, 0),
+            ("Shipped", "bit", 1, 0),
+            ("Paid", "bit", 1, 0),
+            ("Code", "char", 6, 0),
+            ("Country", "nvarchar", 40, 0),
+            ("Name", "nvarchar", 40, 0),
+        ];
+        let uncompressed =
+            BaseTableData::with_table("Orders", columns.clone()).with_clustered_index(1, 0);
+        let compressed = BaseTableData::with_table("Orders", columns)
+            .with_clustered_index(2, 0)
+            .with_compression(cmprlevel);
+        let mut page_reader = PageReader::new(Box::new(async_std::io::Cursor::new(bytes)));
+
+        let expected_rows = rows_of_table(
+            &mut page_reader,
+            uncompressed.table("Orders").unwrap(),
+            strict_options(),
+        )
+        .unwrap()
+        .map(|row| row.unwrap().1.values())
+        .collect::<Vec<_>>()
+        .await;
+        let rows = rows_of_table(
+            &mut page_reader,
+            compressed.table("Orders").unwrap(),
+            strict_options(),
+        )
+        .unwrap()
+        .map(|row| row.unwrap().1)
+        .collect::<Vec<_>>()
+        .await;
+
+        assert_eq!(expected_rows.len(), 3);
+        assert_eq!(
+            rows.iter()
+                .map(|row| row.clone().values())
+                .collect::<Vec<_>>(),
+            expected_rows
+        );
+        assert_eq!(
+            rows[1].value("CustomerID"),
+            Some(&Value::BigInt(5_000_000_000))
+        );
+        assert_eq!(
+            rows[2].value("Country"),
+            Some(&Value::String(String::from("Ghana")))
+        );
+    }
+
+    #[rstest(
+        complex,
+        expected,
+        case(false, Some(Value::String(String::from("Ab")))),
+        // The complex column bit marks a pointer to the value stored out of row
+        case(true, None)
+    )]
+    fn parse_row_with_nvarchar_max_column(complex: bool, expected: Option<Value>) {
+        let base_table_data = BaseTableData::with_table(
+            "ErrorLog",
+            vec![
+                ("ErrorLogID", "int", 4, 0),
+                ("ErrorMessage", "nvarchar", -1, 0),
+            ],
+        );
+        let table = base_table_data.table("ErrorLog").unwrap();
+        let record = RecordBuilder::new(2).fixed(&7i32.to_le_bytes());
+        let bytes = if complex {
+            record.complex(&utf16_bytes("Ab"))
+        } else {
+            record.variable(&utf16_bytes("Ab"))
+        }
+        .build();
+
+        let row = parse_row(&table, &bytes, &strict_options());
+
+        match expected {
+            Some(value) => assert_eq!(row.unwrap().value("ErrorMessage"), Some(&value)),
+            None => assert!(matches!(
+                row,
+                Err(Error::ColumnParse { column, .. }) if column == "ErrorMessage"
+            )),
+        }
+    }
+
+    fn pages_with_boot_page_at(boot_page_id: Option<u8>) -> Vec<u8> {
+        let mut bytes = vec![0u8; 20 * 8192];
+        if let Some(page_id) = boot_page_id {
+            let page = &mut bytes[page_id as usize * 8192..];
+            page[1] = BOOT_PAGE;
+            page[32] = page_id;
+            page[36] = 1;
+            for (i, byte) in "TestDB"
+                .encode_utf16()
+                .flat_map(u16::to_le_bytes)
+                .enumerate()
+            {
+                page[148 + i] = byte;
+            }
+        }
+        bytes
+    }
+
+    #[async_std::test]
+    async fn open_database_with_cyclic_system_page_chain() {
+        let mut bytes = pages_with_boot_page_at(Some(9));
+        // The first page of sysallocunits refers to itself as next page
+        bytes[9 * 8192 + 612] = 10;
+        bytes[9 * 8192 + 616] = 1;
+        PageBuilder::new(DATA_PAGE, 10)
+            .next_page(10)
+            .write_to(&mut bytes);
+
+        let result = MdfDatabase::from_read(Box::new(async_std::io::Cursor::new(bytes))).await;
+
+        assert!(matches!(result, Err(Error::CorruptMetadata(_))));
+    }
+
+    #[async_std::test]
+    async fn reject_truncated_file_in_strict_mode() {
+        let mut bytes = pages_with_boot_page_at(Some(9));
+        bytes.extend_from_slice(&[0u8; 100]);
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&bytes).unwrap();
+
+        let result = MdfDatabase::open_with_options(file.path(), strict_options()).await;
+
+        assert!(matches!(
+            result,
+            Err(Error::TruncatedFile { length }) if length == 20 * 8192 + 100
+        ));
+    }
+
+    #[rstest(boot_page_id, case(9), case(5))]
+    #[async_std::test]
+    async fn read_boot_page(boot_page_id: u8) {
+        let bytes = pages_with_boot_page_at(Some(boot_page_id));
+        let mut page_reader = PageReader::new(Box::new(async_std::io::Cursor::new(bytes)));
+
+        let boot_page = page_reader.read_boot_page().await.unwrap();
+
+        assert_eq!(boot_page.header.page_pointer.page_id, boot_page_id as u32);
+    }
+
+    #[async_std::test]
+    async fn read_boot_page_of_file_without_boot_page() {
+        let bytes = pages_with_boot_page_at(None);
+        let mut page_reader = PageReader::new(Box::new(async_std::io::Cursor::new(bytes)));
+
+        assert!(matches!(
+            page_reader.read_boot_page().await,
+            Err(Error::BootPageNotFound)
+        ));
+    }
+
+    #[async_std::test]
+    async fn read_boot_page_with_invalid_content() {
+        let mut bytes = pages_with_boot_page_at(Some(9));
+        // The database name region of the boot page is empty
+        bytes[9 * 8192 + 148..9 * 8192 + 404].fill(0);
+        let mut page_reader = PageReader::new(Box::new(async_std::io::Cursor::new(bytes)));
+
+        let err = page_reader.read_boot_page().await.unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "invalid boot page 9: database name region contains no valid name"
+        );
+    }
+
+    #[rstest(file, case("AWLT2005.mdf"), case("spg_verein_TST.mdf"))]
+    #[async_std::test]
+    async fn locate_boot_page_of_bundled_files(file: &str) -> Result<(), Error> {
+        let file = File::open(format!("data/{}", file)).await?;
+        let mut page_reader = PageReader::new(Box::new(file));
+
+        let boot_page = page_reader.read_boot_page().await?;
+
+        assert_eq!(boot_page.header.page_pointer.page_id, 9);
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn stop_scan_at_page_chain_cycle() {
+        let mut bytes = vec![0u8; 3 * 8192];
+        for (page_id, next_page_id) in [(1, 2), (2, 1)] {
+            PageBuilder::new(DATA_PAGE, page_id)
+                .next_page(next_page_id)
+                .write_to(&mut bytes);
+        }
+
+        let mut page_reader = PageReader::new(Box::new(async_std::io::Cursor::new(bytes)));
+        let page_ids = page_reader
+            .read_pages_of_pointers(vec![PagePointer {
+                page_id: 1,
+                file_id: 1,
+            }])
+            .map(|page| page.unwrap().header().page_pointer.page_id)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(page_ids, vec![1, 2]);
+    }
+
+    #[async_std::test]
+    async fn continue_with_next_partition_after_page_chain_cycle() {
+        let mut bytes = vec![0u8; 4 * 8192];
+        // The page chain of the first partition cycles, the second partition is page 3
+        for (page_id, next_page_id) in [(1, 2), (2, 1), (3, 0)] {
+            PageBuilder::new(DATA_PAGE, page_id)
+                .next_page(next_page_id)
+                .write_to(&mut bytes);
+        }
+
+        let mut page_reader = PageReader::new(Box::new(async_std::io::Cursor::new(bytes)));
+        let page_ids = page_reader
+            .read_pages_of_pointers(vec![
+                PagePointer {
+                    page_id: 1,
+                    file_id: 1,
+                },
+                PagePointer {
+                    page_id: 3,
+                    file_id: 1,
+                },
+            ])
+            .map(|page| page.unwrap().header().page_pointer.page_id)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(page_ids, vec![1, 2, 3]);
+    }
+
+    #[rstest(
+        interval,
+        expected_ticks,
+        case(0, 0),
+        case(10, 10),
+        case(100, 1),
+        case(1000, 0)
+    )]
+    fn yield_to_concurrent_tasks_during_scan(interval: usize, expected_ticks: usize) {
+        // Ten linked data pages with ten records each
+        let mut bytes = vec![0u8; 11 * 8192];
+        for page_id in 1..=10 {
+            let page = PageBuilder::new(DATA_PAGE, page_id).next_page(if page_id < 10 {
+                page_id + 1
+            } else {
+                0
+            });
+            (0..10)
+                .fold(page, |page, order_id| page.record(&int_record(&[order_id])))
+                .write_to(&mut bytes);
+        }
+        let base_table_data = BaseTableData::with_table("Orders", vec![("OrderID", "int", 4, 0)])
+            .with_clustered_index(1, 0);
+        let table = base_table_data.table("Orders").unwrap();
+        let mut page_reader = PageReader::new(Box::new(async_std::io::Cursor::new(bytes)));
+
+        let scan_done = Cell::new(false);
+        let ticks = Cell::new(0);
+
+        let scan = async {
+            let rows = rows_of_table(
+                &mut page_reader,
+                table,
+                Options {
+                    yield_interval: interval,
+                    ..Options::default()
+                },
+            )
+            .unwrap()
+            .count()
+            .await;
+            scan_done.set(true);
+            rows
+        };
+        let timer = async {
+            while !scan_done.get() {
+                ticks.set(ticks.get() + 1);
+                futures_lite::future::yield_now().await;
+            }
+        };
+
+        let (rows, ()) = futures_lite::future::block_on(futures_lite::future::zip(scan, timer));
+
+        assert_eq!(rows, 100);
+        assert_eq!(ticks.get(), expected_ticks);
+    }
+
+    #[async_std::test]
+    async fn stop_after_single_page() {
+        let mut bytes = vec![0u8; 3 * 8192];
+        PageBuilder::new(DATA_PAGE, 1).write_to(&mut bytes);
+
+        let mut page_reader = PageReader::new(Box::new(async_std::io::Cursor::new(bytes)));
+        let mut page_stream = page_reader.read_pages_of_pointers(vec![PagePointer {
+            page_id: 1,
+            file_id: 1,
+        }]);
+
+        let page = page_stream.next().await.unwrap().unwrap();
+        assert_eq!(page.header().page_pointer.page_id, 1);
+        assert!(page_stream.next().await.is_none());
+        assert!(page_stream.next().await.is_none());
+        drop(page_stream);
+
+        // Pages 0 and 1 are read, the page after the table's only page is not
+        assert_eq!(page_reader.io_stats.pages_read, 2);
+    }
+
+    #[async_std::test]
+    async fn read_page_chains_of_all_partitions() {
+        let mut bytes = vec![0u8; 5 * 8192];
+        // The first partition consists of the pages 1 and 2, the second of the pages 3 and 4
+        for (page_id, next_page_id) in [(1, 2), (2, 0), (3, 4), (4, 0)] {
+            PageBuilder::new(DATA_PAGE, page_id)
+                .next_page(next_page_id)
+                .write_to(&mut bytes);
+        }
+
+        let mut page_reader = PageReader::new(Box::new(async_std::io::Cursor::new(bytes)));
+        let page_ids = page_reader
+            .read_pages_of_pointers(vec![
+                PagePointer {
+                    page_id: 1,
+                    file_id: 1,
+                },
+                PagePointer {
+                    page_id: 3,
+                    file_id: 1,
+                },
+            ])
+            .map(|page| page.unwrap().header().page_pointer.page_id)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(page_ids, vec![1, 2, 3, 4]);
+    }
+
+    #[async_std::test]
+    async fn serve_repeated_scans_from_page_cache() {
+        let mut bytes = vec![0u8; 3 * 8192];
+        for (page_id, next_page_id) in [(1, 2), (2, 0)] {
+            PageBuilder::new(DATA_PAGE, page_id)
+                .next_page(next_page_id)
+                .write_to(&mut bytes);
+        }
+        let first_page = PagePointer {
+            page_id: 1,
+            file_id: 1,
+        };
+
+        let mut page_reader = PageReader::new(Box::new(async_std::io::Cursor::new(bytes)));
+        page_reader
+            .read_pages_of_pointers(vec![first_page.clone()])
+            .for_each(drop)
+            .await;
+        let first_scan = page_reader.io_stats;
+        page_reader
+            .read_pages_of_pointers(vec![first_page])
+            .for_each(drop)
+            .await;
+        let second_scan = page_reader.io_stats;
+
+        assert_eq!(first_scan.pages_read, 3);
+        assert_eq!(first_scan.bytes_read, 3 * 8192);
+        assert_eq!(second_scan.pages_read, first_scan.pages_read);
+        assert_eq!(second_scan.cache_misses, first_scan.cache_misses);
+        assert!(second_scan.cache_hits - first_scan.cache_hits >= 2);
+    }
+
+    #[async_std::test]
+    async fn start_clustered_table_at_leftmost_leaf_instead_of_first_page() {
+        let mut bytes = vec![0u8; 6 * 8192];
+
+        // Root page 2 at level 1 whose first record points to the leaf page 4
+        PageBuilder::new(2, 2)
+            .level(1)
+            .min_record_length(11)
+            .record(&[0x06, 0, 0, 0, 0, 4, 0, 0, 0, 1, 0])
+            .write_to(&mut bytes);
+
+        // Leaf pages linked in key order 4, 3, 5
+        for (page_id, previous_page_id, next_page_id) in [(3, 4, 5), (4, 0, 3), (5, 3, 0)] {
+            PageBuilder::new(DATA_PAGE, page_id)
+                .previous_page(previous_page_id)
+                .next_page(next_page_id)
+                .write_to(&mut bytes);
+        }
+
+        // The first page of the allocation unit is page 3, e.g. after a page split
+        let base_table_data = BaseTableData::with_table("Orders", vec![("OrderID", "int", 4, 0)])
+            .with_clustered_index(3, 2);
+        let table = base_table_data.table("Orders").unwrap();
+
+        let mut page_reader = PageReader::new(Box::new(async_std::io::Cursor::new(bytes)));
+        let page_ids = page_reader
+            .read_pages_of_table(&table)
+            .map(|page| page.unwrap().header().page_pointer.page_id)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(page_ids, vec![4, 3, 5]);
+    }
+
+    #[async_std::test]
+    async fn start_at_leftmost_leaf_below_root_page() {
+        let mut bytes = vec![0u8; 6 * 8192];
+
+        // IAM page 1 with single page allocations of the leaf pages 3, 4 and 5
+        let mut iam_header = [0u8; 94];
+        for (i, page_id) in [3, 4, 5].iter().enumerate() {
+            iam_header[4 + 42 + i * 6] = *page_id;
+            iam_header[4 + 46 + i * 6] = 1;
+        }
+        PageBuilder::new(IAM_PAGE, 1)
+            .record(&iam_header)
+            .record(&[0; 4])
+            .write_to(&mut bytes);
+
+        // Root page 2 at level 1
+        PageBuilder::new(2, 2).level(1).write_to(&mut bytes);
+
+        // Leaf pages linked in key order 4, 3, 5
+        for (page_id, previous_page_id, next_page_id) in [(3, 4, 5), (4, 0, 3), (5, 3, 0)] {
+            PageBuilder::new(DATA_PAGE, page_id)
+                .previous_page(previous_page_id)
+                .next_page(next_page_id)
+                .write_to(&mut bytes);
+        }
+
+        let mut page_reader = PageReader::new(Box::new(async_std::io::Cursor::new(bytes)));
+        let page_stream = PageStream::new(PageScan {
+            iam_fallback: Some(IamFallback {
+                iam_page_pointers: vec![PagePointer {
+                    page_id: 1,
+                    file_id: 1,
+                }],
+                allocation_unit_ids: vec![0],
+                expected_records: 0,
+                heap: false,
+            }),
+            ..page_reader.scan_pages_of_pointers(vec![PagePointer {
+                page_id: 2,
+                file_id: 1,
+            }])
+        });
+
+        let page_ids = page_stream
+            .map(|page| page.unwrap().header().page_pointer.page_id)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(page_ids, vec![4, 3, 5]);
+    }
 }
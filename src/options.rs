@@ -0,0 +1,108 @@
+//! Options controlling how the content of an MDF file is decoded.
+
+use crate::error::Error;
+#[cfg(feature = "log")]
+use log::error;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+/// Options for opening a database with [`MdfDatabase::open_with_options`](crate::MdfDatabase::open_with_options).
+///
+/// ```rust
+/// use oxidized_mdf::options::{EmptyStringMode, Options};
+///
+/// let options = Options {
+///     empty_string_mode: EmptyStringMode::AsEmptyString,
+///     ..Options::default()
+/// };
+/// ```
+#[derive(Clone)]
+pub struct Options {
+    /// How a zero-length variable column, that is not NULL in the null bitmap, is decoded.
+    pub empty_string_mode: EmptyStringMode,
+    /// Adds the ordinal of each row within the scan, starting at 0, as `Value::BigInt` in the
+    /// synthetic column [`ROW_INDEX_COLUMN`]. The column is not part of the table's schema.
+    pub row_index: bool,
+    /// Fails rows whose values cannot be decoded, e.g. due to unsupported column types, with an
+    /// error instead of logging the error and returning the values decoded so far. Scans of
+    /// tables with column types that cannot be decoded fail before any row is read. Opening
+    /// files whose length is no multiple of the page size fails, too.
+    pub strict: bool,
+    /// Removes the trailing spaces that pad `char(n)` values to their length.
+    pub trim_char_padding: bool,
+    /// Receives the errors of rows that cannot be decoded instead of the log, e.g. to collect
+    /// them in applications that do not use the `log` crate.
+    ///
+    /// ```rust
+    /// use oxidized_mdf::options::Options;
+    /// use std::sync::Arc;
+    ///
+    /// let options = Options {
+    ///     on_record_error: Some(Arc::new(|err| eprintln!("skipped row: {}", err))),
+    ///     ..Options::default()
+    /// };
+    /// ```
+    pub on_record_error: Option<RecordErrorHook>,
+    /// The number of records after which a scan yields to the executor so that other tasks
+    /// make progress during large scans, e.g. on a single-threaded runtime. `0` never yields.
+    pub yield_interval: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            empty_string_mode: EmptyStringMode::default(),
+            row_index: false,
+            strict: false,
+            trim_char_padding: false,
+            on_record_error: None,
+            yield_interval: DEFAULT_YIELD_INTERVAL,
+        }
+    }
+}
+
+/// The default of [`Options::yield_interval`].
+pub const DEFAULT_YIELD_INTERVAL: usize = 1024;
+
+/// A callback receiving the errors of rows that cannot be decoded, see
+/// [`Options::on_record_error`].
+pub type RecordErrorHook = Arc<dyn Fn(&Error) + Send + Sync>;
+
+impl Options {
+    /// Passes the error to the hook or, if there is none, logs it.
+    pub(crate) fn report_record_error(&self, err: &Error) {
+        match &self.on_record_error {
+            Some(on_record_error) => on_record_error(err),
+            None => error!("Cannot read row: {}", err),
+        }
+    }
+}
+
+impl Debug for Options {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Options")
+            .field("empty_string_mode", &self.empty_string_mode)
+            .field("row_index", &self.row_index)
+            .field("strict", &self.strict)
+            .field("trim_char_padding", &self.trim_char_padding)
+            .field("on_record_error", &self.on_record_error.is_some())
+            .field("yield_interval", &self.yield_interval)
+            .finish()
+    }
+}
+
+/// The name of the synthetic column added by [`Options::row_index`].
+pub const ROW_INDEX_COLUMN: &str = "__row_index";
+
+/// Decides how a zero-length variable-length string is decoded.
+///
+/// SQL Server distinguishes between an empty string and NULL but historically this crate
+/// decodes both as [`Value::Null`](crate::Value::Null), which is kept as the default.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum EmptyStringMode {
+    /// Decodes an empty string as `Value::Null`.
+    #[default]
+    AsNull,
+    /// Decodes an empty string as `Value::String("")`.
+    AsEmptyString,
+}
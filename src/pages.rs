@@ -1,31 +1,122 @@
+#[cfg(feature = "compression")]
+use crate::compression::CompressedRecord;
+use crate::options::EmptyStringMode;
 use bitvec::{order::Lsb0, slice::BitSlice};
 use byteorder::{LittleEndian, ReadBytesExt};
-use chrono::{DateTime, Duration, TimeZone, Utc};
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveTime, TimeZone, Utc};
 use core::iter::Iterator;
+#[cfg(feature = "log")]
+use log::warn;
 use rust_decimal::Decimal;
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::iter::FromIterator;
+use std::rc::Rc;
 use uuid::Uuid;
 
+pub(crate) const DATA_PAGE: u8 = 1;
+#[cfg(test)]
+pub(crate) const TEXT_MIX_PAGE: u8 = 3;
+const GAM_PAGE: u8 = 8;
+const SGAM_PAGE: u8 = 9;
+pub(crate) const IAM_PAGE: u8 = 10;
+const PFS_PAGE: u8 = 11;
+pub(crate) const BOOT_PAGE: u8 = 13;
+const DIFF_MAP_PAGE: u8 = 16;
+const ML_MAP_PAGE: u8 = 17;
+
 #[derive(Clone, Debug)]
 pub(crate) struct PageHeader {
+    pub(crate) page_pointer: PagePointer,
+    page_type: u8,
+    level: u8,
+    pub(crate) alloc_unit_id: i64,
+    /// The length of the fixed length part of the records on the page.
+    min_record_length: u16,
     pub(crate) slot_count: u16,
+    pub(crate) previous_page_pointer: Option<PagePointer>,
     pub(crate) next_page_pointer: Option<PagePointer>,
 }
 
+impl PageHeader {
+    /// The type of the page, e.g. [`DATA_PAGE`] or [`IAM_PAGE`].
+    pub(crate) fn page_type(&self) -> u8 {
+        self.page_type
+    }
+
+    /// The level of the page within a B-tree where the leaf level is 0.
+    pub(crate) fn level(&self) -> u8 {
+        self.level
+    }
+}
+
 #[derive(Debug)]
 pub struct BootPage {
     pub(crate) header: PageHeader,
+    pub(crate) version: u16,
     pub(crate) database_name: String,
     pub(crate) first_sys_indexes: PagePointer,
 }
 
+impl BootPage {
+    /// Checks that the page is the boot page read from the given page id, i.e. that the header
+    /// refers to the same page and that the database name region contains a name.
+    pub(crate) fn validate(&self, page_id: u32) -> Result<(), &'static str> {
+        if self.header.page_type() != BOOT_PAGE {
+            return Err("page type is not the boot page type");
+        }
+
+        if self.header.page_pointer.page_id != page_id {
+            return Err("page header refers to another page");
+        }
+
+        let name = self.database_name.trim_end_matches('\0');
+        if name.is_empty() || name.chars().any(char::is_control) {
+            return Err("database name region contains no valid name");
+        }
+
+        Ok(())
+    }
+
+    /// Returns the SQL Server release which writes the on-disk format of the given internal
+    /// database version.
+    pub(crate) fn sql_server_version(&self) -> Option<&'static str> {
+        let release = match self.version {
+            515 => "SQL Server 7.0",
+            539 => "SQL Server 2000",
+            611 | 612 => "SQL Server 2005",
+            655 => "SQL Server 2008",
+            660 | 661 => "SQL Server 2008 R2",
+            706 => "SQL Server 2012",
+            782 => "SQL Server 2014",
+            852 => "SQL Server 2016",
+            869 => "SQL Server 2017",
+            904 => "SQL Server 2019",
+            957 => "SQL Server 2022",
+            _ => return None,
+        };
+
+        Some(release)
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct Record<'a> {
+    /// All fixed length data of the record.
+    fixed_data: &'a [u8],
+    /// The fixed length data that is not parsed yet.
     fixed_bytes: &'a [u8],
     r#type: RecordType,
     null_bitmap: Option<NullBitmap<'a>>,
     variable_columns: Option<VariableColumns<'a>>,
+    number_of_columns: usize,
+    /// The byte of the bit columns parsed last and the position of the next bit within it.
+    packed_bits: Option<(u8, u8)>,
+    /// The offset of the record within its page.
+    offset: usize,
+    /// The pages of the LOB data the complex columns of the record point to.
+    lob_pages: Option<&'a LobPages>,
 }
 
 #[derive(Debug)]
@@ -44,6 +135,10 @@ impl<'a> TryFrom<&'a [u8]> for Record<'a> {
     type Error = &'static str;
 
     fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        if bytes.len() < 4 {
+            return Err("Record must contain at least the status bits and the fixed length size");
+        }
+
         // Bits 1-3 represents record type
         let record_type = (bytes[0] & 0b0000_1110) >> 1;
         let r#type = match record_type {
@@ -70,12 +165,14 @@ impl<'a> TryFrom<&'a [u8]> for Record<'a> {
         // Parse fixed length size
         let fixed_length_size = {
             let fixed_length_size = bytes.read_u16::<LittleEndian>().unwrap();
-            fixed_length_size - 4
+            fixed_length_size
+                .checked_sub(4)
+                .ok_or("Fixed length size must include the record header")?
         };
         read_bytes += 2;
 
-        if fixed_length_size == 0 {
-            todo!("No fixed length data! Record cannot be handled yet");
+        if bytes.len() < fixed_length_size as usize + 2 {
+            return Err("Record is shorter than its fixed length data");
         }
 
         let (fixed_bytes, mut bytes) = bytes.split_at(fixed_length_size as usize);
@@ -84,8 +181,33 @@ impl<'a> TryFrom<&'a [u8]> for Record<'a> {
         let number_of_columns = bytes.read_u16::<LittleEndian>().unwrap() as usize;
         read_bytes += 2;
 
+        // A record without columns has no null bitmap and might even lack the number of
+        // variable length columns
+        if number_of_columns == 0 {
+            let variable_columns = if has_variable_length_columns && bytes.len() >= 2 {
+                Some(VariableColumns::new(read_bytes, bytes)?)
+            } else {
+                None
+            };
+
+            return Ok(Self {
+                fixed_data: fixed_bytes,
+                fixed_bytes,
+                r#type,
+                null_bitmap: None,
+                variable_columns,
+                number_of_columns,
+                packed_bits: None,
+                offset: 0,
+                lob_pages: None,
+            });
+        }
+
         let (null_bitmap, bytes) = if has_null_bitmap {
             let null_bitmap_length = (number_of_columns + 7) / 8;
+            if bytes.len() < null_bitmap_length {
+                return Err("Record is shorter than its null bitmap");
+            }
             let (null_bitmap, bytes) = bytes.split_at(null_bitmap_length);
             read_bytes += null_bitmap_length;
             (Some(null_bitmap), bytes)
@@ -93,26 +215,98 @@ impl<'a> TryFrom<&'a [u8]> for Record<'a> {
             (None, bytes)
         };
 
-        let variable_columns = if has_variable_length_columns {
-            Some(VariableColumns::new(read_bytes, bytes))
-        } else {
+        let variable_columns = if !has_variable_length_columns {
             None
+        } else if fixed_length_size == 0 && !has_null_bitmap {
+            // Without fixed length data and null bitmap all columns are variable length columns
+            // and their number is not stored a second time
+            Some(VariableColumns::with_count(
+                read_bytes,
+                number_of_columns as u16,
+                bytes,
+            )?)
+        } else {
+            Some(VariableColumns::new(read_bytes, bytes)?)
         };
 
         Ok(Self {
+            fixed_data: fixed_bytes,
             fixed_bytes,
             r#type,
             null_bitmap: null_bitmap.map(NullBitmap::new),
             variable_columns,
+            number_of_columns,
+            packed_bits: None,
+            offset: 0,
+            lob_pages: None,
         })
     }
 }
 
 impl<'a> Record<'a> {
+    pub(crate) fn number_of_columns(&self) -> usize {
+        self.number_of_columns
+    }
+
     pub(crate) fn has_variable_length_columns(&self) -> bool {
         self.variable_columns.is_some()
     }
 
+    /// Sets the offset of the record within its page, which the read offsets are based on.
+    pub(crate) fn at_offset(self, offset: usize) -> Self {
+        Self { offset, ..self }
+    }
+
+    /// Returns the offset of the record within its page.
+    pub(crate) fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Sets the pages of the LOB data stored out of row, which the values of the complex
+    /// columns of the record are read from.
+    pub(crate) fn with_lob_pages(self, lob_pages: &'a LobPages) -> Self {
+        Self {
+            lob_pages: Some(lob_pages),
+            ..self
+        }
+    }
+
+    /// Returns the values of the complex variable length columns, e.g. pointers to LOB data
+    /// stored out of row, without parsing the columns.
+    pub(crate) fn complex_columns(&self) -> Vec<&'a [u8]> {
+        let mut variable_columns = match &self.variable_columns {
+            Some(variable_columns) => variable_columns.clone(),
+            None => return Vec::new(),
+        };
+
+        let mut complex_columns = Vec::new();
+        while let Ok(Some((bytes, complex))) = variable_columns.next_column() {
+            if complex {
+                complex_columns.push(bytes);
+            }
+        }
+        complex_columns
+    }
+
+    /// Returns the offset within the page of the next fixed length byte to parse.
+    pub(crate) fn fixed_read_offset(&self) -> usize {
+        // The fixed length data starts after the status bits and the fixed length size
+        self.offset + 4 + self.fixed_data.len() - self.fixed_bytes.len()
+    }
+
+    /// Returns the offset within the page of the next variable length column to parse or of the
+    /// end of the fixed length data if the record has no variable length columns left.
+    pub(crate) fn variable_read_offset(&self) -> usize {
+        match self
+            .variable_columns
+            .as_ref()
+            .and_then(|columns| columns.read_bytes_index)
+        {
+            Some(index) => self.offset + index,
+            None => self.offset + 4 + self.fixed_data.len(),
+        }
+    }
+
     pub(crate) fn parse_i8(self) -> Result<(i8, Record<'a>), &'static str> {
         let (mut bytes, record) = self.parse_bytes(1)?;
 
@@ -163,12 +357,49 @@ impl<'a> Record<'a> {
         })
     }
 
-    fn parse_u128(self) -> Result<(u128, Record<'a>), &'static str> {
-        let (mut bytes, record) = self.parse_bytes(16)?;
+    fn parse_u128_opt(self) -> Result<(Option<u128>, Record<'a>), &'static str> {
+        self.parse_bytes_opt(16).map(|(bytes, record)| {
+            (
+                bytes.map(|mut bytes| bytes.read_u128::<LittleEndian>().unwrap()),
+                record,
+            )
+        })
+    }
 
-        let n = bytes.read_u128::<LittleEndian>().unwrap();
+    /// Parses a `real` value, a 4 byte IEEE 754 floating point number.
+    pub(crate) fn parse_f32_opt(self) -> Result<(Option<f32>, Record<'a>), &'static str> {
+        self.parse_bytes_opt(4).map(|(bytes, record)| {
+            (
+                bytes.map(|mut bytes| bytes.read_f32::<LittleEndian>().unwrap()),
+                record,
+            )
+        })
+    }
 
-        Ok((n, record))
+    /// Parses a `float` value, an 8 byte IEEE 754 floating point number.
+    pub(crate) fn parse_f64_opt(self) -> Result<(Option<f64>, Record<'a>), &'static str> {
+        self.parse_bytes_opt(8).map(|(bytes, record)| {
+            (
+                bytes.map(|mut bytes| bytes.read_f64::<LittleEndian>().unwrap()),
+                record,
+            )
+        })
+    }
+
+    /// Parses a `money` value, which is stored as 8 byte integer in ten-thousandths of a
+    /// currency unit.
+    pub(crate) fn parse_money_opt(self) -> Result<(Option<Decimal>, Record<'a>), &'static str> {
+        let (money, record) = self.parse_i64_opt()?;
+        Ok((money.map(|money| Decimal::new(money, 4)), record))
+    }
+
+    /// Parses a `smallmoney` value, which is stored as 4 byte integer in ten-thousandths of a
+    /// currency unit.
+    pub(crate) fn parse_smallmoney_opt(
+        self,
+    ) -> Result<(Option<Decimal>, Record<'a>), &'static str> {
+        let (money, record) = self.parse_i32_opt()?;
+        Ok((money.map(|money| Decimal::new(money as i64, 4)), record))
     }
 
     pub(crate) fn parse_decimal_opt(
@@ -182,37 +413,79 @@ impl<'a> Record<'a> {
             2 * 4
         } else if precision <= 28 {
             3 * 4
-        } else {
+        } else if precision <= 38 {
             4 * 4
+        } else {
+            return Err("Decimal precision must not exceed 38");
         };
 
         let (bytes, record) = self.parse_bytes_opt(required_storage_bytes)?;
-        Ok((
-            bytes.map(|bytes| {
+
+        let decimal = match bytes {
+            Some(bytes) => {
                 let (sign_byte, mut bytes) = bytes.split_at(1usize);
 
-                let x = if precision <= 9 {
-                    bytes.read_i32::<LittleEndian>().unwrap() as i128
-                } else if precision <= 19 {
-                    bytes.read_i64::<LittleEndian>().unwrap() as i128
-                } else if precision <= 28 {
-                    todo!();
-                } else {
-                    bytes.read_i128::<LittleEndian>().unwrap() as i128
-                };
+                // The magnitude is stored unsigned, the sign is kept separately in the first byte
+                let x = bytes
+                    .read_uint128::<LittleEndian>(required_storage_bytes - 1)
+                    .unwrap();
+
+                if x > Self::MAX_DECIMAL_MANTISSA {
+                    return Err("Decimal value exceeds the supported 96 bit mantissa");
+                }
 
-                let mut decimal = Decimal::from_i128_with_scale(x, scale as u32);
+                let mut decimal = Decimal::from_i128_with_scale(x as i128, scale as u32);
                 decimal.set_sign_positive(sign_byte[0] != 0);
-                decimal
-            }),
-            record,
-        ))
+                Some(decimal)
+            }
+            None => None,
+        };
+
+        Ok((decimal, record))
+    }
+
+    /// `rust_decimal` can represent magnitudes up to 2^96 - 1 only.
+    const MAX_DECIMAL_MANTISSA: u128 = (1 << 96) - 1;
+
+    /// Parses a bit column. Up to eight bit columns share a byte, even if other columns are
+    /// stored in between, where the first bit column is stored in the least significant bit.
+    pub(crate) fn parse_bit(mut self) -> Result<(Option<bool>, Record<'a>), &'static str> {
+        let null = self.pop_next_null_bit();
+
+        let (byte, bit) = match self.packed_bits {
+            Some((byte, bit)) if bit < 8 => (byte, bit),
+            _ => {
+                // The byte is occupied by the following bit columns even if this one is NULL
+                let (byte, remaining_bytes) = self
+                    .fixed_bytes
+                    .split_first()
+                    .ok_or("fixed data underflow")?;
+                self.fixed_bytes = remaining_bytes;
+                (*byte, 0)
+            }
+        };
+
+        self.packed_bits = Some((byte, bit + 1));
+
+        let bit = Some((byte >> bit) & 1 == 1).filter(|_| !null);
+
+        Ok((bit, self))
     }
 
-    pub(crate) fn parse_bit(self) -> Result<(bool, Record<'a>), &'static str> {
-        let (bytes, record) = self.parse_bytes(1)?;
+    /// Parses a bit column at the given position of the byte at the current offset, as located
+    /// by the leaf offset and bit position of `sysrscols`. The byte is not consumed because
+    /// other bit columns may share it.
+    pub(crate) fn parse_bit_at(
+        mut self,
+        position: u8,
+    ) -> Result<(Option<bool>, Record<'a>), &'static str> {
+        if self.pop_next_null_bit() {
+            return Ok((None, self));
+        }
+
+        let byte = *self.fixed_bytes.first().ok_or("fixed data underflow")?;
 
-        Ok((bytes[0] > 0, record))
+        Ok((Some((byte >> position) & 1 == 1), self))
     }
 
     const CLOCK_TICK_MS: f64 = 10.0 / 3.0;
@@ -228,10 +501,14 @@ impl<'a> Record<'a> {
                 let days = bytes.read_i32::<LittleEndian>().unwrap();
 
                 let datetime = Utc
-                    .ymd(1900, 1, 1)
-                    .and_hms(0, 0, 0)
+                    .from_utc_datetime(
+                        &NaiveDate::from_ymd_opt(1900, 1, 1)
+                            .unwrap()
+                            .and_hms_opt(0, 0, 0)
+                            .unwrap(),
+                    )
                     .checked_add_signed(Duration::milliseconds(
-                        (time as f64 * Self::CLOCK_TICK_MS) as i64,
+                        (time as f64 * Self::CLOCK_TICK_MS).round() as i64,
                     ))
                     .ok_or("Cannot parse datetime due to overflow")?
                     .checked_add_signed(Duration::days(days as i64))
@@ -245,29 +522,35 @@ impl<'a> Record<'a> {
         Ok((datetime, record))
     }
 
+    /// The number of days from 0001-01-01 to 9999-12-31, the maximum date of `datetime2`.
+    const MAX_DATETIME2_DAYS: u32 = 3_652_058;
+
     pub(crate) fn parse_datetime2_opt(
         self,
         scale: u8,
     ) -> Result<(Option<DateTime<Utc>>, Record<'a>), &'static str> {
-        let (bytes, record) = self.parse_bytes_opt(8)?;
+        let bytes_of_time = time_length(scale);
+
+        let (bytes, record) = self.parse_bytes_opt(bytes_of_time + 3)?;
 
         let datetime = match bytes {
             Some(mut bytes) => {
-                let bytes_of_time = if scale <= 2 {
-                    3
-                } else if (3..=4).contains(&scale) {
-                    4
-                } else {
-                    5
-                };
-
                 let _time = bytes.read_int::<LittleEndian>(bytes_of_time).unwrap();
                 // TODO: include time in the calcution
-                let days = bytes.read_i24::<LittleEndian>().unwrap();
+                let days = bytes.read_u24::<LittleEndian>().unwrap();
+
+                // Misaligned bytes would otherwise result in plausible-looking wrong dates
+                if days > Self::MAX_DATETIME2_DAYS {
+                    return Err("datetime2 date exceeds 9999-12-31");
+                }
 
                 let datetime = Utc
-                    .ymd(1, 1, 1)
-                    .and_hms(0, 0, 0)
+                    .from_utc_datetime(
+                        &NaiveDate::from_ymd_opt(1, 1, 1)
+                            .unwrap()
+                            .and_hms_opt(0, 0, 0)
+                            .unwrap(),
+                    )
                     .checked_add_signed(Duration::days(days as i64))
                     .ok_or("Cannot parse datetime due to overflow")?;
 
@@ -279,6 +562,108 @@ impl<'a> Record<'a> {
         Ok((datetime, record))
     }
 
+    /// Parses a `time` value, which is stored as number of ticks of `10^-scale` seconds since
+    /// midnight.
+    pub(crate) fn parse_time_opt(
+        self,
+        scale: u8,
+    ) -> Result<(Option<NaiveTime>, Record<'a>), &'static str> {
+        if scale > 7 {
+            return Err("time scale exceeds 7");
+        }
+
+        let (bytes, record) = self.parse_bytes_opt(time_length(scale))?;
+
+        let time = match bytes {
+            Some(mut bytes) => {
+                let ticks = bytes.read_uint::<LittleEndian>(bytes.len()).unwrap();
+
+                // Misaligned bytes would otherwise result in plausible-looking wrong times
+                let time = time_of_ticks(ticks, scale).ok_or("time exceeds 23:59:59.9999999")?;
+
+                Some(time)
+            }
+            None => None,
+        };
+
+        Ok((time, record))
+    }
+
+    /// The largest offset of `datetimeoffset` values from UTC in minutes, i.e. 14 hours.
+    const MAX_DATETIMEOFFSET_MINUTES: i16 = 14 * 60;
+
+    /// Parses a `datetimeoffset` value, which is stored like a `datetime2` value in UTC followed
+    /// by the 2 byte offset of its time zone in minutes.
+    pub(crate) fn parse_datetimeoffset_opt(
+        self,
+        scale: u8,
+    ) -> Result<(Option<DateTime<FixedOffset>>, Record<'a>), &'static str> {
+        if scale > 7 {
+            return Err("datetimeoffset scale exceeds 7");
+        }
+
+        let bytes_of_time = time_length(scale);
+
+        let (bytes, record) = self.parse_bytes_opt(bytes_of_time + 5)?;
+
+        let datetime = match bytes {
+            Some(mut bytes) => {
+                let ticks = bytes.read_uint::<LittleEndian>(bytes_of_time).unwrap();
+                let days = bytes.read_u24::<LittleEndian>().unwrap();
+                let minutes = bytes.read_i16::<LittleEndian>().unwrap();
+
+                // Misaligned bytes would otherwise result in plausible-looking wrong values
+                if days > Self::MAX_DATETIME2_DAYS {
+                    return Err("datetimeoffset date exceeds 9999-12-31");
+                }
+                let time = time_of_ticks(ticks, scale)
+                    .ok_or("datetimeoffset time exceeds 23:59:59.9999999")?;
+                let offset = Some(minutes)
+                    .filter(|minutes| minutes.abs() <= Self::MAX_DATETIMEOFFSET_MINUTES)
+                    .and_then(|minutes| FixedOffset::east_opt(minutes as i32 * 60))
+                    .ok_or("datetimeoffset offset exceeds 14:00")?;
+
+                let utc = NaiveDate::from_ymd_opt(1, 1, 1)
+                    .unwrap()
+                    .checked_add_signed(Duration::days(days as i64))
+                    .ok_or("Cannot parse datetimeoffset due to overflow")?
+                    .and_time(time);
+
+                Some(offset.from_utc_datetime(&utc))
+            }
+            None => None,
+        };
+
+        Ok((datetime, record))
+    }
+
+    /// Parses a `date` value, which is stored as 3 byte number of days since 0001-01-01. The
+    /// stored day 0 is 0001-01-01, NULL is only marked in the null bitmap.
+    pub(crate) fn parse_date_opt(self) -> Result<(Option<NaiveDate>, Record<'a>), &'static str> {
+        let (bytes, record) = self.parse_bytes_opt(3)?;
+
+        let date = match bytes {
+            Some(mut bytes) => {
+                let days = bytes.read_u24::<LittleEndian>().unwrap();
+
+                // Misaligned bytes would otherwise result in plausible-looking wrong dates
+                if days > Self::MAX_DATETIME2_DAYS {
+                    return Err("date exceeds 9999-12-31");
+                }
+
+                let date = NaiveDate::from_ymd_opt(1, 1, 1)
+                    .unwrap()
+                    .checked_add_signed(Duration::days(days as i64))
+                    .ok_or("Cannot parse date due to overflow")?;
+
+                Some(date)
+            }
+            None => None,
+        };
+
+        Ok((date, record))
+    }
+
     pub(crate) fn parse_bytes(self, len: usize) -> Result<(&'a [u8], Record<'a>), &'static str> {
         let (bytes, record) = self.parse_bytes_opt(len)?;
 
@@ -288,6 +673,13 @@ impl<'a> Record<'a> {
         }
     }
 
+    /// Skips a column without reading its data, e.g. a variable length column of a record that
+    /// has no variable length data, by consuming its bit of the null bitmap.
+    pub(crate) fn skip_column(mut self) -> Record<'a> {
+        self.pop_next_null_bit();
+        self
+    }
+
     fn pop_next_null_bit(&mut self) -> bool {
         if let Some(null_bitmap) = self.null_bitmap.as_mut() {
             if let Some(null_bit) = null_bitmap.next() {
@@ -306,48 +698,132 @@ impl<'a> Record<'a> {
             return Ok((None, self));
         }
 
+        if self.fixed_bytes.len() < len {
+            return Err("fixed data underflow");
+        }
+
         let (bytes, remaining_bytes) = &self.fixed_bytes.split_at(len);
 
         let record = Self {
+            fixed_data: self.fixed_data,
             fixed_bytes: remaining_bytes,
             r#type: self.r#type,
             null_bitmap: self.null_bitmap,
             variable_columns: self.variable_columns,
+            number_of_columns: self.number_of_columns,
+            packed_bits: self.packed_bits,
+            offset: self.offset,
+            lob_pages: self.lob_pages,
         };
 
         Ok((Some(bytes), record))
     }
 
+    /// Continues parsing the fixed length data at the given offset which is counted from the start
+    /// of the record, like the leaf offsets of `sysrscols`.
+    pub(crate) fn seek_fixed_offset(self, offset: usize) -> Result<Record<'a>, &'static str> {
+        // The fixed length data starts after the status bits and the fixed length size
+        let fixed_offset = offset
+            .checked_sub(4)
+            .ok_or("Fixed data offset must not point into the record header")?;
+        let fixed_bytes = self
+            .fixed_data
+            .get(fixed_offset..)
+            .ok_or("Fixed data offset exceeds the fixed data")?;
+
+        Ok(Self {
+            fixed_data: self.fixed_data,
+            fixed_bytes,
+            r#type: self.r#type,
+            null_bitmap: self.null_bitmap,
+            variable_columns: self.variable_columns,
+            number_of_columns: self.number_of_columns,
+            packed_bits: self.packed_bits,
+            offset: self.offset,
+            lob_pages: self.lob_pages,
+        })
+    }
+
+    /// Returns the fixed length data that has not been parsed yet. Bytes left over after
+    /// parsing all columns indicate a missing column or a wrong column width.
+    pub(crate) fn remaining_fixed_bytes(&self) -> &'a [u8] {
+        self.fixed_bytes
+    }
+
     const EMPTY_SLICE: &'static [u8] = &[];
 
     pub(crate) fn parse_variables_bytes_opt(
-        mut self,
+        self,
     ) -> Result<(Option<&'a [u8]>, Record<'a>), &'static str> {
-        if self.pop_next_null_bit() {
-            return Ok((None, self));
+        let (column, record) = self.parse_variable_column_opt()?;
+        Ok((column.map(|(bytes, _)| bytes), record))
+    }
+
+    /// Parses the value of a large object column, e.g. `xml` or `nvarchar(max)`. Values stored
+    /// out of row, for which the record only contains a pointer into the LOB data allocation
+    /// unit, are read from the [LOB pages](Record::with_lob_pages) of the record.
+    pub(crate) fn parse_lob_bytes_opt(
+        self,
+    ) -> Result<(Option<LobValue<'a>>, Record<'a>), &'static str> {
+        match self.parse_variable_column_opt()? {
+            (Some((pointer, true)), record) => {
+                let lob_pages = record
+                    .lob_pages
+                    .ok_or("The pages of LOB values stored out of row have not been read")?;
+                let bytes = read_lob_value(pointer, lob_pages)?;
+                Ok((Some(Cow::Owned(bytes)), record))
+            }
+            (column, record) => Ok((column.map(|(bytes, _)| Cow::Borrowed(bytes)), record)),
         }
+    }
+
+    /// Parses the next variable length column and whether it is a complex column, e.g. a
+    /// pointer to data stored out of row.
+    fn parse_variable_column_opt(
+        mut self,
+    ) -> Result<(Option<VariableColumn<'a>>, Record<'a>), &'static str> {
+        let null = self.pop_next_null_bit();
 
         let mut variable_columns = match self.variable_columns {
             Some(columns) => columns,
+            None if null => return Ok((None, self)),
             None => {
                 return Err("no variable column data");
             }
         };
 
-        let bytes = variable_columns
-            .next()
+        // NULL columns followed by non-NULL ones have an offset entry, too, which has to be
+        // skipped
+        if null {
+            variable_columns.next_column()?;
+            return Ok((
+                None,
+                Self {
+                    variable_columns: Some(variable_columns),
+                    ..self
+                },
+            ));
+        }
+
+        let column = variable_columns
+            .next_column()?
             // If the current variable length column index exceeds the number of stored
             // variable length columns, the value is empty by definition (that is, 0 bytes, but not null).
-            .unwrap_or(Self::EMPTY_SLICE);
+            .unwrap_or((Self::EMPTY_SLICE, false));
 
         let record = Self {
+            fixed_data: self.fixed_data,
             fixed_bytes: self.fixed_bytes,
             r#type: self.r#type,
             null_bitmap: self.null_bitmap,
             variable_columns: Some(variable_columns),
+            number_of_columns: self.number_of_columns,
+            packed_bits: self.packed_bits,
+            offset: self.offset,
+            lob_pages: self.lob_pages,
         };
 
-        Ok((Some(bytes), record))
+        Ok((Some(column), record))
     }
 
     pub(crate) fn parse_string_from_fixed_bytes(
@@ -362,21 +838,66 @@ impl<'a> Record<'a> {
         Ok((s, record))
     }
 
+    /// Parses a fixed length single-byte string, i.e. a `char` value, of `len` bytes. The bytes
+    /// are decoded as Windows-1252, the code page of the `Latin1_General` collations, and the
+    /// trailing spaces padding the value are removed if `trim_padding` is set.
+    pub(crate) fn parse_char_opt(
+        self,
+        len: usize,
+        trim_padding: bool,
+    ) -> Result<(Option<String>, Record<'a>), &'static str> {
+        let (bytes, record) = self.parse_bytes_opt(len)?;
+
+        let s = bytes.map(|bytes| {
+            let (s, _, _) = encoding_rs::WINDOWS_1252.decode(bytes);
+            if trim_padding {
+                s.trim_end_matches(' ').to_string()
+            } else {
+                s.into_owned()
+            }
+        });
+
+        Ok((s, record))
+    }
+
+    /// Parses a fixed length UTF-16 string, i.e. an `nchar` value, of `len` bytes.
+    pub(crate) fn parse_nchar_opt(
+        self,
+        len: usize,
+    ) -> Result<(Option<String>, Record<'a>), &'static str> {
+        let (bytes, record) = self.parse_bytes_opt(len)?;
+
+        let s = bytes.map(|bytes| {
+            let (s, _, _) = encoding_rs::UTF_16LE.decode(bytes);
+            s.into_owned()
+        });
+
+        Ok((s, record))
+    }
+
     pub(crate) fn parse_string(self) -> Result<(Option<String>, Record<'a>), &'static str> {
-        let (bytes, record) = self.parse_variables_bytes_opt()?;
+        self.parse_string_as(EmptyStringMode::AsNull)
+    }
 
-        let s = match bytes {
+    /// Parses a variable-length UTF-16 string where `empty_string_mode` decides whether a
+    /// zero-length value, that is not NULL in the null bitmap, is an empty string or NULL.
+    pub(crate) fn parse_string_as(
+        self,
+        empty_string_mode: EmptyStringMode,
+    ) -> Result<(Option<String>, Record<'a>), &'static str> {
+        // (max) columns and row-overflow data only store a pointer in the record
+        let (bytes, record) = self.parse_lob_bytes_opt()?;
+
+        let s = match bytes.as_deref() {
+            Some([]) => match empty_string_mode {
+                EmptyStringMode::AsNull => None,
+                EmptyStringMode::AsEmptyString => Some(String::new()),
+            },
             Some(first) => {
-                if first.is_empty() {
-                    // TODO: this is an open question: is it correct to assume that an
-                    // empty array is an null string? Some SQL Server do so but is that
-                    // true for MSSQL and therefore, is this true for MDF files?
-                    // One of the integration tests demands this assumption.
-                    None
-                } else {
-                    let (s, _, _) = encoding_rs::UTF_16LE.decode(first);
-                    Some(s.into_owned())
-                }
+                // decode sniffs a leading BOM: it is stripped and a big-endian BOM switches
+                // the decoding to UTF-16BE
+                let (s, _, _) = encoding_rs::UTF_16LE.decode(first);
+                Some(s.into_owned())
             }
             None => None,
         };
@@ -384,12 +905,21 @@ impl<'a> Record<'a> {
         Ok((s, record))
     }
 
-    pub(crate) fn parse_uuid(self) -> Result<(Uuid, Self), &'static str> {
-        let (bytes, record) = self.parse_u128()?;
+    /// Parses an 8 byte row identifier consisting of the page id, the file id and the slot.
+    pub(crate) fn parse_rid(self) -> Result<((u32, u16, u16), Record<'a>), &'static str> {
+        let (mut bytes, record) = self.parse_bytes(8)?;
+
+        let page_id = bytes.read_u32::<LittleEndian>().unwrap();
+        let file_id = bytes.read_u16::<LittleEndian>().unwrap();
+        let slot = bytes.read_u16::<LittleEndian>().unwrap();
+
+        Ok(((page_id, file_id, slot), record))
+    }
 
-        let uuid = Uuid::from_u128_le(bytes);
+    pub(crate) fn parse_uuid_opt(self) -> Result<(Option<Uuid>, Self), &'static str> {
+        let (bytes, record) = self.parse_u128_opt()?;
 
-        Ok((uuid, record))
+        Ok((bytes.map(Uuid::from_u128_le), record))
     }
 }
 
@@ -426,7 +956,13 @@ impl<'a> Iterator for NullBitmap<'a> {
     }
 }
 
-#[derive(Debug)]
+/// The bytes of a variable length column and whether it is a complex column.
+type VariableColumn<'a> = (&'a [u8], bool);
+
+/// The bytes of a LOB value, borrowed from the record if the value is stored in row.
+type LobValue<'a> = Cow<'a, [u8]>;
+
+#[derive(Clone, Debug)]
 struct VariableColumns<'a> {
     variable_columns: &'a [u8],
     variable_length_column_lengths: &'a [u8],
@@ -434,69 +970,126 @@ struct VariableColumns<'a> {
 }
 
 impl<'a> VariableColumns<'a> {
-    fn new(mut read_bytes: usize, mut bytes: &'a [u8]) -> Self {
-        let number_of_variable_length_columns = bytes.read_u16::<LittleEndian>().unwrap();
+    fn new(mut read_bytes: usize, mut bytes: &'a [u8]) -> Result<Self, &'static str> {
+        let number_of_variable_length_columns = bytes
+            .read_u16::<LittleEndian>()
+            .map_err(|_| "Record is shorter than its number of variable length columns")?;
         read_bytes += 2;
 
-        /* TODO: from the original coder
-        // If there is no fixed length data and no null bitmap, only the number of variable length columns is stored.
-        if (FixedLengthData.Length == 0 && !HasNullBitmap)
-            NumberOfVariableLengthColumns = NumberOfColumns;
-        else
-        {
-            NumberOfVariableLengthColumns = BitConverter.ToInt16(bytes, offset);
-            offset += 2;
+        Self::with_count(read_bytes, number_of_variable_length_columns, bytes)
+    }
+
+    fn with_count(
+        read_bytes: usize,
+        number_of_variable_length_columns: u16,
+        bytes: &'a [u8],
+    ) -> Result<Self, &'static str> {
+        if bytes.len() < number_of_variable_length_columns as usize * 2 {
+            return Err("Record is shorter than its variable length column offsets");
         }
-        */
 
         let (variable_length_column_lengths, variable_columns) =
             bytes.split_at(number_of_variable_length_columns as usize * 2);
 
-        Self {
+        Ok(Self {
             variable_columns,
             variable_length_column_lengths,
             read_bytes_index: Some(read_bytes + variable_length_column_lengths.len()),
-        }
+        })
     }
-}
-
-impl<'a> Iterator for VariableColumns<'a> {
-    type Item = &'a [u8];
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let read_bytes_index = self.read_bytes_index.take()?;
+    /// Returns the next column and whether it is a complex column, or an error if the end
+    /// offset of the column exceeds the record.
+    fn next_column(&mut self) -> Result<Option<VariableColumn<'a>>, &'static str> {
+        let read_bytes_index = match self.read_bytes_index.take() {
+            Some(read_bytes_index) => read_bytes_index,
+            None => return Ok(None),
+        };
 
         if self.variable_length_column_lengths.len() < 2 {
-            return None;
+            return Ok(None);
         }
 
         let (mut length_bytes, variable_length_column_lengths) =
             self.variable_length_column_lengths.split_at(2);
         self.variable_length_column_lengths = variable_length_column_lengths;
 
-        let end_index_of_readable_bytes = length_bytes.read_i16::<LittleEndian>().unwrap() as usize;
+        // The high bit of the end offset marks complex columns (e.g. row-overflow pointers) and
+        // is not part of the offset.
+        let end_offset = length_bytes.read_u16::<LittleEndian>().unwrap();
+        let complex = end_offset & 0x8000 != 0;
+        let end_index_of_readable_bytes = (end_offset & 0x7FFF) as usize;
         self.read_bytes_index = Some(end_index_of_readable_bytes);
 
-        let length = end_index_of_readable_bytes - read_bytes_index;
+        let length = end_index_of_readable_bytes.saturating_sub(read_bytes_index);
 
-        let (bytes, remaining_bytes) = self
-            .variable_columns
-            .split_at(std::cmp::min(length, self.variable_columns.len()));
+        // A corrupt end offset would otherwise result in a value cut at the end of the record
+        if length > self.variable_columns.len() {
+            return Err("variable column exceeds record");
+        }
+
+        let (bytes, remaining_bytes) = self.variable_columns.split_at(length);
 
         self.variable_columns = remaining_bytes;
 
-        Some(bytes)
+        Ok(Some((bytes, complex)))
+    }
+}
+
+impl<'a> Iterator for VariableColumns<'a> {
+    type Item = &'a [u8];
+
+    /// Returns the bytes of the next column, ending at the first column that exceeds the
+    /// record.
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_column().ok().flatten().map(|(bytes, _)| bytes)
+    }
+}
+
+/// Returns the length of the record at the start of the bytes as declared by its fixed length
+/// size, null bitmap and variable column offsets, or `None` if the record exceeds the bytes.
+fn record_length(bytes: &[u8]) -> Option<usize> {
+    let read_u16 = |at: usize| {
+        bytes
+            .get(at..at + 2)
+            .map(|b| u16::from_le_bytes([b[0], b[1]]) as usize)
+    };
+
+    let status = *bytes.first()?;
+    let mut length = read_u16(2)?;
+    if length < 4 {
+        return None;
+    }
+
+    if status & 0x10 != 0 {
+        let number_of_columns = read_u16(length)?;
+        length += 2 + number_of_columns.div_ceil(8);
+    }
+
+    if status & 0x20 != 0 {
+        let number_of_variable_columns = read_u16(length)?;
+        length += 2;
+        if number_of_variable_columns > 0 {
+            let end_offset = read_u16(length + 2 * (number_of_variable_columns - 1))? & 0x7FFF;
+            length = std::cmp::max(length + 2 * number_of_variable_columns, end_offset);
+        }
+    }
+
+    if length <= bytes.len() {
+        Some(length)
+    } else {
+        None
     }
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub(crate) struct PagePointer {
-    pub(crate) page_id: u16,
+    pub(crate) page_id: u32,
     pub(crate) file_id: u16,
 }
 
 impl PagePointer {
-    pub(crate) fn with_page_id(&self, page_id: u16) -> Self {
+    pub(crate) fn with_page_id(&self, page_id: u32) -> Self {
         Self {
             page_id,
             file_id: self.file_id,
@@ -513,19 +1106,209 @@ impl TryFrom<&[u8]> for PagePointer {
         }
 
         Ok(Self {
-            page_id: (&bytes[0..4]).read_u16::<LittleEndian>().unwrap(),
+            page_id: (&bytes[0..4]).read_u32::<LittleEndian>().unwrap(),
             file_id: (&bytes[4..6]).read_u16::<LittleEndian>().unwrap(),
         })
     }
 }
 
-/// Converts the bytes into an `BootPage`.
+/// The pages of the LOB data the records of a data page point to, see
+/// [`Record::with_lob_pages`].
+pub(crate) type LobPages = HashMap<PagePointer, Rc<Page>>;
+
+/// A link to a record of the LOB data allocation unit, i.e. to the root of a value or to a
+/// fragment of its data.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct LobLink {
+    pub(crate) page_pointer: PagePointer,
+    pub(crate) slot: u16,
+    /// The offset of the end of the linked data within the data of the parent, `None` for text
+    /// pointers.
+    end_offset: Option<u32>,
+}
+
+impl LobLink {
+    /// Parses the 8 byte location of the linked record, i.e. its page id, file id and slot.
+    fn new(mut bytes: &[u8], end_offset: Option<u32>) -> Self {
+        let page_id = bytes.read_u32::<LittleEndian>().unwrap();
+        let file_id = bytes.read_u16::<LittleEndian>().unwrap();
+        let slot = bytes.read_u16::<LittleEndian>().unwrap();
+
+        Self {
+            page_pointer: PagePointer { page_id, file_id },
+            slot,
+            end_offset,
+        }
+    }
+
+    /// Parses links of 12 bytes, each the end offset of the linked data followed by the location
+    /// of the linked record.
+    fn parse_links(bytes: &[u8]) -> Vec<Self> {
+        bytes
+            .chunks_exact(12)
+            .map(|mut link| {
+                let end_offset = link.read_u32::<LittleEndian>().unwrap();
+                Self::new(link, Some(end_offset))
+            })
+            .collect()
+    }
+}
+
+/// Returns the links to the data of a LOB value stored out of row, given the pointer that is
+/// stored in the complex column of the record instead.
 ///
 /// ```text
 /// Bytes       Content
 /// -----       -------
-/// ...         ?
-/// 148-404     DatabaseName (nchar(128))
+/// 0-7         Timestamp of a text pointer
+/// 8-15        Location of the root of the value
+///
+/// 0-11        Header of an inline root, e.g. its type, level and timestamp
+/// 12-         Links to the fragments of the value, 12 bytes each
+/// ```
+pub(crate) fn lob_links(pointer: &[u8]) -> Result<Vec<LobLink>, &'static str> {
+    match pointer.len() {
+        16 => Ok(vec![LobLink::new(&pointer[8..], None)]),
+        length if length >= 24 && length % 12 == 0 => Ok(LobLink::parse_links(&pointer[12..])),
+        _ => Err("Unknown pointer to LOB data stored out of row"),
+    }
+}
+
+/// A record of the LOB data allocation unit.
+#[derive(Debug, PartialEq)]
+pub(crate) enum LobRecord<'a> {
+    /// The data of a value or a fragment of it.
+    Data(&'a [u8]),
+    /// The root of a value with the links to the fragments of its data.
+    Root(Vec<LobLink>),
+}
+
+impl<'a> LobRecord<'a> {
+    pub(crate) const SMALL_ROOT: u16 = 0;
+    pub(crate) const INTERNAL: u16 = 2;
+    pub(crate) const DATA: u16 = 3;
+    pub(crate) const LARGE_ROOT_YUKON: u16 = 5;
+}
+
+/// Converts the bytes into a `LobRecord`, which is stored as blob fragment.
+///
+/// ```text
+/// Bytes       Content
+/// -----       -------
+/// 0-1         Status bits
+/// 2-3         Length of the record
+/// 4-11        Blob id
+/// 12-13       Type
+///
+/// SMALL_ROOT
+/// 14-15       Length of the data
+/// 20-         Data
+///
+/// DATA
+/// 14-         Data
+///
+/// LARGE_ROOT_YUKON
+/// 16-17       Number of links
+/// 24-         Links to the fragments of the data, 12 bytes each
+/// ```
+impl<'a> TryFrom<&'a [u8]> for LobRecord<'a> {
+    type Error = &'static str;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        if bytes.len() < 14 {
+            return Err("LOB record is shorter than its header");
+        }
+
+        // Bits 1-3 represents record type
+        if (bytes[0] & 0b0000_1110) >> 1 != 4 {
+            return Err("LOB record is no blob fragment");
+        }
+
+        let length = (&bytes[2..4]).read_u16::<LittleEndian>().unwrap() as usize;
+        if length < 14 || length > bytes.len() {
+            return Err("LOB record length exceeds the page");
+        }
+        let bytes = &bytes[..length];
+
+        let read_u16 = |at: usize| {
+            bytes
+                .get(at..at + 2)
+                .map(|b| u16::from_le_bytes([b[0], b[1]]) as usize)
+                .ok_or("LOB record is shorter than its header")
+        };
+
+        match read_u16(12)? as u16 {
+            Self::SMALL_ROOT => {
+                let data_length = read_u16(14)?;
+                bytes
+                    .get(20..20 + data_length)
+                    .map(LobRecord::Data)
+                    .ok_or("LOB data exceeds its record")
+            }
+            Self::DATA => Ok(LobRecord::Data(&bytes[14..])),
+            Self::LARGE_ROOT_YUKON => {
+                let number_of_links = read_u16(16)?;
+                bytes
+                    .get(24..24 + number_of_links * 12)
+                    .map(|links| LobRecord::Root(LobLink::parse_links(links)))
+                    .ok_or("LOB links exceed their record")
+            }
+            Self::INTERNAL => Err("LOB values with internal nodes are not supported yet"),
+            _ => Err("Unknown LOB record type"),
+        }
+    }
+}
+
+/// Follow at most this many roots when reading a LOB value, which rules out cycles.
+const MAX_LOB_DEPTH: usize = 8;
+
+/// Reads the data of the LOB value stored out of row that the pointer of a complex column
+/// refers to from the given pages.
+fn read_lob_value(pointer: &[u8], lob_pages: &LobPages) -> Result<Vec<u8>, &'static str> {
+    let mut value = Vec::new();
+    append_lob_data(&lob_links(pointer)?, lob_pages, &mut value, 0)?;
+    Ok(value)
+}
+
+fn append_lob_data(
+    links: &[LobLink],
+    lob_pages: &LobPages,
+    value: &mut Vec<u8>,
+    depth: usize,
+) -> Result<(), &'static str> {
+    if depth > MAX_LOB_DEPTH {
+        return Err("LOB value exceeds the maximum depth, its roots might form a cycle");
+    }
+
+    // The end offsets of the links are relative to the start of the data of their parent
+    let start = value.len();
+    for link in links {
+        let page = lob_pages
+            .get(&link.page_pointer)
+            .ok_or("The page of the LOB value has not been read")?;
+
+        match page.lob_record(link.slot)? {
+            LobRecord::Data(data) => value.extend_from_slice(data),
+            LobRecord::Root(links) => append_lob_data(&links, lob_pages, value, depth + 1)?,
+        }
+
+        if let Some(end_offset) = link.end_offset {
+            value.truncate(start + end_offset as usize);
+        }
+    }
+
+    Ok(())
+}
+
+/// Converts the bytes into an `BootPage`.
+///
+/// ```text
+/// Bytes       Content
+/// -----       -------
+/// ...         ?
+/// 100-101     Version (smallint)
+/// ...         ?
+/// 148-404     DatabaseName (nchar(128))
 /// 612-615     FirstSysIndexes PageID (int)
 /// 616-617     FirstSysIndexes FileID (smallint)
 /// ...         ?
@@ -536,6 +1319,8 @@ impl TryFrom<[u8; 8192]> for BootPage {
     fn try_from(bytes: [u8; 8192]) -> Result<Self, Self::Error> {
         let header = PageHeader::try_from(&bytes[0..96])?;
 
+        let version = (&bytes[100..102]).read_u16::<LittleEndian>().unwrap();
+
         let (s, _, _) = encoding_rs::UTF_16LE.decode(&bytes[148..(404)]);
         let database_name = String::from_iter(s.chars().filter(|c| *c != '†'));
 
@@ -543,6 +1328,7 @@ impl TryFrom<[u8; 8192]> for BootPage {
 
         Ok(Self {
             header,
+            version,
             database_name,
             first_sys_indexes,
         })
@@ -555,9 +1341,22 @@ impl TryFrom<[u8; 8192]> for BootPage {
 /// Bytes       Content
 /// -----       -------
 /// ...         ?
+/// 1           Type (tinyint)
+/// ...         ?
+/// 3           Level (tinyint)
+/// ...         ?
+/// 6-7         IndexID (smallint)
+/// ...         ?
+/// 8-11        PrevPageID (int)
+/// 12-13       PrevPageFileID (smallint)
+/// 14-15       MinLen (smallint)
 //  16-19       NextPageID (int)
 /// 20-21       NextPageFileID (smallint)
 /// 22-23       SlotCnt (smallint)
+/// 24-27       ObjectID (int)
+/// ...         ?
+/// 32-35       PageID (int)
+/// 36-37       FileID (smallint)
 /// ...         ?
 /// ```
 impl TryFrom<&[u8]> for PageHeader {
@@ -568,6 +1367,13 @@ impl TryFrom<&[u8]> for PageHeader {
             return Err("Page header must be 96 bytes.");
         }
 
+        let previous_page_pointer = PagePointer::try_from(&bytes[8..14])?;
+        let previous_page_pointer = if previous_page_pointer.page_id > 0 {
+            Some(previous_page_pointer)
+        } else {
+            None
+        };
+
         let next_page_pointer = PagePointer::try_from(&bytes[16..22])?;
         let next_page_pointer = if next_page_pointer.page_id > 0 {
             Some(next_page_pointer)
@@ -575,8 +1381,18 @@ impl TryFrom<&[u8]> for PageHeader {
             None
         };
 
+        // The allocation unit id is split into the object id and the index id of the header
+        let object_id = (&bytes[24..28]).read_u32::<LittleEndian>().unwrap() as i64;
+        let index_id = (&bytes[6..8]).read_u16::<LittleEndian>().unwrap();
+
         Ok(PageHeader {
+            page_pointer: PagePointer::try_from(&bytes[32..38])?,
+            page_type: bytes[1],
+            level: bytes[3],
+            alloc_unit_id: ((index_id as i64) << 48) | (object_id << 16),
+            min_record_length: (&bytes[14..16]).read_u16::<LittleEndian>().unwrap(),
             slot_count: (&bytes[22..24]).read_u16::<LittleEndian>().unwrap(),
+            previous_page_pointer,
             next_page_pointer,
         })
     }
@@ -593,41 +1409,328 @@ impl Page {
         &self.header
     }
 
-    fn slots(&self) -> Vec<usize> {
-        let mut slots = Vec::with_capacity(self.header.slot_count as usize);
+    const HEADER_LENGTH: usize = 96;
+
+    /// Returns the slot numbers and their record offsets, ordered by offset.
+    fn slots(&self) -> Vec<(u16, usize)> {
+        let slot_count = self.header.slot_count as usize;
+        if slot_count * 2 > self.bytes.len() - Self::HEADER_LENGTH {
+            warn!(
+                "Page {:?} claims {} slots which exceed the page",
+                self.header.page_pointer, slot_count
+            );
+            return Vec::new();
+        }
+
+        let mut slots = Vec::with_capacity(slot_count);
 
-        let slot_range = (self.bytes.len() - self.header.slot_count as usize * 2)..self.bytes.len();
+        let slot_range = (self.bytes.len() - slot_count * 2)..self.bytes.len();
         let mut slot_bytes = &self.bytes[slot_range];
 
+        // The slot array grows backwards from the end of the page, i.e. slot 0 is stored last.
+        let mut slot = slot_count;
         while !slot_bytes.is_empty() {
+            slot -= 1;
             let slot_value = slot_bytes.read_u16::<LittleEndian>().unwrap();
-            slots.push(slot_value as usize);
+            slots.push((slot as u16, slot_value as usize));
         }
 
-        slots.sort_unstable();
+        slots.sort_unstable_by_key(|(_, offset)| *offset);
 
         slots
     }
 
     pub(crate) fn records<'a, 'b: 'a>(&'b self) -> Vec<Record<'a>> {
+        self.records_with_slots()
+            .into_iter()
+            .map(|(_, record)| record)
+            .collect()
+    }
+
+    /// Returns `true` for the allocation map pages whose content consists of bitmaps instead of
+    /// data records.
+    fn is_allocation_map(&self) -> bool {
+        matches!(
+            self.header.page_type(),
+            GAM_PAGE | SGAM_PAGE | IAM_PAGE | PFS_PAGE | DIFF_MAP_PAGE | ML_MAP_PAGE
+        )
+    }
+
+    /// Returns the records of this page together with the number of the slot pointing to them.
+    ///
+    /// Allocation map pages (e.g. reached by a bad page pointer) have no records.
+    pub(crate) fn records_with_slots<'a, 'b: 'a>(&'b self) -> Vec<(u16, Record<'a>)> {
+        self.record_bytes_with_slots()
+            .into_iter()
+            .filter_map(|(slot, offset, bytes)| match Record::try_from(bytes) {
+                Ok(record) => Some((slot, record.at_offset(offset))),
+                Err(err) => {
+                    warn!(
+                        "Skipping slot {} of page {:?}: {}",
+                        slot, self.header.page_pointer, err
+                    );
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the bytes of the records of this page together with the number of the slot
+    /// pointing to them and their offset, without decoding the records.
+    pub(crate) fn record_bytes_with_slots(&self) -> Vec<(u16, usize, &[u8])> {
+        if self.is_allocation_map() {
+            warn!(
+                "Page {:?} is an allocation map page of type {} without records",
+                self.header.page_pointer,
+                self.header.page_type()
+            );
+            return Vec::new();
+        }
+
         let mut records = Vec::with_capacity(self.header.slot_count as usize);
 
+        // Records are stored between the page header and the slot array
+        let records_end = self
+            .bytes
+            .len()
+            .saturating_sub(self.header.slot_count as usize * 2);
+
         let slots = self.slots();
-        for (index, slot) in slots.iter().enumerate() {
+        for (index, (slot, offset)) in slots.iter().enumerate() {
             let range = match slots.get(index + 1) {
-                Some(next_slot) => *slot..*next_slot,
-                None => *slot..self.bytes.len(),
+                Some((_, next_offset)) => *offset..std::cmp::min(*next_offset, records_end),
+                None => *offset..records_end,
             };
 
-            let record = Record::try_from(&self.bytes[range]).unwrap();
-            records.push(record);
+            if range.is_empty() || range.start < Self::HEADER_LENGTH {
+                warn!(
+                    "Skipping slot {} of page {:?} with invalid record range {:?}",
+                    slot, self.header.page_pointer, range
+                );
+                continue;
+            }
+
+            records.push((*slot, *offset, &self.bytes[range]));
+        }
+        records
+    }
+
+    /// Returns the values of the complex columns of the records of this page, e.g. pointers to
+    /// LOB data stored out of row.
+    pub(crate) fn complex_columns(&self) -> Vec<&[u8]> {
+        self.record_bytes_with_slots()
+            .into_iter()
+            .flat_map(|(_, _, bytes)| {
+                #[cfg(feature = "compression")]
+                if let Ok(record) = CompressedRecord::try_from(bytes) {
+                    return record.complex_columns();
+                }
+
+                Record::try_from(bytes)
+                    .map(|record| record.complex_columns())
+                    .unwrap_or_default()
+            })
+            .collect()
+    }
+
+    /// Returns the bytes following the page header if they start with the compression
+    /// information of a PAGE-compressed page, see
+    /// [`CompressionInfo`](crate::compression::CompressionInfo).
+    #[cfg(feature = "compression")]
+    pub(crate) fn compression_info_bytes(&self) -> Option<&[u8]> {
+        // The type flag bits of the header mark pages with compression information
+        if self.bytes[2] & Self::HAS_COMPRESSION_INFO != 0 {
+            Some(&self.bytes[Self::HEADER_LENGTH..])
+        } else {
+            None
+        }
+    }
+
+    #[cfg(feature = "compression")]
+    const HAS_COMPRESSION_INFO: u8 = 0x80;
+
+    /// Returns the records in the space of the page that no slot refers to, i.e. the gaps between
+    /// the records and the free space after them, together with their offsets. Deleted rows
+    /// remain there until the space is reused, so scanning it recovers them on a best-effort
+    /// basis.
+    pub(crate) fn unreferenced_records<'a, 'b: 'a>(&'b self) -> Vec<(usize, Record<'a>)> {
+        if self.is_allocation_map() {
+            return Vec::new();
+        }
+
+        let records_end = self
+            .bytes
+            .len()
+            .saturating_sub(self.header.slot_count as usize * 2);
+
+        let mut referenced = self
+            .slots()
+            .into_iter()
+            .filter(|(_, offset)| (Self::HEADER_LENGTH..records_end).contains(offset))
+            .map(|(_, offset)| {
+                let length = record_length(&self.bytes[offset..records_end]).unwrap_or(1);
+                offset..offset + length
+            })
+            .collect::<Vec<_>>();
+        referenced.push(records_end..records_end);
+
+        let mut records = Vec::new();
+        let mut offset = Self::HEADER_LENGTH;
+        for range in referenced {
+            while offset < range.start {
+                let bytes = &self.bytes[offset..range.start];
+
+                // Only data records with a null bitmap, which is always present since SQL
+                // Server 2005, are plausible candidates
+                let record_type = (bytes[0] & 0b0000_1110) >> 1;
+                let candidate = (record_type == 0 || record_type == 6) && bytes[0] & 0x10 != 0;
+
+                match record_length(bytes).filter(|_| candidate) {
+                    Some(length) => match Record::try_from(&bytes[..length]) {
+                        Ok(record) => {
+                            records.push((offset, record.at_offset(offset)));
+                            offset += length;
+                        }
+                        Err(_) => offset += 1,
+                    },
+                    None => offset += 1,
+                }
+            }
+            offset = std::cmp::max(offset, range.end);
         }
+
         records
     }
 
+    /// Returns the record of the LOB data allocation unit in the given slot of this page.
+    pub(crate) fn lob_record(&self, slot: u16) -> Result<LobRecord<'_>, &'static str> {
+        let records_end = self
+            .bytes
+            .len()
+            .saturating_sub(self.header.slot_count as usize * 2);
+
+        let offset = self
+            .slots()
+            .into_iter()
+            .find(|(other_slot, _)| *other_slot == slot)
+            .map(|(_, offset)| offset)
+            .filter(|offset| (Self::HEADER_LENGTH..records_end).contains(offset))
+            .ok_or("LOB record slot does not exist")?;
+
+        LobRecord::try_from(&self.bytes[offset..records_end])
+    }
+
     pub(crate) fn next_page_pointer(&self) -> Option<&PagePointer> {
         self.header.next_page_pointer.as_ref()
     }
+
+    /// Returns the child page the first record of an index page above the leaf level points to,
+    /// which is the leftmost page of the level below.
+    ///
+    /// The fixed length part of index records starts with the status byte and ends with the
+    /// pointer to the child page, its length is the minimum record length of the page header.
+    pub(crate) fn first_child_page_pointer(&self) -> Result<PagePointer, &'static str> {
+        if self.header.level() == 0 {
+            return Err("Leaf pages have no child pages");
+        }
+
+        let (_, offset) = self
+            .slots()
+            .into_iter()
+            .find(|(slot, _)| *slot == 0)
+            .ok_or("Index page has no records")?;
+
+        let end = offset + self.header.min_record_length as usize;
+        let start = end
+            .checked_sub(6)
+            .filter(|start| *start > offset)
+            .ok_or("Index records are too short for a child page pointer")?;
+        let bytes = self
+            .bytes
+            .get(start..end)
+            .ok_or("Child page pointer exceeds the page")?;
+
+        PagePointer::try_from(bytes)
+    }
+
+    const IAM_EXTENT_BITMAP_LENGTH: usize = 7988;
+
+    /// Returns the pages which are allocated to the allocation unit of this IAM page.
+    ///
+    /// ```text
+    /// Slot 0 (IAM header, offsets relative to the fixed data)
+    /// Bytes       Content
+    /// -----       -------
+    /// ...         ?
+    /// 16-21       StartPage (page pointer)
+    /// ...         ?
+    /// 42-89       SinglePageAllocations (8 page pointers)
+    ///
+    /// Slot 1 (extent allocation bitmap, offsets relative to the fixed data)
+    /// Bytes       Content
+    /// -----       -------
+    /// 0-7987      One bit for each extent of eight pages, counted from StartPage
+    /// ```
+    pub(crate) fn iam_allocated_pages(&self) -> Result<Vec<PagePointer>, &'static str> {
+        if self.header.page_type() != IAM_PAGE {
+            return Err("Page is not an IAM page");
+        }
+
+        let slots = self.slots();
+        if slots.len() < 2 {
+            return Err("IAM page must contain a header and an extent bitmap");
+        }
+
+        let header_start = slots[0].1 + 4;
+        let header = self
+            .bytes
+            .get(header_start..header_start + 90)
+            .ok_or("IAM header exceeds the page")?;
+
+        let start_page = PagePointer::try_from(&header[16..22])?;
+
+        let mut pages = header[42..90]
+            .chunks(6)
+            .map(PagePointer::try_from)
+            .filter_map(Result::ok)
+            .filter(|page_pointer| page_pointer.page_id > 0)
+            .collect::<Vec<_>>();
+
+        let bitmap_start = slots[1].1 + 4;
+        let bitmap_end = std::cmp::min(
+            bitmap_start + Self::IAM_EXTENT_BITMAP_LENGTH,
+            self.bytes.len() - slots.len() * 2,
+        );
+        let bitmap = self
+            .bytes
+            .get(bitmap_start..bitmap_end)
+            .ok_or("IAM extent bitmap exceeds the page")?;
+
+        for (extent, allocated) in BitSlice::<Lsb0, u8>::from_slice(bitmap)
+            .unwrap()
+            .iter()
+            .enumerate()
+        {
+            if !*allocated {
+                continue;
+            }
+
+            for page in 0..8 {
+                let page_id = extent
+                    .checked_mul(8)
+                    .and_then(|p| p.checked_add(page))
+                    .and_then(|p| p.checked_add(start_page.page_id as usize))
+                    .filter(|p| *p <= u32::MAX as usize);
+
+                match page_id {
+                    Some(page_id) => pages.push(start_page.with_page_id(page_id as u32)),
+                    None => break,
+                }
+            }
+        }
+
+        Ok(pages)
+    }
 }
 
 impl TryFrom<[u8; 8192]> for Page {
@@ -640,6 +1743,219 @@ impl TryFrom<[u8; 8192]> for Page {
     }
 }
 
+/// Returns the number of bytes of the time of `time` and `datetime2` values with the given
+/// number of fractional second digits.
+pub(crate) fn time_length(scale: u8) -> usize {
+    match scale {
+        0..=2 => 3,
+        3..=4 => 4,
+        _ => 5,
+    }
+}
+
+/// Converts the number of ticks of `10^-scale` seconds since midnight to the time of day, or
+/// `None` if the ticks exceed a day.
+fn time_of_ticks(ticks: u64, scale: u8) -> Option<NaiveTime> {
+    let ticks_per_second = 10u64.pow(scale as u32);
+    let seconds = ticks / ticks_per_second;
+    let nanos = (ticks % ticks_per_second) * 10u64.pow(9 - scale as u32);
+
+    u32::try_from(seconds)
+        .ok()
+        .filter(|seconds| *seconds < 86_400)
+        .and_then(|seconds| NaiveTime::from_num_seconds_from_midnight_opt(seconds, nanos as u32))
+}
+
+/// Builds the bytes of a record in the FixedVar format for tests.
+#[cfg(test)]
+pub(crate) struct RecordBuilder {
+    column_count: u16,
+    fixed_bytes: Vec<u8>,
+    null_columns: Vec<usize>,
+    variable_columns: Vec<(Vec<u8>, bool)>,
+}
+
+#[cfg(test)]
+impl RecordBuilder {
+    /// Starts a record with the given number of columns in its null bitmap.
+    pub(crate) fn new(column_count: u16) -> Self {
+        Self {
+            column_count,
+            fixed_bytes: Vec::new(),
+            null_columns: Vec::new(),
+            variable_columns: Vec::new(),
+        }
+    }
+
+    /// Appends the bytes of a fixed length column.
+    pub(crate) fn fixed(mut self, bytes: &[u8]) -> Self {
+        self.fixed_bytes.extend_from_slice(bytes);
+        self
+    }
+
+    /// Marks the column with the given index as NULL in the null bitmap.
+    pub(crate) fn null(mut self, column_index: usize) -> Self {
+        self.null_columns.push(column_index);
+        self
+    }
+
+    /// Appends a variable length column stored in row.
+    pub(crate) fn variable(mut self, bytes: &[u8]) -> Self {
+        self.variable_columns.push((bytes.to_vec(), false));
+        self
+    }
+
+    /// Appends a variable length column whose end offset has the complex column bit set, e.g. a
+    /// pointer to a value stored out of row.
+    pub(crate) fn complex(mut self, bytes: &[u8]) -> Self {
+        self.variable_columns.push((bytes.to_vec(), true));
+        self
+    }
+
+    pub(crate) fn build(self) -> Vec<u8> {
+        let status = if self.variable_columns.is_empty() {
+            0x10
+        } else {
+            0x30
+        };
+        let mut bytes = vec![status, 0];
+        bytes.extend_from_slice(&(4 + self.fixed_bytes.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(&self.fixed_bytes);
+        bytes.extend_from_slice(&self.column_count.to_le_bytes());
+
+        let mut null_bitmap = vec![0u8; (self.column_count as usize).div_ceil(8)];
+        for column_index in self.null_columns {
+            null_bitmap[column_index / 8] |= 1 << (column_index % 8);
+        }
+        bytes.extend_from_slice(&null_bitmap);
+
+        if !self.variable_columns.is_empty() {
+            bytes.extend_from_slice(&(self.variable_columns.len() as u16).to_le_bytes());
+            let mut end_offset = bytes.len() + self.variable_columns.len() * 2;
+            for (column, complex) in &self.variable_columns {
+                end_offset += column.len();
+                let flag = if *complex { 0x8000 } else { 0 };
+                bytes.extend_from_slice(&(end_offset as u16 | flag).to_le_bytes());
+            }
+            for (column, _complex) in &self.variable_columns {
+                bytes.extend_from_slice(column);
+            }
+        }
+
+        bytes
+    }
+}
+
+/// Builds a record of the LOB data allocation unit of the given type for tests.
+#[cfg(test)]
+pub(crate) fn lob_record(r#type: u16, body: &[u8]) -> Vec<u8> {
+    let mut bytes = vec![0x08, 0];
+    bytes.extend_from_slice(&(14 + body.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(&[0; 8]);
+    bytes.extend_from_slice(&r#type.to_le_bytes());
+    bytes.extend_from_slice(body);
+    bytes
+}
+
+/// Builds a link to the record in the given slot of a page in file 1 for tests.
+#[cfg(test)]
+pub(crate) fn lob_link(end_offset: u32, page_id: u32, slot: u16) -> Vec<u8> {
+    let mut bytes = end_offset.to_le_bytes().to_vec();
+    bytes.extend_from_slice(&page_id.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&slot.to_le_bytes());
+    bytes
+}
+
+/// Builds the bytes of a page in file 1 for tests.
+#[cfg(test)]
+pub(crate) struct PageBuilder {
+    page_id: u32,
+    bytes: [u8; 8192],
+    free_offset: usize,
+    slot_count: usize,
+}
+
+#[cfg(test)]
+impl PageBuilder {
+    /// Starts a page of the given type without records which is not linked to other pages.
+    pub(crate) fn new(page_type: u8, page_id: u32) -> Self {
+        let mut bytes = [0u8; 8192];
+        bytes[1] = page_type;
+        bytes[32..36].copy_from_slice(&page_id.to_le_bytes());
+        bytes[36] = 1;
+
+        Self {
+            page_id,
+            bytes,
+            free_offset: Page::HEADER_LENGTH,
+            slot_count: 0,
+        }
+    }
+
+    pub(crate) fn previous_page(mut self, page_id: u32) -> Self {
+        self.bytes[8..12].copy_from_slice(&page_id.to_le_bytes());
+        self.bytes[12] = 1;
+        self
+    }
+
+    pub(crate) fn next_page(mut self, page_id: u32) -> Self {
+        self.bytes[16..20].copy_from_slice(&page_id.to_le_bytes());
+        self.bytes[20] = 1;
+        self
+    }
+
+    pub(crate) fn level(mut self, level: u8) -> Self {
+        self.bytes[3] = level;
+        self
+    }
+
+    pub(crate) fn min_record_length(mut self, length: u16) -> Self {
+        self.bytes[14..16].copy_from_slice(&length.to_le_bytes());
+        self
+    }
+
+    /// Appends a record to the free space and refers to it from the next slot.
+    pub(crate) fn record(self, record: &[u8]) -> Self {
+        let offset = self.free_offset as u16;
+        self.bytes(record).slot(offset)
+    }
+
+    /// Appends bytes to the free space which no slot refers to, e.g. a deleted record.
+    pub(crate) fn bytes(mut self, bytes: &[u8]) -> Self {
+        self.bytes[self.free_offset..self.free_offset + bytes.len()].copy_from_slice(bytes);
+        self.free_offset += bytes.len();
+        self
+    }
+
+    /// Marks the page as PAGE-compressed and appends its compression information, which has to
+    /// precede the records.
+    #[cfg(feature = "compression")]
+    pub(crate) fn compression_info(mut self, compression_info: &[u8]) -> Self {
+        self.bytes[2] |= Page::HAS_COMPRESSION_INFO;
+        self.bytes(compression_info)
+    }
+
+    /// Appends a slot with the given record offset to the slot array.
+    pub(crate) fn slot(mut self, offset: u16) -> Self {
+        self.slot_count += 1;
+        let position = self.bytes.len() - self.slot_count * 2;
+        self.bytes[position..position + 2].copy_from_slice(&offset.to_le_bytes());
+        self.bytes[22..24].copy_from_slice(&(self.slot_count as u16).to_le_bytes());
+        self
+    }
+
+    pub(crate) fn build(self) -> [u8; 8192] {
+        self.bytes
+    }
+
+    /// Writes the page at the position of its page id into the bytes of a database file.
+    pub(crate) fn write_to(self, file: &mut [u8]) {
+        let start = self.page_id as usize * 8192;
+        file[start..start + 8192].copy_from_slice(&self.bytes);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -721,6 +2037,267 @@ mod tests {
         assert_eq!(Some(expected_value), parsed_value);
     }
 
+    #[rstest(
+        value,
+        null,
+        expected_date,
+        case([0, 0, 0], false, Some(NaiveDate::from_ymd_opt(1, 1, 1).unwrap())),
+        case([0x54, 0x2a, 0x0b], false, Some(NaiveDate::from_ymd_opt(2004, 6, 1).unwrap())),
+        case([0xda, 0xb9, 0x37], false, Some(NaiveDate::from_ymd_opt(9999, 12, 31).unwrap())),
+        case([0, 0, 0], true, None)
+    )]
+    fn parse_date(value: [u8; 3], null: bool, expected_date: Option<NaiveDate>) {
+        let bytes = fixed_value_record(&value, null);
+        let record = Record::try_from(&bytes[..]).unwrap();
+
+        let (parsed_date, _record) = record.parse_date_opt().unwrap();
+
+        assert_eq!(parsed_date, expected_date);
+    }
+
+    #[test]
+    fn parse_date_exceeding_9999_12_31() {
+        let bytes = fixed_value_record(&[0xdb, 0xb9, 0x37], false);
+        let record = Record::try_from(&bytes[..]).unwrap();
+
+        assert_eq!(
+            record.parse_date_opt().err(),
+            Some("date exceeds 9999-12-31")
+        );
+    }
+
+    #[rstest(
+        scale,
+        value,
+        expected_time,
+        case(0, vec![0x27, 0xba, 0x00], Ok(Some(NaiveTime::from_hms_opt(13, 14, 15).unwrap()))),
+        case(3, vec![0xd3, 0x28, 0xd7, 0x02], Ok(Some(NaiveTime::from_hms_milli_opt(13, 14, 15, 123).unwrap()))),
+        case(
+            7,
+            vec![0xff, 0xbf, 0x69, 0x2a, 0xc9],
+            Ok(Some(NaiveTime::from_hms_nano_opt(23, 59, 59, 999_999_900).unwrap()))
+        ),
+        case(0, vec![0x80, 0x51, 0x01], Err("time exceeds 23:59:59.9999999")),
+        case(7, vec![0xff, 0xff, 0xff, 0xff, 0xff], Err("time exceeds 23:59:59.9999999"))
+    )]
+    fn parse_time(
+        scale: u8,
+        value: Vec<u8>,
+        expected_time: Result<Option<NaiveTime>, &'static str>,
+    ) {
+        let bytes = fixed_value_record(&value, false);
+        let record = Record::try_from(&bytes[..]).unwrap();
+
+        let parsed_time = record.parse_time_opt(scale).map(|(time, _record)| time);
+
+        assert_eq!(parsed_time, expected_time);
+    }
+
+    #[rstest(
+        scale,
+        value,
+        expected_datetime,
+        case(
+            7,
+            vec![0x80, 0x1d, 0x0f, 0x31, 0x5e, 0x54, 0x2a, 0x0b, 0x78, 0x00],
+            Ok(Some("2004-06-01T13:14:15+02:00"))
+        ),
+        case(
+            0,
+            vec![0x07, 0x9e, 0x00, 0x54, 0x2a, 0x0b, 0xd4, 0xfe],
+            Ok(Some("2004-06-01T06:14:15-05:00"))
+        ),
+        case(
+            0,
+            vec![0x07, 0x9e, 0x00, 0x54, 0x2a, 0x0b, 0x84, 0x03],
+            Err("datetimeoffset offset exceeds 14:00")
+        ),
+        case(
+            0,
+            vec![0x07, 0x9e, 0x00, 0xdb, 0xb9, 0x37, 0x00, 0x00],
+            Err("datetimeoffset date exceeds 9999-12-31")
+        )
+    )]
+    fn parse_datetimeoffset(
+        scale: u8,
+        value: Vec<u8>,
+        expected_datetime: Result<Option<&str>, &'static str>,
+    ) {
+        let bytes = fixed_value_record(&value, false);
+        let record = Record::try_from(&bytes[..]).unwrap();
+
+        // Compare the rendered values because equal instants with different offsets are equal
+        let parsed_datetime = record
+            .parse_datetimeoffset_opt(scale)
+            .map(|(datetime, _record)| datetime.map(|datetime| datetime.to_rfc3339()));
+
+        assert_eq!(
+            parsed_datetime,
+            expected_datetime.map(|datetime| datetime.map(str::to_string))
+        );
+    }
+
+    #[rstest(
+        scale,
+        expected_length,
+        case(0, 3),
+        case(2, 3),
+        case(3, 4),
+        case(4, 4),
+        case(5, 5),
+        case(7, 5)
+    )]
+    fn length_of_time_by_scale(scale: u8, expected_length: usize) {
+        assert_eq!(time_length(scale), expected_length);
+    }
+
+    /// Builds a record of a single fixed length column with the given bytes.
+    fn fixed_value_record(value: &[u8], null: bool) -> Vec<u8> {
+        let record = RecordBuilder::new(1).fixed(value);
+        if null { record.null(0) } else { record }.build()
+    }
+
+    #[rstest(
+        value,
+        null,
+        expected_bits,
+        case(1.5f32.to_le_bytes(), false, Some(1.5f32.to_bits())),
+        case((-0.0f32).to_le_bytes(), false, Some(0x8000_0000)),
+        // The smallest positive denormal
+        case([1, 0, 0, 0], false, Some(1)),
+        case([0, 0, 0, 0], true, None)
+    )]
+    fn parse_f32(value: [u8; 4], null: bool, expected_bits: Option<u32>) {
+        let bytes = fixed_value_record(&value, null);
+        let record = Record::try_from(&bytes[..]).unwrap();
+
+        let (parsed_value, _record) = record.parse_f32_opt().unwrap();
+
+        assert_eq!(parsed_value.map(f32::to_bits), expected_bits);
+    }
+
+    #[rstest(
+        value,
+        null,
+        expected_bits,
+        case(1.5f64.to_le_bytes(), false, Some(1.5f64.to_bits())),
+        case((-0.0f64).to_le_bytes(), false, Some(0x8000_0000_0000_0000)),
+        // The smallest positive denormal
+        case([1, 0, 0, 0, 0, 0, 0, 0], false, Some(1)),
+        case([0, 0, 0, 0, 0, 0, 0, 0], true, None)
+    )]
+    fn parse_f64(value: [u8; 8], null: bool, expected_bits: Option<u64>) {
+        let bytes = fixed_value_record(&value, null);
+        let record = Record::try_from(&bytes[..]).unwrap();
+
+        let (parsed_value, _record) = record.parse_f64_opt().unwrap();
+
+        assert_eq!(parsed_value.map(f64::to_bits), expected_bits);
+    }
+
+    #[rstest(
+        bytes,
+        expected_value,
+        case(vec![0x10, 0, 12, 0, 0x40, 0xe2, 0x01, 0, 0, 0, 0, 0, 1, 0, 0], Some(Decimal::new(123456, 4))),
+        case(vec![0x10, 0, 12, 0, 0xc0, 0x1d, 0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 1, 0, 0], Some(Decimal::new(-123456, 4))),
+        case(vec![0x10, 0, 12, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 1], None)
+    )]
+    fn parse_money(bytes: Vec<u8>, expected_value: Option<Decimal>) {
+        let record = Record::try_from(&bytes[..]).unwrap();
+
+        let (parsed_value, _record) = record.parse_money_opt().unwrap();
+
+        assert_eq!(expected_value, parsed_value);
+    }
+
+    #[rstest(
+        bytes,
+        expected_value,
+        case(vec![0x10, 0, 8, 0, 0x40, 0xe2, 0x01, 0, 1, 0, 0], Some(Decimal::new(123456, 4))),
+        case(vec![0x10, 0, 8, 0, 0xff, 0xff, 0xff, 0xff, 1, 0, 0], Some(Decimal::new(-1, 4))),
+        case(vec![0x10, 0, 8, 0, 0, 0, 0, 0, 1, 0, 1], None)
+    )]
+    fn parse_smallmoney(bytes: Vec<u8>, expected_value: Option<Decimal>) {
+        let record = Record::try_from(&bytes[..]).unwrap();
+
+        let (parsed_value, _record) = record.parse_smallmoney_opt().unwrap();
+
+        assert_eq!(expected_value, parsed_value);
+    }
+
+    fn decimal_38_bytes(magnitude: u128) -> Vec<u8> {
+        let mut bytes = vec![0u8, 0u8, 21u8, 0u8, 0x01];
+        bytes.extend_from_slice(&magnitude.to_le_bytes());
+        bytes.extend_from_slice(&[0u8, 0u8]);
+        bytes
+    }
+
+    #[test]
+    fn parse_decimal_38_with_max_mantissa() {
+        let bytes = decimal_38_bytes((1 << 96) - 1);
+        let record = Record::try_from(&bytes[..]).unwrap();
+
+        let (parsed_value, _record) = record.parse_decimal_opt(38, 0).unwrap();
+
+        assert_eq!(parsed_value, Some(Decimal::MAX));
+    }
+
+    #[test]
+    fn parse_decimal_38_exceeding_mantissa() {
+        let bytes = decimal_38_bytes(10u128.pow(38) - 1);
+        let record = Record::try_from(&bytes[..]).unwrap();
+
+        assert_eq!(
+            record.parse_decimal_opt(38, 0).err(),
+            Some("Decimal value exceeds the supported 96 bit mantissa")
+        );
+    }
+
+    #[rstest(
+        precision,
+        magnitude_bytes,
+        magnitude,
+        case(9, 4, 999_999_999),
+        case(10, 8, 9_999_999_999),
+        // Exceeds i64::MAX, i.e. the magnitude must be read unsigned
+        case(19, 8, 10u128.pow(19) - 1),
+        case(20, 12, 10u128.pow(20) - 1),
+        case(28, 12, 10u128.pow(28) - 1),
+        case(29, 16, (1 << 96) - 1)
+    )]
+    fn parse_decimal_at_precision_boundaries(
+        precision: u8,
+        magnitude_bytes: usize,
+        magnitude: u128,
+    ) {
+        // Sign byte, magnitude and a trailing byte of the next column
+        let fixed_length = 4 + 1 + magnitude_bytes as u8 + 1;
+        let mut bytes = vec![0u8, 0u8, fixed_length, 0u8, 0x00];
+        bytes.extend_from_slice(&magnitude.to_le_bytes()[..magnitude_bytes]);
+        bytes.extend_from_slice(&[0x7f, 2u8, 0u8]);
+        let record = Record::try_from(&bytes[..]).unwrap();
+
+        let (parsed_value, record) = record.parse_decimal_opt(precision, 0).unwrap();
+        let (next_value, _record) = record.parse_i8().unwrap();
+
+        assert_eq!(
+            parsed_value,
+            Some(-Decimal::from_i128_with_scale(magnitude as i128, 0))
+        );
+        assert_eq!(next_value, 0x7f);
+    }
+
+    #[test]
+    fn parse_decimal_with_precision_exceeding_38() {
+        let bytes = decimal_38_bytes(1);
+        let record = Record::try_from(&bytes[..]).unwrap();
+
+        assert_eq!(
+            record.parse_decimal_opt(39, 0).err(),
+            Some("Decimal precision must not exceed 38")
+        );
+    }
+
     #[rstest(
         bytes,
         expected_value,
@@ -738,6 +2315,47 @@ mod tests {
         assert_eq!(expected_value, parsed_value);
     }
 
+    #[rstest(
+        value,
+        case(vec![0xff, 0xfe, b'C', 0, b'i', 0, b't', 0, b'y', 0]),
+        case(vec![0xfe, 0xff, 0, b'C', 0, b'i', 0, b't', 0, b'y'])
+    )]
+    fn parse_string_with_bom(value: Vec<u8>) {
+        let mut bytes = vec![0b0011_0000, 0u8, 5u8, 0u8, 0u8, 1u8, 0u8, 0u8, 1u8, 0u8];
+        bytes.extend_from_slice(&(12 + value.len() as u16).to_le_bytes());
+        bytes.extend(value);
+        let record = Record::try_from(&bytes[..]).unwrap();
+
+        let (parsed_value, _record) = record.parse_string().unwrap();
+
+        assert_eq!(parsed_value, Some(String::from("City")));
+    }
+
+    #[rstest(
+        bytes,
+        empty_string_mode,
+        expected_value,
+        // no variable column stored at all, thus, the value is empty but not null
+        case(vec![0b0010_0000, 0u8, 5u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8], EmptyStringMode::AsNull, None),
+        case(vec![0b0010_0000, 0u8, 5u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8], EmptyStringMode::AsEmptyString, Some(String::new())),
+        // one variable column whose end offset equals its start offset
+        case(vec![0b0011_0000, 0u8, 5u8, 0u8, 0u8, 1u8, 0u8, 0u8, 1u8, 0u8, 12u8, 0u8], EmptyStringMode::AsNull, None),
+        case(vec![0b0011_0000, 0u8, 5u8, 0u8, 0u8, 1u8, 0u8, 0u8, 1u8, 0u8, 12u8, 0u8], EmptyStringMode::AsEmptyString, Some(String::new())),
+        // null in the bitmap stays null regardless of the mode
+        case(vec![0b0011_0000, 0u8, 5u8, 0u8, 0u8, 1u8, 0u8, 1u8, 1u8, 0u8, 12u8, 0u8], EmptyStringMode::AsEmptyString, None),
+    )]
+    fn parse_empty_string(
+        bytes: Vec<u8>,
+        empty_string_mode: EmptyStringMode,
+        expected_value: Option<String>,
+    ) {
+        let record = Record::try_from(&bytes[..]).unwrap();
+
+        let (parsed_value, _record) = record.parse_string_as(empty_string_mode).unwrap();
+
+        assert_eq!(expected_value, parsed_value);
+    }
+
     #[test]
     fn parse_string_with_length() {
         // Bytes copied from data/spg_verein_TST.mdf
@@ -797,7 +2415,10 @@ mod tests {
     #[rstest(
         bytes,
         expected_value,
-        case(vec![0u8, 0u8, 12u8, 0u8, 0, 0, 0, 0, 249, 148, 0, 0, 0u8, 0u8], Some(Utc.ymd(2004, 6, 1).and_hms(0, 0, 0)))
+        case(vec![0u8, 0u8, 12u8, 0u8, 0, 0, 0, 0, 249, 148, 0, 0, 0u8, 0u8], Some(Utc.with_ymd_and_hms(2004, 6, 1, 0, 0, 0).unwrap())),
+        case(vec![0u8, 0u8, 12u8, 0u8, 0, 0, 0, 0, 0, 0, 0, 0, 0u8, 0u8], Some(Utc.with_ymd_and_hms(1900, 1, 1, 0, 0, 0).unwrap())),
+        case(vec![0u8, 0u8, 12u8, 0u8, 0, 0xc1, 0xc5, 0, 109, 1, 0, 0, 0u8, 0u8], Some(Utc.with_ymd_and_hms(1901, 1, 1, 12, 0, 0).unwrap())),
+        case(vec![0u8, 0u8, 12u8, 0u8, 0, 0, 0, 0, 70, 46, 255, 255, 0u8, 0u8], Some(Utc.with_ymd_and_hms(1753, 1, 1, 0, 0, 0).unwrap()))
     )]
     fn parse_datetime(bytes: Vec<u8>, expected_value: Option<DateTime<Utc>>) {
         let record = Record::try_from(&bytes[..]).unwrap();
@@ -807,16 +2428,568 @@ mod tests {
         assert_eq!(expected_value, parsed_value);
     }
 
+    #[test]
+    fn parse_variable_columns_after_multi_byte_null_bitmap() {
+        // 16 fixed length columns followed by 4 variable length columns, where the 18th column is
+        // NULL in the third byte of the null bitmap
+        let mut bytes = vec![0x30, 0, 20, 0];
+        bytes.extend(1..=16u8);
+        bytes.extend_from_slice(&[20, 0, 0, 0, 0b0000_0010]);
+        bytes.extend_from_slice(&[4, 0, 39, 0, 39, 0, 41, 0, 41, 0]);
+        bytes.extend_from_slice(&[b'A', 0, b'B', 0, b'C', 0]);
+        let mut record = Record::try_from(&bytes[..]).unwrap();
+
+        for expected in 1..=16i8 {
+            let (value, r) = record.parse_i8().unwrap();
+            assert_eq!(value, expected);
+            record = r;
+        }
+
+        let (column_17, record) = record.parse_string().unwrap();
+        let (column_18, record) = record.parse_string().unwrap();
+        let (column_19, record) = record.parse_string().unwrap();
+        let (column_20, _record) = record
+            .parse_string_as(EmptyStringMode::AsEmptyString)
+            .unwrap();
+
+        assert_eq!(column_17, Some(String::from("AB")));
+        assert_eq!(column_18, None);
+        assert_eq!(column_19, Some(String::from("C")));
+        assert_eq!(column_20, Some(String::new()));
+    }
+
+    #[rstest(
+        days,
+        expected_value,
+        case([0, 0, 0], Ok(Some(Utc.with_ymd_and_hms(1, 1, 1, 0, 0, 0).unwrap()))),
+        case([0xda, 0xb9, 0x37], Ok(Some(Utc.with_ymd_and_hms(9999, 12, 31, 0, 0, 0).unwrap()))),
+        case([0xdb, 0xb9, 0x37], Err("datetime2 date exceeds 9999-12-31")),
+        case([0xff, 0xff, 0xff], Err("datetime2 date exceeds 9999-12-31"))
+    )]
+    fn parse_datetime2_date(
+        days: [u8; 3],
+        expected_value: Result<Option<DateTime<Utc>>, &'static str>,
+    ) {
+        let mut bytes = vec![0x10, 0, 12, 0, 0, 0, 0, 0, 0];
+        bytes.extend_from_slice(&days);
+        bytes.extend_from_slice(&[1, 0, 0]);
+        let record = Record::try_from(&bytes[..]).unwrap();
+
+        let parsed_value = record.parse_datetime2_opt(7).map(|(value, _record)| value);
+
+        assert_eq!(parsed_value, expected_value);
+    }
+
+    #[test]
+    fn parse_packed_bits() {
+        let bytes = [0x10, 0, 5, 0, 0b0000_0010, 2, 0, 0];
+        let record = Record::try_from(&bytes[..]).unwrap();
+
+        let (first, record) = record.parse_bit().unwrap();
+        let (second, _record) = record.parse_bit().unwrap();
+
+        assert_eq!(first, Some(false));
+        assert_eq!(second, Some(true));
+    }
+
+    #[rstest(
+        null_column,
+        expected_bits,
+        case(0, [None, Some(true), Some(true)]),
+        case(1, [Some(true), None, Some(true)])
+    )]
+    fn parse_null_bit_in_packed_byte(null_column: usize, expected_bits: [Option<bool>; 3]) {
+        let bytes = RecordBuilder::new(4)
+            .fixed(&[0b0000_0111])
+            .fixed(&42i32.to_le_bytes())
+            .null(null_column)
+            .build();
+        let record = Record::try_from(&bytes[..]).unwrap();
+
+        let (first, record) = record.parse_bit().unwrap();
+        let (second, record) = record.parse_bit().unwrap();
+        let (third, record) = record.parse_bit().unwrap();
+        // A NULL bit must not shift the columns after the shared byte
+        let (int, _record) = record.parse_i32().unwrap();
+
+        assert_eq!([first, second, third], expected_bits);
+        assert_eq!(int, 42);
+    }
+
+    #[test]
+    fn parse_packed_bits_around_other_column() {
+        let bytes = [0x10, 0, 9, 0, 0b0000_0011, 42, 0, 0, 0, 3, 0, 0];
+        let record = Record::try_from(&bytes[..]).unwrap();
+
+        let (first, record) = record.parse_bit().unwrap();
+        let (int, record) = record.parse_i32().unwrap();
+        let (second, _record) = record.parse_bit().unwrap();
+
+        assert_eq!(first, Some(true));
+        assert_eq!(int, 42);
+        assert_eq!(second, Some(true));
+    }
+
+    #[rstest(
+        parse_smallint,
+        remaining,
+        case(true, &[][..]),
+        case(false, &[3u8, 0u8][..])
+    )]
+    fn remaining_fixed_bytes(parse_smallint: bool, remaining: &[u8]) {
+        let bytes = [0x10, 0, 10, 0, 7, 0, 0, 0, 3, 0, 2, 0, 0];
+        let record = Record::try_from(&bytes[..]).unwrap();
+
+        let (_, mut record) = record.parse_i32_opt().unwrap();
+        if parse_smallint {
+            record = record.parse_i16().unwrap().1;
+        }
+
+        assert_eq!(record.remaining_fixed_bytes(), remaining);
+    }
+
+    #[test]
+    fn parse_rid() {
+        let bytes = [0u8, 0u8, 12u8, 0u8, 0x58, 0x1, 0, 0, 1, 0, 3, 0, 0u8, 0u8];
+        let record = Record::try_from(&bytes[..]).unwrap();
+
+        let (rid, _record) = record.parse_rid().unwrap();
+
+        assert_eq!(rid, (344, 1, 3));
+    }
+
     #[rstest(
         bytes,
         expected_value,
-        case(vec![0u8, 0u8, 20u8, 0u8, 215, 208, 221, 236, 178, 45, 77, 70, 178, 218, 137, 191, 252, 98, 118, 170, 0u8, 0u8], Uuid::from_u128_le(226583458013659211989771997646895829207u128))
+        case(vec![0u8, 0u8, 20u8, 0u8, 215, 208, 221, 236, 178, 45, 77, 70, 178, 218, 137, 191, 252, 98, 118, 170, 0u8, 0u8], Some(Uuid::from_u128_le(226583458013659211989771997646895829207u128))),
+        case(vec![0b0001_0000, 0u8, 20u8, 0u8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1u8, 0u8, 1u8], None)
     )]
-    fn parse_uuid(bytes: Vec<u8>, expected_value: Uuid) {
+    fn parse_uuid_opt(bytes: Vec<u8>, expected_value: Option<Uuid>) {
         let record = Record::try_from(&bytes[..]).unwrap();
 
-        let (parsed_value, _record) = record.parse_uuid().unwrap();
+        let (parsed_value, _record) = record.parse_uuid_opt().unwrap();
 
         assert_eq!(expected_value, parsed_value);
     }
+
+    #[test]
+    fn records_with_slots() {
+        // Slot 0 points to the second record and slot 1 to the first one
+        let bytes = PageBuilder::new(DATA_PAGE, 0)
+            .bytes(&[0u8, 0u8, 5u8, 0u8, 1u8, 0u8, 0u8])
+            .bytes(&[0u8, 0u8, 5u8, 0u8, 2u8, 0u8, 0u8])
+            .slot(103)
+            .slot(96)
+            .build();
+
+        let page = Page::try_from(bytes).unwrap();
+
+        let values = page
+            .records_with_slots()
+            .into_iter()
+            .map(|(slot, record)| (slot, record.parse_i8().unwrap().0))
+            .collect::<Vec<_>>();
+
+        assert_eq!(values, vec![(1, 1i8), (0, 2i8)]);
+    }
+
+    #[test]
+    fn unreferenced_records() {
+        // A live record referenced by slot 0 followed by a deleted one and a ghost in the free
+        // space
+        let bytes = PageBuilder::new(DATA_PAGE, 0)
+            .record(&[0x10, 0, 5, 0, 1, 1, 0, 0])
+            .bytes(&[0x10, 0, 5, 0, 2, 1, 0, 0])
+            .bytes(&[0, 0, 0])
+            .bytes(&[0x1c, 0, 5, 0, 3, 1, 0, 0])
+            .build();
+
+        let page = Page::try_from(bytes).unwrap();
+
+        let values = page
+            .unreferenced_records()
+            .into_iter()
+            .map(|(offset, record)| (offset, record.parse_i8().unwrap().0))
+            .collect::<Vec<_>>();
+
+        assert_eq!(values, vec![(104, 2i8), (115, 3i8)]);
+    }
+
+    #[rstest(
+        page_type,
+        expected_records,
+        case(DATA_PAGE, 2),
+        case(GAM_PAGE, 0),
+        case(SGAM_PAGE, 0),
+        case(IAM_PAGE, 0),
+        case(PFS_PAGE, 0)
+    )]
+    fn no_records_on_allocation_map_pages(page_type: u8, expected_records: usize) {
+        let bytes = PageBuilder::new(page_type, 0)
+            .record(&[0x10, 0, 5, 0, 1, 1, 0, 0])
+            .record(&[0x10, 0, 5, 0, 2, 1, 0, 0])
+            .build();
+
+        let page = Page::try_from(bytes).unwrap();
+
+        assert_eq!(page.records().len(), expected_records);
+    }
+
+    #[rstest(
+        version,
+        expected,
+        case(611, Some("SQL Server 2005")),
+        case(661, Some("SQL Server 2008 R2")),
+        case(904, Some("SQL Server 2019")),
+        case(1, None)
+    )]
+    fn boot_page_sql_server_version(version: u16, expected: Option<&str>) {
+        let mut bytes = [0u8; 8192];
+        bytes[100..102].copy_from_slice(&version.to_le_bytes());
+
+        let boot_page = BootPage::try_from(bytes).unwrap();
+
+        assert_eq!(boot_page.sql_server_version(), expected);
+    }
+
+    #[test]
+    fn iam_allocated_pages() {
+        // IAM header record in slot 0 with a single page allocation of page 80
+        let mut iam_header = [0u8; 94];
+        iam_header[4 + 20] = 1;
+        iam_header[4 + 42] = 80;
+        iam_header[4 + 46] = 1;
+
+        // Extent allocation bitmap in slot 1 with the second extent allocated
+        let bytes = PageBuilder::new(IAM_PAGE, 0)
+            .record(&iam_header)
+            .record(&[0, 0, 0, 0, 0b0000_0010])
+            .build();
+
+        let page = Page::try_from(bytes).unwrap();
+
+        assert_eq!(
+            page.iam_allocated_pages(),
+            Ok(std::iter::once(80)
+                .chain(8..16)
+                .map(|page_id| PagePointer {
+                    page_id,
+                    file_id: 1
+                })
+                .collect::<Vec<_>>())
+        );
+    }
+
+    #[rstest(
+        page_type,
+        level,
+        index_id,
+        case(DATA_PAGE, 0u8, 1u16),
+        // intermediate level of a non-clustered index
+        case(2u8, 1u8, 2u16)
+    )]
+    fn parse_page_header_type_and_level(page_type: u8, level: u8, index_id: u16) {
+        let mut bytes = [0u8; 96];
+        bytes[1] = page_type;
+        bytes[3] = level;
+        bytes[6] = index_id as u8;
+
+        let header = PageHeader::try_from(&bytes[..]).unwrap();
+
+        assert_eq!(header.page_type(), page_type);
+        assert_eq!(header.level(), level);
+        assert_eq!(header.alloc_unit_id >> 48, index_id as i64);
+    }
+
+    #[test]
+    fn first_child_page_pointer_of_index_page() {
+        // Status byte, int key and child page pointer
+        let bytes = PageBuilder::new(2, 0)
+            .level(1)
+            .min_record_length(11)
+            .record(&[0x06, 0, 0, 0, 0, 7, 0, 0, 0, 1, 0])
+            .record(&[0x06, 42, 0, 0, 0, 8, 0, 0, 0, 1, 0])
+            .build();
+
+        let page = Page::try_from(bytes).unwrap();
+
+        assert_eq!(
+            page.first_child_page_pointer(),
+            Ok(PagePointer {
+                page_id: 7,
+                file_id: 1
+            })
+        );
+    }
+
+    #[test]
+    fn parse_page_pointer_beyond_u16_page_ids() {
+        let page_pointer = PagePointer::try_from(&[0x01, 0x00, 0x01, 0x00, 0x01, 0x00][..]);
+
+        assert_eq!(
+            page_pointer,
+            Ok(PagePointer {
+                page_id: 65537,
+                file_id: 1
+            })
+        );
+    }
+
+    #[test]
+    fn parse_300_variable_columns() {
+        let columns = (0..300u16)
+            .map(|i| i.to_le_bytes().to_vec())
+            .collect::<Vec<_>>();
+        let bytes = columns
+            .iter()
+            .fold(RecordBuilder::new(300), |record, column| {
+                record.variable(column)
+            })
+            .build();
+
+        let mut record = Record::try_from(&bytes[..]).unwrap();
+        for column in &columns {
+            let (parsed_bytes, r) = record.parse_variables_bytes_opt().unwrap();
+            assert_eq!(parsed_bytes, Some(&column[..]));
+            record = r;
+        }
+    }
+
+    #[test]
+    fn parse_variable_columns_without_fixed_length_data_and_null_bitmap() {
+        let mut bytes = vec![0b0010_0000, 0u8, 4u8, 0u8, 3u8, 0u8];
+        bytes.extend_from_slice(&[14u8, 0u8, 18u8, 0u8, 18u8, 0u8]);
+        bytes.extend_from_slice(&[b'A', 0u8, b'B', 0u8, b'C', 0u8]);
+
+        let record = Record::try_from(&bytes[..]).unwrap();
+        let (first, record) = record
+            .parse_string_as(EmptyStringMode::AsEmptyString)
+            .unwrap();
+        let (second, record) = record
+            .parse_string_as(EmptyStringMode::AsEmptyString)
+            .unwrap();
+        let (third, _record) = record
+            .parse_string_as(EmptyStringMode::AsEmptyString)
+            .unwrap();
+
+        assert_eq!(first, Some(String::from("A")));
+        assert_eq!(second, Some(String::from("BC")));
+        assert_eq!(third, Some(String::new()));
+    }
+
+    #[test]
+    fn parse_variable_column_after_complex_column() {
+        let columns = [vec![1u8; 24], vec![2u8, 3u8]];
+        let bytes = RecordBuilder::new(2)
+            .complex(&columns[0])
+            .variable(&columns[1])
+            .build();
+
+        let record = Record::try_from(&bytes[..]).unwrap();
+        let (first, record) = record.parse_variables_bytes_opt().unwrap();
+        let (second, _record) = record.parse_variables_bytes_opt().unwrap();
+
+        assert_eq!(first, Some(&columns[0][..]));
+        assert_eq!(second, Some(&columns[1][..]));
+    }
+
+    #[rstest(
+        slot_offsets,
+        case(vec![96, 96]),
+        // offset beyond the page
+        case(vec![96, 9000]),
+        // offset within the page header
+        case(vec![96, 40]),
+        // offset within the slot array
+        case(vec![96, 8188])
+    )]
+    fn skip_slots_with_invalid_record_range(slot_offsets: Vec<u16>) {
+        let bytes = slot_offsets
+            .iter()
+            .fold(
+                PageBuilder::new(DATA_PAGE, 0).bytes(&[0x10, 0, 8, 0, 1, 0, 0, 0, 1, 0, 0]),
+                |page, offset| page.slot(*offset),
+            )
+            .build();
+
+        let page = Page::try_from(bytes).unwrap();
+        let records = page.records();
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].fixed_bytes, &[1, 0, 0, 0]);
+    }
+
+    #[test]
+    fn parse_record_without_columns() {
+        let bytes = [0b0011_0000, 0u8, 5u8, 0u8, 0u8, 0u8, 0u8];
+
+        let record = Record::try_from(&bytes[..]).unwrap();
+
+        assert_eq!(record.number_of_columns(), 0);
+        assert!(!record.has_variable_length_columns());
+        assert_eq!(
+            record.parse_variables_bytes_opt().err(),
+            Some("no variable column data")
+        );
+    }
+
+    #[test]
+    fn parse_i64_with_fixed_data_underflow() {
+        let bytes = vec![0u8, 0u8, 8u8, 0u8, 1u8, 0u8, 0u8, 0u8, 0u8, 0u8];
+        let record = Record::try_from(&bytes[..]).unwrap();
+
+        assert_eq!(record.parse_i64().err(), Some("fixed data underflow"));
+    }
+
+    #[rstest(
+        bytes,
+        // two bytes of fixed data
+        case(vec![0u8, 0u8, 6u8, 0u8, 1u8, 0u8, 0u8, 0u8]),
+        // a single byte of fixed data
+        case(vec![0u8, 0u8, 5u8, 0u8, 1u8, 0u8, 0u8])
+    )]
+    fn parse_i32_with_fixed_data_underflow(bytes: Vec<u8>) {
+        let record = Record::try_from(&bytes[..]).unwrap();
+        let (empty, record) = record.parse_bytes(0).unwrap();
+        assert!(empty.is_empty());
+
+        assert_eq!(record.parse_i32().err(), Some("fixed data underflow"));
+    }
+
+    fn lob_pointer(links: &[Vec<u8>]) -> Vec<u8> {
+        let mut pointer = vec![0u8; 12];
+        links
+            .iter()
+            .for_each(|link| pointer.extend_from_slice(link));
+        pointer
+    }
+
+    fn lob_page_link(page_id: u32, slot: u16, end_offset: Option<u32>) -> LobLink {
+        LobLink {
+            page_pointer: PagePointer {
+                page_id,
+                file_id: 1,
+            },
+            slot,
+            end_offset,
+        }
+    }
+
+    #[rstest(
+        pointer,
+        expected_links,
+        // A text pointer starts with an 8 byte timestamp
+        case(
+            [vec![0xff; 8], lob_link(0, 5, 2)[4..].to_vec()].concat(),
+            Ok(vec![lob_page_link(5, 2, None)])
+        ),
+        case(lob_pointer(&[lob_link(8000, 5, 0)]), Ok(vec![lob_page_link(5, 0, Some(8000))])),
+        case(
+            lob_pointer(&[lob_link(8040, 5, 0), lob_link(10000, 6, 1)]),
+            Ok(vec![lob_page_link(5, 0, Some(8040)), lob_page_link(6, 1, Some(10000))])
+        ),
+        case(vec![0; 20], Err("Unknown pointer to LOB data stored out of row"))
+    )]
+    fn parse_lob_links(pointer: Vec<u8>, expected_links: Result<Vec<LobLink>, &'static str>) {
+        assert_eq!(lob_links(&pointer), expected_links);
+    }
+
+    #[rstest(
+        bytes,
+        expected_record,
+        case(lob_record(LobRecord::DATA, b"<a/>"), Ok(LobRecord::Data(b"<a/>"))),
+        case(
+            lob_record(LobRecord::SMALL_ROOT, &[&[4, 0, 0, 0, 0, 0][..], b"<a/>"].concat()),
+            Ok(LobRecord::Data(b"<a/>"))
+        ),
+        case(
+            lob_record(
+                LobRecord::LARGE_ROOT_YUKON,
+                &[&[5, 0, 2, 0, 0, 0, 0, 0, 0, 0][..], &lob_link(3, 7, 0), &lob_link(6, 8, 1)]
+                    .concat()
+            ),
+            Ok(LobRecord::Root(vec![
+                lob_page_link(7, 0, Some(3)),
+                lob_page_link(8, 1, Some(6))
+            ]))
+        ),
+        case(
+            lob_record(LobRecord::SMALL_ROOT, &[&[40, 0, 0, 0, 0, 0][..], b"<a/>"].concat()),
+            Err("LOB data exceeds its record")
+        ),
+        case(
+            lob_record(LobRecord::INTERNAL, &[0; 10]),
+            Err("LOB values with internal nodes are not supported yet")
+        ),
+        // A primary data record
+        case(RecordBuilder::new(1).fixed(&[0; 14]).build(), Err("LOB record is no blob fragment"))
+    )]
+    fn parse_lob_record(bytes: Vec<u8>, expected_record: Result<LobRecord<'_>, &'static str>) {
+        assert_eq!(LobRecord::try_from(&bytes[..]), expected_record);
+    }
+
+    #[test]
+    fn read_lob_value_of_root_with_fragments_on_other_pages() {
+        let root_page = PageBuilder::new(TEXT_MIX_PAGE, 7)
+            .record(&lob_record(
+                LobRecord::LARGE_ROOT_YUKON,
+                &[
+                    &[5, 0, 2, 0, 0, 0, 0, 0, 0, 0][..],
+                    &lob_link(3, 7, 1),
+                    &lob_link(6, 8, 0),
+                ]
+                .concat(),
+            ))
+            .record(&lob_record(LobRecord::DATA, b"<a>"))
+            .build();
+        // The end offset of the link cuts the fragment
+        let fragment_page = PageBuilder::new(TEXT_MIX_PAGE, 8)
+            .record(&lob_record(LobRecord::DATA, b"</a>"))
+            .build();
+        let lob_pages = vec![(7, root_page), (8, fragment_page)]
+            .into_iter()
+            .map(|(page_id, bytes)| {
+                let page_pointer = PagePointer {
+                    page_id,
+                    file_id: 1,
+                };
+                (page_pointer, Rc::new(Page::try_from(bytes).unwrap()))
+            })
+            .collect::<LobPages>();
+        let bytes = RecordBuilder::new(1)
+            .complex(&lob_pointer(&[lob_link(6, 7, 0)]))
+            .build();
+        let record = Record::try_from(&bytes[..]).unwrap();
+
+        let (value, _record) = record
+            .with_lob_pages(&lob_pages)
+            .parse_lob_bytes_opt()
+            .unwrap();
+
+        assert_eq!(value.as_deref(), Some(&b"<a></a"[..]));
+    }
+
+    #[test]
+    fn fail_lob_value_with_cyclic_roots() {
+        // The root links to itself
+        let root_page = PageBuilder::new(TEXT_MIX_PAGE, 7)
+            .record(&lob_record(
+                LobRecord::LARGE_ROOT_YUKON,
+                &[&[5, 0, 1, 0, 0, 0, 0, 0, 0, 0][..], &lob_link(3, 7, 0)].concat(),
+            ))
+            .build();
+        let mut lob_pages = LobPages::new();
+        lob_pages.insert(
+            PagePointer {
+                page_id: 7,
+                file_id: 1,
+            },
+            Rc::new(Page::try_from(root_page).unwrap()),
+        );
+
+        assert_eq!(
+            read_lob_value(&lob_pointer(&[lob_link(3, 7, 0)]), &lob_pages).err(),
+            Some("LOB value exceeds the maximum depth, its roots might form a cycle")
+        );
+    }
 }
@@ -0,0 +1,444 @@
+use crate::pages::time_length;
+use crate::sys::Column;
+use crate::{Error, MdfDatabase, Row};
+use async_std::stream::Stream;
+use std::collections::BTreeMap;
+
+/// Describes a column of a table as declared in the database schema.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ColumnInfo {
+    pub name: String,
+    /// The SQL type name of the column, e.g. `nvarchar` or `int`.
+    pub type_name: String,
+    /// The name of the system type the type of the column is based on, which differs from
+    /// `type_name` for aliases like `sysname` and user-defined types.
+    pub base_type_name: String,
+    /// The maximum length of the column in bytes.
+    pub max_length: i16,
+    /// The maximum number of characters of string columns, e.g. 50 for `nvarchar(50)` whose
+    /// maximum length is 100 bytes. `None` for other types and `(max)` columns.
+    pub max_characters: Option<i16>,
+    pub precision: u8,
+    pub scale: u8,
+    /// The expression of the default constraint of the column, e.g. `(getdate())`.
+    pub default_expr: Option<String>,
+    /// The expression of the check constraint (rule) bound to the column.
+    pub check_expr: Option<String>,
+    /// The seed and the increment if the column is an identity column.
+    pub identity: Option<(i64, i64)>,
+    /// Whether the value of the column is computed from an expression.
+    pub is_computed: bool,
+    /// Whether the computed value is stored in the records. Values of computed columns that are
+    /// not persisted cannot be read from the file and are missing from the rows.
+    pub is_persisted: bool,
+}
+
+impl From<&Column<'_>> for ColumnInfo {
+    fn from(column: &Column<'_>) -> Self {
+        Self {
+            name: column.name.to_string(),
+            type_name: column.r#type.to_string(),
+            base_type_name: column.base_type().to_string(),
+            max_length: column.max_length,
+            max_characters: column.max_characters(),
+            precision: column.precision,
+            scale: column.scale,
+            default_expr: column.default_expr.map(String::from),
+            check_expr: column.check_expr.map(String::from),
+            identity: column.identity,
+            is_computed: column.computed,
+            is_persisted: column.persisted,
+        }
+    }
+}
+
+impl ColumnInfo {
+    /// Returns the type of the column, e.g. as target for
+    /// [`Value::coerce_to`](crate::Value::coerce_to), or `None` for types that cannot be decoded.
+    pub fn column_type(&self) -> Option<ColumnType> {
+        ColumnType::new(
+            &self.base_type_name,
+            self.max_length,
+            self.precision,
+            self.scale,
+        )
+    }
+}
+
+#[cfg(test)]
+impl ColumnInfo {
+    /// Creates a column of the given system type with the given maximum length in bytes.
+    pub(crate) fn with_type(name: &str, type_name: &str, max_length: i16) -> Self {
+        Self {
+            name: name.to_string(),
+            type_name: type_name.to_string(),
+            base_type_name: type_name.to_string(),
+            max_length,
+            max_characters: None,
+            precision: 0,
+            scale: 0,
+            default_expr: None,
+            check_expr: None,
+            identity: None,
+            is_computed: false,
+            is_persisted: false,
+        }
+    }
+
+    /// Sets the precision and scale of the column, e.g. of a `decimal` column.
+    pub(crate) fn with_precision(mut self, precision: u8, scale: u8) -> Self {
+        self.precision = precision;
+        self.scale = scale;
+        self
+    }
+}
+
+/// The type of a column, which determines how its values are stored in the records and which
+/// [`Value`](crate::Value) they are decoded to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ColumnType {
+    Bit,
+    TinyInt,
+    SmallInt,
+    Int,
+    BigInt,
+    Money,
+    SmallMoney,
+    Decimal {
+        precision: u8,
+        scale: u8,
+    },
+    /// A 4 byte floating point number, i.e. `float(24)`.
+    Real,
+    /// An 8 byte floating point number, i.e. `float(53)`.
+    Float,
+    /// A fixed length single-byte string, with the length in bytes.
+    Char {
+        length: u16,
+    },
+    /// A fixed length Unicode string, with the length in bytes.
+    NChar {
+        length: u16,
+    },
+    /// A variable length string, i.e. `varchar` or `nvarchar`.
+    String,
+    /// A date without time, stored as 3 byte number of days since 0001-01-01.
+    Date,
+    DateTime,
+    DateTime2 {
+        scale: u8,
+    },
+    /// A `datetime2` in UTC, followed by a 2 byte offset of its time zone in minutes.
+    DateTimeOffset {
+        scale: u8,
+    },
+    /// A time of day, stored as 3 to 5 byte number of `10^-scale` second ticks since midnight.
+    Time {
+        scale: u8,
+    },
+    Uuid,
+    /// A fixed length binary value, with the length in bytes.
+    FixedBinary {
+        length: u16,
+    },
+    /// A variable length binary value, including `varbinary(max)`.
+    Binary,
+    RowVersion,
+    Xml,
+    /// The physical location of a row, stored in the rows of non-clustered indexes on heaps.
+    Rid,
+}
+
+impl ColumnType {
+    /// Returns the type of a column with the given SQL type name, e.g. `nvarchar`, its length in
+    /// bytes, precision and scale.
+    pub(crate) fn new(type_name: &str, max_length: i16, precision: u8, scale: u8) -> Option<Self> {
+        let column_type = match type_name {
+            "bit" => ColumnType::Bit,
+            "tinyint" => ColumnType::TinyInt,
+            "smallint" => ColumnType::SmallInt,
+            "int" => ColumnType::Int,
+            "bigint" => ColumnType::BigInt,
+            "money" => ColumnType::Money,
+            "smallmoney" => ColumnType::SmallMoney,
+            "decimal" | "numeric" => ColumnType::Decimal { precision, scale },
+            "real" => ColumnType::Real,
+            "float" => ColumnType::Float,
+            "char" if max_length > 0 => ColumnType::Char {
+                length: max_length as u16,
+            },
+            "nchar" if max_length > 0 => ColumnType::NChar {
+                length: max_length as u16,
+            },
+            // sysname is an alias of nvarchar(128)
+            "nvarchar" | "sysname" | "varchar" => ColumnType::String,
+            "date" => ColumnType::Date,
+            "datetime" => ColumnType::DateTime,
+            "datetime2" => ColumnType::DateTime2 { scale },
+            "datetimeoffset" => ColumnType::DateTimeOffset { scale },
+            "time" => ColumnType::Time { scale },
+            "uniqueidentifier" => ColumnType::Uuid,
+            "binary" if max_length > 0 => ColumnType::FixedBinary {
+                length: max_length as u16,
+            },
+            // varbinary(max) has a max length of -1, its values are decoded if stored in the record
+            "varbinary" => ColumnType::Binary,
+            // timestamp is the former name of rowversion
+            "timestamp" => ColumnType::RowVersion,
+            "xml" => ColumnType::Xml,
+            "RID" => ColumnType::Rid,
+            _ => return None,
+        };
+
+        Some(column_type)
+    }
+
+    /// Returns the number of bytes the values of the type occupy in the fixed length data of the
+    /// records or `None` for variable length types. Bits are packed into shared bytes and have
+    /// no length of their own.
+    pub fn fixed_length(&self) -> Option<usize> {
+        match self {
+            ColumnType::Bit => None,
+            ColumnType::TinyInt => Some(1),
+            ColumnType::SmallInt => Some(2),
+            ColumnType::Int | ColumnType::SmallMoney | ColumnType::Real => Some(4),
+            ColumnType::BigInt | ColumnType::Money | ColumnType::Float => Some(8),
+            // A sign byte followed by 4 bytes per 9 to 10 digits
+            ColumnType::Decimal { precision, .. } => match precision {
+                0..=9 => Some(5),
+                10..=19 => Some(9),
+                20..=28 => Some(13),
+                _ => Some(17),
+            },
+            ColumnType::Char { length }
+            | ColumnType::NChar { length }
+            | ColumnType::FixedBinary { length } => Some(*length as usize),
+            ColumnType::Date => Some(3),
+            ColumnType::DateTime => Some(8),
+            // 3 to 5 bytes for the time followed by 3 bytes for the date
+            ColumnType::DateTime2 { scale } => Some(time_length(*scale) + 3),
+            ColumnType::DateTimeOffset { scale } => Some(time_length(*scale) + 5),
+            ColumnType::Time { scale } => Some(time_length(*scale)),
+            ColumnType::Uuid => Some(16),
+            ColumnType::RowVersion | ColumnType::Rid => Some(8),
+            ColumnType::String | ColumnType::Binary | ColumnType::Xml => None,
+        }
+    }
+}
+
+/// The resolved metadata of a table for code that works with the same table repeatedly, see
+/// [`MdfDatabase::table`].
+#[derive(Clone, Debug)]
+pub struct TableHandle {
+    pub(crate) name: String,
+    pub(crate) columns: Vec<ColumnInfo>,
+    pub(crate) row_count: i64,
+}
+
+impl TableHandle {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn columns(&self) -> &[ColumnInfo] {
+        &self.columns
+    }
+
+    /// Returns the number of rows of the table as stored in the system tables.
+    pub fn row_count(&self) -> i64 {
+        self.row_count
+    }
+
+    /// Returns a stream of the rows of the table, read from the given database. Fails with
+    /// [`Error::UnknownTable`] if the database does not contain the table, i.e. the handle
+    /// belongs to another database.
+    pub fn rows<'a, 'b: 'a>(
+        &self,
+        db: &'b mut MdfDatabase,
+    ) -> Result<impl Stream<Item = Row> + 'a, Error> {
+        db.rows(&self.name)
+    }
+}
+
+/// The differences between the schemas of two databases, see [`MdfDatabase::diff`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SchemaDiff {
+    pub added_tables: Vec<String>,
+    pub removed_tables: Vec<String>,
+    /// The tables contained in both databases whose columns differ.
+    pub changed_tables: Vec<TableDiff>,
+}
+
+/// The differences between the columns of a table contained in two databases.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TableDiff {
+    pub name: String,
+    pub added_columns: Vec<String>,
+    pub removed_columns: Vec<String>,
+    /// The columns whose type, length, precision or scale differ, as old and new column.
+    pub changed_columns: Vec<(ColumnInfo, ColumnInfo)>,
+}
+
+impl SchemaDiff {
+    pub(crate) fn new(
+        old: BTreeMap<String, Vec<ColumnInfo>>,
+        mut new: BTreeMap<String, Vec<ColumnInfo>>,
+    ) -> Self {
+        let mut diff = SchemaDiff::default();
+
+        for (table_name, old_columns) in old {
+            match new.remove(&table_name) {
+                Some(new_columns) => {
+                    if let Some(table_diff) = TableDiff::new(table_name, old_columns, new_columns) {
+                        diff.changed_tables.push(table_diff);
+                    }
+                }
+                None => diff.removed_tables.push(table_name),
+            }
+        }
+        diff.added_tables = new.into_keys().collect();
+
+        diff
+    }
+
+    /// Returns `true` if both schemas are equal.
+    pub fn is_empty(&self) -> bool {
+        self.added_tables.is_empty()
+            && self.removed_tables.is_empty()
+            && self.changed_tables.is_empty()
+    }
+}
+
+impl TableDiff {
+    fn new(name: String, old: Vec<ColumnInfo>, new: Vec<ColumnInfo>) -> Option<Self> {
+        let mut new = new
+            .into_iter()
+            .map(|column| (column.name.clone(), column))
+            .collect::<BTreeMap<_, _>>();
+
+        let mut removed_columns = Vec::new();
+        let mut changed_columns = Vec::new();
+        for old_column in old {
+            match new.remove(&old_column.name) {
+                Some(new_column) => {
+                    if (
+                        &old_column.type_name,
+                        old_column.max_length,
+                        old_column.precision,
+                        old_column.scale,
+                    ) != (
+                        &new_column.type_name,
+                        new_column.max_length,
+                        new_column.precision,
+                        new_column.scale,
+                    ) {
+                        changed_columns.push((old_column, new_column));
+                    }
+                }
+                None => removed_columns.push(old_column.name),
+            }
+        }
+        let added_columns = new.into_keys().collect::<Vec<_>>();
+
+        if added_columns.is_empty() && removed_columns.is_empty() && changed_columns.is_empty() {
+            None
+        } else {
+            Some(Self {
+                name,
+                added_columns,
+                removed_columns,
+                changed_columns,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn schema(tables: Vec<(&str, Vec<ColumnInfo>)>) -> BTreeMap<String, Vec<ColumnInfo>> {
+        tables
+            .into_iter()
+            .map(|(name, columns)| (name.to_string(), columns))
+            .collect()
+    }
+
+    #[test]
+    fn column_type_of_user_defined_type() {
+        // e.g. the AdventureWorks type Name which is based on nvarchar(50)
+        let column = ColumnInfo {
+            base_type_name: String::from("nvarchar"),
+            ..ColumnInfo::with_type("LastName", "Name", 100)
+        };
+
+        assert_eq!(column.column_type(), Some(ColumnType::String));
+    }
+
+    #[test]
+    fn diff_equal_schemas() {
+        let tables = vec![(
+            "Address",
+            vec![
+                ColumnInfo::with_type("AddressID", "int", 4),
+                ColumnInfo::with_type("City", "nvarchar", 60),
+            ],
+        )];
+
+        let diff = SchemaDiff::new(schema(tables.clone()), schema(tables));
+
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn diff_changed_schemas() {
+        let old = schema(vec![
+            (
+                "Address",
+                vec![
+                    ColumnInfo::with_type("AddressID", "int", 4),
+                    ColumnInfo::with_type("City", "nvarchar", 60),
+                    ColumnInfo::with_type("PostalCode", "nvarchar", 30),
+                ],
+            ),
+            (
+                "ErrorLog",
+                vec![ColumnInfo::with_type("ErrorLogID", "int", 4)],
+            ),
+        ]);
+        let new = schema(vec![
+            (
+                "Address",
+                vec![
+                    ColumnInfo::with_type("AddressID", "bigint", 8),
+                    ColumnInfo::with_type("City", "nvarchar", 60),
+                    ColumnInfo::with_type("CountryRegion", "nvarchar", 100),
+                ],
+            ),
+            (
+                "Customer",
+                vec![ColumnInfo::with_type("CustomerID", "int", 4)],
+            ),
+        ]);
+
+        let diff = SchemaDiff::new(old, new);
+
+        assert_eq!(
+            diff,
+            SchemaDiff {
+                added_tables: vec![String::from("Customer")],
+                removed_tables: vec![String::from("ErrorLog")],
+                changed_tables: vec![TableDiff {
+                    name: String::from("Address"),
+                    added_columns: vec![String::from("CountryRegion")],
+                    removed_columns: vec![String::from("PostalCode")],
+                    changed_columns: vec![(
+                        ColumnInfo::with_type("AddressID", "int", 4),
+                        ColumnInfo::with_type("AddressID", "bigint", 8)
+                    )],
+                }],
+            }
+        );
+    }
+}
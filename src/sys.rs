@@ -1,8 +1,13 @@
 use crate::error::Error;
 use crate::pages::{BootPage, PagePointer, Record};
+use crate::schema::ColumnType;
 use crate::PageReader;
 use async_std::stream::StreamExt;
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::convert::TryFrom;
+use std::rc::Rc;
 
 pub(crate) struct BaseTableData {
     sysalloc_units: Vec<SysallocUnit>,
@@ -10,19 +15,48 @@ pub(crate) struct BaseTableData {
     sysschobjs: Vec<Sysschobj>,
     sysscalartypes: Vec<Sysscalartype>,
     syscolpars: Vec<Syscolpar>,
+    sysobjvalues: Vec<Sysobjvalue>,
+    sysprufiles: Vec<Sysprufile>,
+    sysrscols: Vec<Sysrscol>,
+    /// The columns of each table by table id, resolved on the first access of the table.
+    resolved_columns: RefCell<HashMap<i32, Rc<[ResolvedColumn]>>>,
+}
+
+/// The positions of a column's `syscolpars` and `sysscalartypes` rows together with its leaf
+/// offset, which are costly to look up for databases with many tables.
+#[derive(Clone, Debug)]
+struct ResolvedColumn {
+    syscolpar: usize,
+    sysscalartype: usize,
+    leaf_offset: Option<u16>,
+    leaf_bit_position: Option<u8>,
 }
 
 const SYSROWEST_AUID: i64 = 327680;
 const SYSSCHOBJS_IDMAJOR: i32 = 34;
 const SYSCOLPARS_IDMAJOR: i32 = 41;
 const SYSSCALARTYPE_IDMAJOR: i32 = 50;
+const SYSOBJVALUES_IDMAJOR: i32 = 60;
+const SYSPRUFILES_IDMAJOR: i32 = 24;
+const SYSRSCOLS_IDMAJOR: i32 = 3;
+
+/// The `filetype` of `sysprufiles` rows which describe data files (in contrast to log files).
+const ROWS_FILETYPE: u8 = 0;
+
+/// The `valclass` of `sysobjvalues` rows which store the definition of an object.
+const OBJECT_DEFINITION_VALCLASS: u8 = 1;
 
 macro_rules! parse_page_records {
     ( $page_reader:expr, $page_pointer:expr, $t:ty ) => {{
         let mut parsed_records = Vec::new();
 
-        let mut page_stream = $page_reader.read_pages_of_pointer($page_pointer);
-        while let Some(Ok(page)) = page_stream.next().await {
+        let mut page_stream = $page_reader.read_system_pages_of_pointer($page_pointer);
+        while let Some(page) = page_stream.next().await {
+            let page = match page {
+                Ok(page) => page,
+                Err(err @ Error::CorruptMetadata(_)) => return Err(err),
+                Err(_) => break,
+            };
             parsed_records.extend(
                 page.records()
                     .into_iter()
@@ -48,8 +82,13 @@ macro_rules! parse_from_sysrow_set {
 
         let mut parsed_records = Vec::new();
 
-        let mut page_stream = $page_reader.read_pages_of_pointer(page_pointer);
-        while let Some(Ok(page)) = page_stream.next().await {
+        let mut page_stream = $page_reader.read_system_pages_of_pointer(page_pointer);
+        while let Some(page) = page_stream.next().await {
+            let page = match page {
+                Ok(page) => page,
+                Err(err @ Error::CorruptMetadata(_)) => return Err(err),
+                Err(_) => break,
+            };
             parsed_records.extend(
                 page.records()
                     .into_iter()
@@ -109,12 +148,53 @@ impl BaseTableData {
             Syscolpar
         );
 
+        let sysobjvalues = parse_from_sysrow_set!(
+            &mut page_reader,
+            &sysrow_sets
+                .iter()
+                .find(|row| row.idmajor == SYSOBJVALUES_IDMAJOR && row.idminor == 1),
+            &sysalloc_units,
+            Sysobjvalue
+        );
+
+        let sysprufiles_rowset = sysrow_sets
+            .iter()
+            .find(|row| row.idmajor == SYSPRUFILES_IDMAJOR && row.idminor == 1);
+        let sysprufiles = if sysprufiles_rowset.is_some() {
+            parse_from_sysrow_set!(
+                &mut page_reader,
+                &sysprufiles_rowset,
+                &sysalloc_units,
+                Sysprufile
+            )
+        } else {
+            Vec::new()
+        };
+
+        let sysrscols_rowset = sysrow_sets
+            .iter()
+            .find(|row| row.idmajor == SYSRSCOLS_IDMAJOR && row.idminor == 1);
+        let sysrscols = if sysrscols_rowset.is_some() {
+            parse_from_sysrow_set!(
+                &mut page_reader,
+                &sysrscols_rowset,
+                &sysalloc_units,
+                Sysrscol
+            )
+        } else {
+            Vec::new()
+        };
+
         Ok(Self {
             sysalloc_units,
             sysrow_sets,
             sysschobjs,
             sysscalartypes,
             syscolpars,
+            sysobjvalues,
+            sysprufiles,
+            sysrscols,
+            resolved_columns: RefCell::new(HashMap::new()),
         })
     }
 
@@ -124,6 +204,97 @@ impl BaseTableData {
             .filter(|o| o.nsclass == 0 && o.pclass == 1)
     }
 
+    /// Returns the definition (e.g. the expression of a default constraint) of the given object.
+    fn object_definition(&self, object_id: i32) -> Option<&str> {
+        if object_id == 0 {
+            return None;
+        }
+
+        self.sysobjvalues
+            .iter()
+            .find(|v| {
+                v.valclass == OBJECT_DEFINITION_VALCLASS && v.objid == object_id && v.subobjid == 0
+            })
+            .and_then(|v| v.imageval.as_deref())
+    }
+
+    /// Returns the physical names of the data files of the database, ordered by file id.
+    pub(crate) fn data_file_names(&self) -> Vec<String> {
+        let mut data_files = self
+            .sysprufiles
+            .iter()
+            .filter(|f| f.filetype == ROWS_FILETYPE)
+            .collect::<Vec<_>>();
+        data_files.sort_by_key(|f| f.fileid);
+
+        data_files
+            .into_iter()
+            .map(|f| f.pname.clone().unwrap_or_default())
+            .collect()
+    }
+
+    /// Returns the `sysrscols` row of the given column in the table's first partition, which
+    /// locates the column within the records.
+    fn sysrscol(&self, table_id: i32, colid: i32) -> Option<&Sysrscol> {
+        let rowset = self
+            .sysrow_sets
+            .iter()
+            .filter(|sysrow| sysrow.idmajor == table_id && sysrow.idminor <= 1)
+            .min_by_key(|sysrow| sysrow.numpart)?;
+
+        self.sysrscols
+            .iter()
+            .find(|c| c.rsid == rowset.rowsetid && c.rscolid == colid)
+    }
+
+    /// Returns the columns of the given table, resolving them on the first call only.
+    fn resolved_columns(&self, table_id: i32) -> Rc<[ResolvedColumn]> {
+        if let Some(columns) = self.resolved_columns.borrow().get(&table_id) {
+            return columns.clone();
+        }
+
+        let columns = self
+            .syscolpars
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| c.number == 0 && c.id == table_id && c.name.is_some())
+            .map(|(syscolpar, c)| {
+                let sysrscol = self.sysrscol(table_id, c.colid);
+
+                ResolvedColumn {
+                    syscolpar,
+                    sysscalartype: self
+                        .sysscalartypes
+                        .iter()
+                        .position(|st| st.xtype == c.xtype)
+                        .expect("Should have type for column"),
+                    leaf_offset: sysrscol.and_then(Sysrscol::leaf_offset),
+                    leaf_bit_position: sysrscol.and_then(Sysrscol::leaf_bit_position),
+                }
+            })
+            .collect::<Rc<[_]>>();
+
+        self.resolved_columns
+            .borrow_mut()
+            .insert(table_id, columns.clone());
+
+        columns
+    }
+
+    /// Returns the number of rows of the given table as recorded in the metadata of its
+    /// partitions, without resolving the columns of the table.
+    pub(crate) fn row_count(&self, table_name: &str) -> Option<i64> {
+        let table = self.objects_dollar().find(|o| o.name == table_name)?;
+
+        Some(
+            self.sysrow_sets
+                .iter()
+                .filter(|sysrow| sysrow.idmajor == table.id && sysrow.idminor <= 1)
+                .map(|sysrow| sysrow.rcrows)
+                .sum(),
+        )
+    }
+
     pub(crate) fn tables(&self) -> Vec<String> {
         self.objects_dollar()
             .filter(|o| o.r#type == "U")
@@ -138,24 +309,27 @@ impl BaseTableData {
                 objects_dollar: table,
                 sysalloc_units: &self.sysalloc_units,
                 sysrow_sets: &self.sysrow_sets,
+                partition: None,
                 columns: self
-                    .syscolpars
+                    .resolved_columns(table.id)
                     .iter()
-                    .filter(|c| c.number == 0 && c.id == table.id && c.name.is_some())
-                    .map(|c| {
-                        let r#type = self
-                            .sysscalartypes
-                            .iter()
-                            .find(|st| st.xtype == c.xtype)
-                            .map(|st| &st.name)
-                            .expect("Should have type for column");
+                    .map(|resolved| {
+                        let c = &self.syscolpars[resolved.syscolpar];
 
                         Column {
-                            name: c.name.as_ref().unwrap(),
-                            r#type,
+                            name: normalize_name(c.name.as_ref().unwrap()),
+                            r#type: &self.sysscalartypes[resolved.sysscalartype].name,
+                            xtype: c.xtype as u8,
                             max_length: c.length,
                             precision: c.prec as u8,
                             scale: c.scale as u8,
+                            default_expr: self.object_definition(c.dflt),
+                            check_expr: self.object_definition(c.chk),
+                            identity: c.identity(),
+                            computed: c.status & Syscolpar::COMPUTED_STATUS != 0,
+                            persisted: c.status & Syscolpar::PERSISTED_STATUS != 0,
+                            leaf_offset: resolved.leaf_offset,
+                            leaf_bit_position: resolved.leaf_bit_position,
                         }
                     })
                     .collect(),
@@ -168,11 +342,30 @@ pub(crate) struct Table<'a> {
     objects_dollar: &'a Sysschobj,
     sysalloc_units: &'a Vec<SysallocUnit>,
     sysrow_sets: &'a Vec<SysrowSet>,
+    /// Restricts the table to the partition with the given index, see [`Table::with_partition`].
+    partition: Option<usize>,
     pub(crate) columns: Vec<Column<'a>>,
 }
 
 impl<'a> Table<'a> {
-    pub(crate) fn page_pointers(&self) -> Vec<PagePointer> {
+    pub(crate) fn name(&self) -> &'a str {
+        &self.objects_dollar.name
+    }
+
+    /// Restricts the table to the partition with the given 0-based index in partition number
+    /// order, so that only its pages and rows are read. Indexes beyond the last partition result
+    /// in an empty table.
+    pub(crate) fn with_partition(self, partition: usize) -> Self {
+        Self {
+            partition: Some(partition),
+            ..self
+        }
+    }
+
+    /// Returns the partitions of the heap or the clustered index, whose records contain all
+    /// stored columns. Nonclustered indexes only contain their key and included columns and
+    /// are not read.
+    fn partitions(&self) -> Vec<&'a SysrowSet> {
         let mut partitions = self
             .sysrow_sets
             .iter()
@@ -181,20 +374,101 @@ impl<'a> Table<'a> {
 
         partitions.sort_by_key(|p| p.numpart);
 
-        let mut page_pointers = Vec::new();
+        match self.partition {
+            Some(partition) => partitions.into_iter().skip(partition).take(1).collect(),
+            None => partitions,
+        }
+    }
 
-        for partition in partitions {
-            if let Some(unit) = self
-                .sysalloc_units
-                .iter()
-                .find(|unit| unit.ownerid == partition.rowsetid && unit.r#type == 1)
-            {
-                let page_pointer = PagePointer::try_from(&unit.pgfirst[..]).unwrap();
-                page_pointers.push(page_pointer);
-            }
+    fn in_row_allocation_units(&self) -> Vec<&'a SysallocUnit> {
+        self.partitions()
+            .into_iter()
+            .filter_map(|partition| {
+                self.sysalloc_units
+                    .iter()
+                    .find(|unit| unit.ownerid == partition.rowsetid && unit.r#type == 1)
+            })
+            .collect()
+    }
+
+    /// Returns `true` if the table has no clustered index, i.e. its data pages are not linked in
+    /// key order.
+    pub(crate) fn is_heap(&self) -> bool {
+        // The rowset of a heap has the index id 0, the one of a clustered index 1
+        self.partitions()
+            .first()
+            .is_some_and(|partition| partition.idminor == 0)
+    }
+
+    /// Returns the table with the values of all columns read in storage order instead of at
+    /// their leaf offsets, which only apply to uncompressed records.
+    #[cfg(feature = "compression")]
+    pub(crate) fn without_leaf_offsets(mut self) -> Self {
+        for column in &mut self.columns {
+            column.leaf_offset = None;
+            column.leaf_bit_position = None;
         }
+        self
+    }
 
-        page_pointers
+    /// Returns the columns whose values are stored in the records of the table, in storage order.
+    pub(crate) fn stored_columns(&self) -> impl Iterator<Item = &Column<'a>> {
+        self.columns.iter().filter(|column| column.is_stored())
+    }
+
+    /// Returns the page to start reading each in-row allocation unit at. Clustered tables start
+    /// at the root page of the index, which `PageStream` resolves to the leftmost leaf page,
+    /// because the first page is not necessarily the first one in key order. Heaps and units
+    /// without a root page start at their first page.
+    pub(crate) fn page_pointers(&self) -> Vec<PagePointer> {
+        let heap = self.is_heap();
+
+        self.in_row_allocation_units()
+            .into_iter()
+            .map(|unit| {
+                let first_page = PagePointer::try_from(&unit.pgfirst[..]).unwrap();
+                match PagePointer::try_from(&unit.pgroot[..]) {
+                    Ok(root_page)
+                        if root_page.page_id > 0 && (!heap || first_page.page_id == 0) =>
+                    {
+                        root_page
+                    }
+                    _ => first_page,
+                }
+            })
+            .collect()
+    }
+
+    pub(crate) fn iam_page_pointers(&self) -> Vec<PagePointer> {
+        self.in_row_allocation_units()
+            .into_iter()
+            .map(|unit| PagePointer::try_from(&unit.pgfirstiam[..]).unwrap())
+            .filter(|page_pointer| page_pointer.page_id > 0)
+            .collect()
+    }
+
+    pub(crate) fn allocation_unit_ids(&self) -> Vec<i64> {
+        self.in_row_allocation_units()
+            .into_iter()
+            .map(|unit| unit.auid)
+            .collect()
+    }
+
+    /// Returns the name of the data compression of the first compressed partition, if any.
+    pub(crate) fn compression(&self) -> Option<&'static str> {
+        self.partitions()
+            .into_iter()
+            .find_map(|partition| match partition.cmprlevel {
+                0 => None,
+                1 => Some("ROW"),
+                2 => Some("PAGE"),
+                _ => Some("columnstore"),
+            })
+    }
+
+    /// Returns the number of rows as recorded in the metadata of the table's partitions.
+    pub(crate) fn row_count(&self) -> i64 {
+        self.partitions().into_iter().map(|p| p.rcrows).sum()
     }
 }
 
@@ -202,9 +476,107 @@ impl<'a> Table<'a> {
 pub(crate) struct Column<'a> {
     pub(crate) name: &'a str,
     pub(crate) r#type: &'a str,
+    /// The id of the system type the type of the column is based on.
+    pub(crate) xtype: u8,
     pub(crate) max_length: i16,
     pub(crate) precision: u8,
     pub(crate) scale: u8,
+    pub(crate) default_expr: Option<&'a str>,
+    pub(crate) check_expr: Option<&'a str>,
+    pub(crate) identity: Option<(i64, i64)>,
+    pub(crate) computed: bool,
+    pub(crate) persisted: bool,
+    /// The offset of fixed length values within the records, counted from the start of the
+    /// record. `None` for variable length columns or if `sysrscols` is not available.
+    pub(crate) leaf_offset: Option<u16>,
+    /// The position of the bit within the byte at `leaf_offset` for bit columns.
+    pub(crate) leaf_bit_position: Option<u8>,
+}
+
+impl<'a> Column<'a> {
+    /// Returns the name of the system type of the column, resolved via the system type id so
+    /// that aliases (e.g. `sysname` or user-defined types) map to their base type. Falls back
+    /// to the type name for unknown system type ids.
+    pub(crate) fn base_type(&self) -> &'a str {
+        system_type_name(self.xtype).unwrap_or(self.r#type)
+    }
+
+    /// Returns the type of the column or `None` if values of the type cannot be decoded.
+    pub(crate) fn column_type(&self) -> Option<ColumnType> {
+        ColumnType::new(
+            self.base_type(),
+            self.max_length,
+            self.precision,
+            self.scale,
+        )
+    }
+
+    /// Returns the maximum number of characters of string columns, which differs from the
+    /// maximum length in bytes for Unicode types. Returns `None` for other types and for
+    /// `(max)` columns.
+    pub(crate) fn max_characters(&self) -> Option<i16> {
+        if self.max_length < 0 {
+            return None;
+        }
+
+        match self.base_type() {
+            // Unicode types store two bytes per character
+            "nchar" | "nvarchar" | "sysname" => Some(self.max_length / 2),
+            "char" | "varchar" => Some(self.max_length),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the values of the column are stored in the records, which is not the
+    /// case for computed columns that are not persisted.
+    pub(crate) fn is_stored(&self) -> bool {
+        !self.computed || self.persisted
+    }
+}
+
+/// Returns the name of the system type with the given id (`system_type_id` of `sys.types`).
+fn system_type_name(xtype: u8) -> Option<&'static str> {
+    let name = match xtype {
+        34 => "image",
+        35 => "text",
+        36 => "uniqueidentifier",
+        40 => "date",
+        41 => "time",
+        42 => "datetime2",
+        43 => "datetimeoffset",
+        48 => "tinyint",
+        52 => "smallint",
+        56 => "int",
+        58 => "smalldatetime",
+        59 => "real",
+        60 => "money",
+        61 => "datetime",
+        62 => "float",
+        98 => "sql_variant",
+        99 => "ntext",
+        104 => "bit",
+        106 => "decimal",
+        108 => "numeric",
+        122 => "smallmoney",
+        127 => "bigint",
+        165 => "varbinary",
+        167 => "varchar",
+        173 => "binary",
+        175 => "char",
+        189 => "timestamp",
+        231 => "nvarchar",
+        239 => "nchar",
+        241 => "xml",
+        _ => return None,
+    };
+
+    Some(name)
+}
+
+/// Strips trailing NUL characters, spaces and the `†` padding artifacts (the same artifacts
+/// removed from the database name of the boot page) from a name.
+fn normalize_name(name: &str) -> &str {
+    name.trim_end_matches(&['\0', ' ', '†'][..])
 }
 
 #[derive(Debug)]
@@ -269,7 +641,9 @@ struct SysrowSet {
     status: i32,
     fgidfs: i16,
     rcrows: i64,
-    // TODO cmprlevel: i8,
+    /// The data compression of the partition: 0 for none, 1 for ROW and 2 for PAGE
+    /// compression. SQL Server 2005 does not store it and does not support compression.
+    cmprlevel: i8,
     // TODO fillfact: i8,
     // TODO maxnullbit: i16,
     // TODO maxleaf: i32,
@@ -292,8 +666,13 @@ impl<'a> TryFrom<Record<'a>> for SysrowSet {
         let (numpart, record) = record.parse_i32()?;
         let (status, record) = record.parse_i32()?;
         let (fgidfs, record) = record.parse_i16()?;
-        let (rcrows, _record) = record.parse_i64()?;
-        // TODO let (cmprlevel, record) = record.parse_i8()?;
+        let (rcrows, record) = record.parse_i64()?;
+        let cmprlevel = if record.remaining_fixed_bytes().is_empty() {
+            0
+        } else {
+            let (cmprlevel, _record) = record.parse_i8()?;
+            cmprlevel
+        };
         // TODO let (fillfact, _record) = record.parse_i8()?;
 
         // TODO let maxnullbit = bytes.read_i16::<LittleEndian>().unwrap();
@@ -311,7 +690,7 @@ impl<'a> TryFrom<Record<'a>> for SysrowSet {
             status,
             fgidfs,
             rcrows,
-            // TODO cmprlevel,
+            cmprlevel,
             // TODO fillfact,
         })
     }
@@ -421,7 +800,7 @@ struct Syscolpar {
     xmlns: i32,
     dflt: i32,
     chk: i32,
-    // idtval: varbinary
+    idtval: Option<Vec<u8>>,
 }
 
 impl<'a> TryFrom<Record<'a>> for Syscolpar {
@@ -431,11 +810,13 @@ impl<'a> TryFrom<Record<'a>> for Syscolpar {
         let (id, record) = record.parse_i32()?;
         let (number, record) = record.parse_i16()?;
         let (colid, record) = record.parse_i32()?;
-        let (name, record) = if record.has_variable_length_columns() {
-            let (name, record) = record.parse_string()?;
-            (Some(name.unwrap()), record)
+        // name and idtval are the only variable length columns. Records without variable length
+        // data still have bits in the null bitmap for both of them.
+        let has_variable_length_columns = record.has_variable_length_columns();
+        let (name, record) = if has_variable_length_columns {
+            record.parse_string()?
         } else {
-            (None, record)
+            (None, record.skip_column())
         };
         let (xtype, record) = record.parse_i8()?;
         let (utype, record) = record.parse_i32()?;
@@ -447,7 +828,13 @@ impl<'a> TryFrom<Record<'a>> for Syscolpar {
         let (maxinrow, record) = record.parse_i16()?;
         let (xmlns, record) = record.parse_i32()?;
         let (dflt, record) = record.parse_i32()?;
-        let (chk, _record) = record.parse_i32()?;
+        let (chk, record) = record.parse_i32()?;
+        let idtval = if has_variable_length_columns {
+            let (idtval, _record) = record.parse_variables_bytes_opt()?;
+            idtval.map(<[u8]>::to_vec)
+        } else {
+            None
+        };
 
         Ok(Self {
             id,
@@ -465,14 +852,594 @@ impl<'a> TryFrom<Record<'a>> for Syscolpar {
             xmlns,
             dflt,
             chk,
+            idtval,
+        })
+    }
+}
+
+impl Syscolpar {
+    const IDENTITY_STATUS: i32 = 0x4;
+    const COMPUTED_STATUS: i32 = 0x10;
+    const PERSISTED_STATUS: i32 = 0x200;
+
+    /// Returns the seed and the increment of an identity column.
+    ///
+    /// `idtval` starts with the seed followed by the increment, both stored with the byte
+    /// length of the column type. Identities of other types than integers are not supported.
+    fn identity(&self) -> Option<(i64, i64)> {
+        if self.status & Self::IDENTITY_STATUS == 0 {
+            return None;
+        }
+
+        let mut idtval = self.idtval.as_deref()?;
+        let mut read_int = || match self.length {
+            1 => idtval.read_u8().ok().map(i64::from),
+            2 => idtval.read_i16::<LittleEndian>().ok().map(i64::from),
+            4 => idtval.read_i32::<LittleEndian>().ok().map(i64::from),
+            8 => idtval.read_i64::<LittleEndian>().ok(),
+            _ => None,
+        };
+
+        let seed = read_int()?;
+        let increment = read_int()?;
+
+        Some((seed, increment))
+    }
+}
+
+#[derive(Debug)]
+struct Sysobjvalue {
+    valclass: u8,
+    objid: i32,
+    subobjid: i32,
+    // value: sql_variant
+    imageval: Option<String>,
+}
+
+impl<'a> TryFrom<Record<'a>> for Sysobjvalue {
+    type Error = &'static str;
+
+    fn try_from(record: Record<'a>) -> Result<Self, Self::Error> {
+        let (valclass, record) = record.parse_i8()?;
+        let (objid, record) = record.parse_i32()?;
+        let (subobjid, record) = record.parse_i32()?;
+        let (_valnum, record) = record.parse_i32()?;
+        let (_value, record) = record.parse_variables_bytes_opt()?;
+        // Definitions are stored as UTF-16 text in the binary column
+        let (imageval, _record) = record.parse_string()?;
+
+        Ok(Self {
+            valclass: valclass as u8,
+            objid,
+            subobjid,
+            imageval,
         })
     }
 }
 
+#[derive(Debug)]
+struct Sysprufile {
+    fileid: i32,
+    filetype: u8,
+    pname: Option<String>,
+    // TODO createlsn: binary(10),
+    // TODO droplsn: binary(10),
+    // TODO fileguid: uniqueidentifier,
+    // ...
+}
+
+impl<'a> TryFrom<Record<'a>> for Sysprufile {
+    type Error = &'static str;
+
+    fn try_from(record: Record<'a>) -> Result<Self, Self::Error> {
+        let (fileid, record) = record.parse_i32()?;
+        let (_grpid, record) = record.parse_i32()?;
+        let (_status, record) = record.parse_i32()?;
+        let (filetype, record) = record.parse_i8()?;
+        let (_filestate, record) = record.parse_i8()?;
+        let (_size, record) = record.parse_i32()?;
+        let (_maxsize, record) = record.parse_i32()?;
+        let (_growth, record) = record.parse_i32()?;
+        let (_lname, record) = record.parse_string()?;
+        let (pname, _record) = record.parse_string()?;
+
+        Ok(Self {
+            fileid,
+            filetype: filetype as u8,
+            pname,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct Sysrscol {
+    rsid: i64,
+    rscolid: i32,
+    offset: i32,
+    bitpos: i16,
+    // ...
+}
+
+impl<'a> TryFrom<Record<'a>> for Sysrscol {
+    type Error = &'static str;
+
+    fn try_from(record: Record<'a>) -> Result<Self, Self::Error> {
+        let (rsid, record) = record.parse_i64()?;
+        let (rscolid, record) = record.parse_i32()?;
+        let (_hbcolid, record) = record.parse_i32()?;
+        let (_rcmodified, record) = record.parse_i64()?;
+        let (_ti, record) = record.parse_i32()?;
+        let (_cid, record) = record.parse_i32()?;
+        let (_ordkey, record) = record.parse_i16()?;
+        let (_maxinrowlen, record) = record.parse_i16()?;
+        let (_status, record) = record.parse_i32()?;
+        let (offset, record) = record.parse_i32()?;
+        let (_nullbit, record) = record.parse_i32()?;
+        let (bitpos, _record) = record.parse_i16()?;
+
+        Ok(Self {
+            rsid,
+            rscolid,
+            offset,
+            bitpos,
+        })
+    }
+}
+
+impl Sysrscol {
+    /// Returns the offset of the column within the record. The lower two bytes of `offset` are
+    /// negative for variable length columns, where they denote the index of the column in the
+    /// variable length column offset array.
+    fn leaf_offset(&self) -> Option<u16> {
+        let leaf_offset = (self.offset & 0xFFFF) as i16;
+        if leaf_offset >= 4 {
+            Some(leaf_offset as u16)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the position of the bit within the byte at the leaf offset, which is only
+    /// meaningful for bit columns.
+    fn leaf_bit_position(&self) -> Option<u8> {
+        if (0..8).contains(&self.bitpos) {
+            Some(self.bitpos as u8)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+impl BaseTableData {
+    /// The object id of the table created by [`BaseTableData::with_table`].
+    const TABLE_ID: i32 = 1000;
+
+    /// Creates the system table data of a database with a single user table whose columns are
+    /// given as name, type name, length and `syscolpars` status.
+    pub(crate) fn with_table(table_name: &str, columns: Vec<(&str, &str, i16, i32)>) -> Self {
+        let mut sysscalartypes: Vec<Sysscalartype> = Vec::new();
+        let mut syscolpars = Vec::new();
+
+        for (colid, (name, type_name, length, status)) in columns.into_iter().enumerate() {
+            let xtype = match sysscalartypes.iter().find(|st| st.name == type_name) {
+                Some(scalar_type) => scalar_type.xtype,
+                None => {
+                    let xtype = sysscalartypes.len() as i8 + 1;
+                    sysscalartypes.push(Sysscalartype {
+                        id: xtype as i32,
+                        schid: 4,
+                        name: type_name.to_string(),
+                        xtype,
+                        length,
+                        prec: 0,
+                        scale: 0,
+                        collationid: 0,
+                        status: 0,
+                    });
+                    xtype
+                }
+            };
+
+            syscolpars.push(Syscolpar {
+                id: Self::TABLE_ID,
+                number: 0,
+                colid: colid as i32 + 1,
+                name: Some(name.to_string()),
+                xtype,
+                utype: xtype as i32,
+                length,
+                prec: 0,
+                scale: 0,
+                collationid: 0,
+                status,
+                maxinrow: length,
+                xmlns: 0,
+                dflt: 0,
+                chk: 0,
+                idtval: None,
+            });
+        }
+
+        Self {
+            sysalloc_units: Vec::new(),
+            sysrow_sets: Vec::new(),
+            sysschobjs: vec![Sysschobj {
+                id: Self::TABLE_ID,
+                name: table_name.to_string(),
+                nsid: 1,
+                nsclass: 0,
+                status: 0,
+                r#type: String::from("U"),
+                pid: 0,
+                pclass: 1,
+            }],
+            sysscalartypes,
+            syscolpars,
+            sysobjvalues: Vec::new(),
+            sysprufiles: Vec::new(),
+            sysrscols: Vec::new(),
+            resolved_columns: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Sets the compression level of the partitions of the table, adding a heap partition if
+    /// there is none yet.
+    pub(crate) fn with_compression(mut self, cmprlevel: i8) -> Self {
+        if !self.sysrow_sets.is_empty() {
+            for sysrow_set in &mut self.sysrow_sets {
+                sysrow_set.cmprlevel = cmprlevel;
+            }
+            return self;
+        }
+
+        self.sysrow_sets.push(SysrowSet {
+            rowsetid: 1,
+            ownertype: 1,
+            idmajor: Self::TABLE_ID,
+            idminor: 0,
+            numpart: 1,
+            status: 0,
+            fgidfs: 0,
+            rcrows: 0,
+            cmprlevel,
+        });
+        self
+    }
+
+    /// Adds a partition of a clustered index with the given first and root page to the table.
+    pub(crate) fn with_clustered_index(mut self, first_page_id: u8, root_page_id: u8) -> Self {
+        self.sysrow_sets.push(SysrowSet {
+            rowsetid: 1,
+            ownertype: 1,
+            idmajor: Self::TABLE_ID,
+            idminor: 1,
+            numpart: 1,
+            status: 0,
+            fgidfs: 0,
+            rcrows: 0,
+            cmprlevel: 0,
+        });
+        self.sysalloc_units.push(SysallocUnit {
+            auid: 11,
+            r#type: 1,
+            ownerid: 1,
+            status: 0,
+            fgid: 1,
+            pgfirst: vec![first_page_id, 0, 0, 0, 1, 0],
+            pgroot: vec![root_page_id, 0, 0, 0, 1, 0],
+            pgfirstiam: vec![0; 6],
+            pcused: 0,
+            pcdata: 0,
+            pcreserved: 0,
+        });
+        self
+    }
+
+    /// Sets the precision and scale of the given column, e.g. of a `decimal` column.
+    pub(crate) fn with_precision(mut self, column_name: &str, precision: i8, scale: i8) -> Self {
+        for syscolpar in self
+            .syscolpars
+            .iter_mut()
+            .filter(|syscolpar| syscolpar.name.as_deref() == Some(column_name))
+        {
+            syscolpar.prec = precision;
+            syscolpar.scale = scale;
+        }
+        self
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use super::{normalize_name, BaseTableData, SysallocUnit, Syscolpar, SysrowSet};
+    use crate::pages::{PagePointer, Record};
     use crate::{Error, MdfDatabase};
     use pretty_assertions::assert_eq;
+    use std::convert::TryFrom;
+
+    fn syscolpar(length: i16, status: i32, idtval: Vec<u8>) -> Syscolpar {
+        Syscolpar {
+            id: 1,
+            number: 0,
+            colid: 1,
+            name: Some(String::from("ID")),
+            xtype: 56,
+            utype: 56,
+            length,
+            prec: 10,
+            scale: 0,
+            collationid: 0,
+            status,
+            maxinrow: length,
+            xmlns: 0,
+            dflt: 0,
+            chk: 0,
+            idtval: Some(idtval),
+        }
+    }
+
+    #[test]
+    fn normalize_padded_names() {
+        assert_eq!(normalize_name("AddressID"), "AddressID");
+        assert_eq!(normalize_name("AddressID\0\0"), "AddressID");
+        assert_eq!(normalize_name("City  "), "City");
+        assert_eq!(normalize_name("rowguid††"), "rowguid");
+        assert_eq!(normalize_name("Postal Code"), "Postal Code");
+    }
+
+    #[test]
+    fn skip_non_persisted_computed_columns_for_storage() {
+        let base_table_data = BaseTableData::with_table(
+            "Orders",
+            vec![
+                ("OrderID", "int", 4, 0),
+                ("OrderNumber", "nvarchar", 50, Syscolpar::COMPUTED_STATUS),
+                (
+                    "Total",
+                    "int",
+                    4,
+                    Syscolpar::COMPUTED_STATUS | Syscolpar::PERSISTED_STATUS,
+                ),
+                ("Quantity", "int", 4, 0),
+            ],
+        );
+        let table = base_table_data.table("Orders").unwrap();
+
+        assert_eq!(table.columns.len(), 4);
+        assert_eq!(
+            table.stored_columns().map(|c| c.name).collect::<Vec<_>>(),
+            vec!["OrderID", "Total", "Quantity"]
+        );
+    }
+
+    #[test]
+    fn resolve_columns_on_first_access() {
+        let base_table_data = BaseTableData::with_table("Orders", vec![("OrderID", "int", 4, 0)]);
+        assert!(base_table_data.resolved_columns.borrow().is_empty());
+
+        for _ in 0..2 {
+            let table = base_table_data.table("Orders").unwrap();
+            assert_eq!(table.columns.len(), 1);
+            assert_eq!(base_table_data.resolved_columns.borrow().len(), 1);
+        }
+    }
+
+    #[test]
+    fn count_rows_without_resolving_columns() {
+        let base_table_data = BaseTableData::with_table("Orders", vec![("OrderID", "int", 4, 0)]);
+
+        assert_eq!(base_table_data.row_count("Orders"), Some(0));
+        assert_eq!(base_table_data.row_count("Customers"), None);
+        assert!(base_table_data.resolved_columns.borrow().is_empty());
+    }
+
+    #[test]
+    fn read_clustered_index_instead_of_nonclustered_index() {
+        let mut base_table_data = BaseTableData::with_table(
+            "Orders",
+            vec![("OrderID", "int", 4, 0), ("Quantity", "int", 4, 0)],
+        );
+        // The clustered index has the index id 1, the nonclustered index the index id 2
+        for (rowsetid, idminor, page_id) in [(1i64, 1i32, 100u8), (2, 2, 200)].iter() {
+            base_table_data.sysrow_sets.push(SysrowSet {
+                rowsetid: *rowsetid,
+                ownertype: 1,
+                idmajor: 1000,
+                idminor: *idminor,
+                numpart: 1,
+                status: 0,
+                fgidfs: 0,
+                rcrows: 0,
+                cmprlevel: 0,
+            });
+            base_table_data.sysalloc_units.push(SysallocUnit {
+                auid: 10 + rowsetid,
+                r#type: 1,
+                ownerid: *rowsetid,
+                status: 0,
+                fgid: 1,
+                pgfirst: vec![*page_id, 0, 0, 0, 1, 0],
+                pgroot: vec![0; 6],
+                pgfirstiam: vec![0; 6],
+                pcused: 0,
+                pcdata: 0,
+                pcreserved: 0,
+            });
+        }
+
+        let table = base_table_data.table("Orders").unwrap();
+
+        assert_eq!(
+            table.page_pointers(),
+            vec![PagePointer {
+                page_id: 100,
+                file_id: 1
+            }]
+        );
+        assert!(!table.is_heap());
+        assert_eq!(table.stored_columns().count(), 2);
+    }
+
+    #[test]
+    fn start_clustered_table_at_root_page() {
+        let base_table_data = BaseTableData::with_table("Orders", vec![("OrderID", "int", 4, 0)])
+            .with_clustered_index(3, 2);
+        let table = base_table_data.table("Orders").unwrap();
+
+        assert_eq!(
+            table.page_pointers(),
+            vec![PagePointer {
+                page_id: 2,
+                file_id: 1
+            }]
+        );
+    }
+
+    #[test]
+    fn read_compression_level_of_sysrowset() {
+        let mut bytes = vec![0x10, 0, 39, 0];
+        bytes.extend_from_slice(&72057594038321152i64.to_le_bytes()); // rowsetid
+        bytes.push(1); // ownertype
+        bytes.extend_from_slice(&1000i32.to_le_bytes()); // idmajor
+        bytes.extend_from_slice(&1i32.to_le_bytes()); // idminor
+        bytes.extend_from_slice(&1i32.to_le_bytes()); // numpart
+        bytes.extend_from_slice(&0i32.to_le_bytes()); // status
+        bytes.extend_from_slice(&1i16.to_le_bytes()); // fgidfs
+        bytes.extend_from_slice(&42i64.to_le_bytes()); // rcrows
+                                                       // SQL Server 2005 ends the fixed length data after rcrows
+        let mut bytes_2005 = bytes.clone();
+        bytes_2005.extend_from_slice(&[1, 0, 0]);
+        bytes[2] = 41;
+        bytes.extend_from_slice(&[2, 0]); // cmprlevel, fillfact
+        bytes.extend_from_slice(&[1, 0, 0]);
+
+        let sysrowset = SysrowSet::try_from(Record::try_from(&bytes[..]).unwrap()).unwrap();
+        let sysrowset_2005 =
+            SysrowSet::try_from(Record::try_from(&bytes_2005[..]).unwrap()).unwrap();
+
+        assert_eq!(sysrowset.rcrows, 42);
+        assert_eq!(sysrowset.cmprlevel, 2);
+        assert_eq!(sysrowset_2005.rcrows, 42);
+        assert_eq!(sysrowset_2005.cmprlevel, 0);
+    }
+
+    #[async_std::test]
+    async fn open_does_not_resolve_columns() -> Result<(), Error> {
+        let db = MdfDatabase::open("data/AWLT2005.mdf").await?;
+        assert!(db.base_table_data.resolved_columns.borrow().is_empty());
+
+        db.estimated_total_rows();
+        assert!(db.base_table_data.resolved_columns.borrow().is_empty());
+
+        db.columns("Address");
+        assert_eq!(db.base_table_data.resolved_columns.borrow().len(), 1);
+
+        Ok(())
+    }
+
+    /// Builds a syscolpars record of column 2 of object 5 with the given null bitmap and
+    /// variable length columns.
+    fn syscolpar_record(null_bitmap: [u8; 2], variable_columns: &[&[u8]]) -> Vec<u8> {
+        let status = if variable_columns.is_empty() {
+            0x10
+        } else {
+            0x30
+        };
+        let mut bytes = vec![status, 0, 45, 0];
+        bytes.extend_from_slice(&5i32.to_le_bytes()); // id
+        bytes.extend_from_slice(&0i16.to_le_bytes()); // number
+        bytes.extend_from_slice(&2i32.to_le_bytes()); // colid
+        bytes.push(56); // xtype
+        bytes.extend_from_slice(&56i32.to_le_bytes()); // utype
+        bytes.extend_from_slice(&4i16.to_le_bytes()); // length
+        bytes.extend_from_slice(&[10, 0]); // prec, scale
+        bytes.extend_from_slice(&[0; 8]); // collationid, status
+        bytes.extend_from_slice(&4i16.to_le_bytes()); // maxinrow
+        bytes.extend_from_slice(&[0; 12]); // xmlns, dflt, chk
+        bytes.extend_from_slice(&16u16.to_le_bytes());
+        bytes.extend_from_slice(&null_bitmap);
+
+        if !variable_columns.is_empty() {
+            bytes.extend_from_slice(&(variable_columns.len() as u16).to_le_bytes());
+            let mut end_offset = bytes.len() + 2 * variable_columns.len();
+            for column in variable_columns {
+                end_offset += column.len();
+                bytes.extend_from_slice(&(end_offset as u16).to_le_bytes());
+            }
+            for column in variable_columns {
+                bytes.extend_from_slice(column);
+            }
+        }
+
+        bytes
+    }
+
+    #[test]
+    fn read_name_from_first_variable_column_of_syscolpar() {
+        let name = "Quantity"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect::<Vec<_>>();
+        let bytes = syscolpar_record([0, 0], &[&name, &[1, 0, 0, 0, 1, 0, 0, 0]]);
+
+        let syscolpar = Syscolpar::try_from(Record::try_from(&bytes[..]).unwrap()).unwrap();
+
+        assert_eq!(syscolpar.name.as_deref(), Some("Quantity"));
+        assert_eq!(syscolpar.xtype, 56);
+        assert_eq!(syscolpar.idtval, Some(vec![1, 0, 0, 0, 1, 0, 0, 0]));
+    }
+
+    #[test]
+    fn read_syscolpar_without_name() {
+        // The name in the first variable column is NULL while idtval is set
+        let bytes = syscolpar_record([0b1000, 0], &[&[], &[1, 0, 0, 0, 1, 0, 0, 0]]);
+
+        let syscolpar = Syscolpar::try_from(Record::try_from(&bytes[..]).unwrap()).unwrap();
+
+        assert_eq!(syscolpar.name, None);
+        assert_eq!(syscolpar.idtval, Some(vec![1, 0, 0, 0, 1, 0, 0, 0]));
+    }
+
+    #[test]
+    fn read_syscolpar_without_variable_length_columns() {
+        let bytes = syscolpar_record([0b1000, 0b1000_0000], &[]);
+
+        let syscolpar = Syscolpar::try_from(Record::try_from(&bytes[..]).unwrap()).unwrap();
+
+        assert_eq!(syscolpar.name, None);
+        assert_eq!(syscolpar.xtype, 56);
+        assert_eq!(syscolpar.length, 4);
+        assert_eq!(syscolpar.chk, 0);
+        assert_eq!(syscolpar.idtval, None);
+    }
+
+    #[test]
+    fn identity_of_int_column() {
+        let column = syscolpar(4, 0x4, vec![1, 0, 0, 0, 1, 0, 0, 0, 42, 0, 0, 0]);
+
+        assert_eq!(column.identity(), Some((1, 1)));
+    }
+
+    #[test]
+    fn identity_of_bigint_column() {
+        let column = syscolpar(
+            8,
+            0x4,
+            vec![
+                232, 3, 0, 0, 0, 0, 0, 0, 254, 255, 255, 255, 255, 255, 255, 255,
+            ],
+        );
+
+        assert_eq!(column.identity(), Some((1000, -2)));
+    }
+
+    #[test]
+    fn no_identity_without_identity_status() {
+        let column = syscolpar(4, 0, vec![1, 0, 0, 0, 1, 0, 0, 0]);
+
+        assert_eq!(column.identity(), None);
+    }
 
     #[async_std::test]
     async fn test_read_boot_page_records() -> Result<(), Error> {
@@ -1,8 +1,10 @@
 use chrono::{TimeZone, Utc};
 use futures_lite::stream::StreamExt;
+use oxidized_mdf::options::{Options, ROW_INDEX_COLUMN};
 use oxidized_mdf::{error::Error, MdfDatabase, Value};
 use pretty_assertions::assert_eq;
 use rstest::rstest;
+use std::io::Write;
 
 #[rstest(
     file,
@@ -17,6 +19,73 @@ async fn database_name(file: &str, db_name: &str) -> Result<(), Error> {
     Ok(())
 }
 
+#[async_std::test]
+async fn into_stream_all_on_other_thread() {
+    let db = MdfDatabase::open("data/AWLT2005.mdf").await.unwrap();
+    let tables = db.into_stream_all().await.unwrap();
+
+    let row_counts = std::thread::spawn(move || {
+        tables
+            .map(|(table_name, rows)| (table_name, rows.len()))
+            .collect::<Vec<_>>()
+    })
+    .join()
+    .unwrap();
+
+    assert_eq!(row_counts.len(), 12);
+    assert!(row_counts.contains(&(String::from("Address"), 450)));
+}
+
+#[rstest(partition, expected_rows, case(0, 450), case(1, 0))]
+#[async_std::test]
+async fn rows_partition(partition: usize, expected_rows: usize) {
+    let mut db = MdfDatabase::open("data/AWLT2005.mdf").await.unwrap();
+    let rows = db
+        .rows_partition("Address", partition)
+        .unwrap()
+        .collect::<Vec<_>>()
+        .await;
+
+    assert_eq!(rows.len(), expected_rows);
+}
+
+#[async_std::test]
+async fn open_truncated_file_in_lenient_mode() -> Result<(), Error> {
+    let mut bytes = std::fs::read("data/AWLT2005.mdf")?;
+    bytes.extend_from_slice(&[0u8; 100]);
+    let mut file = tempfile::NamedTempFile::new()?;
+    file.write_all(&bytes)?;
+
+    let db = MdfDatabase::open(file.path()).await?;
+    assert_eq!(db.database_name(), "AdventureWorksLT");
+
+    Ok(())
+}
+
+#[rstest(
+    expected_name,
+    matches,
+    case("AdventureWorksLT", true),
+    case("spg_verein_TST", false)
+)]
+#[async_std::test]
+async fn open_expecting(expected_name: &str, matches: bool) {
+    let result = MdfDatabase::open_expecting("data/AWLT2005.mdf", expected_name).await;
+
+    match result {
+        Ok(db) => {
+            assert!(matches);
+            assert_eq!(db.database_name(), "AdventureWorksLT");
+        }
+        Err(Error::UnexpectedDatabase { found, expected }) => {
+            assert!(!matches);
+            assert_eq!(found, "AdventureWorksLT");
+            assert_eq!(expected, expected_name);
+        }
+        Err(err) => panic!("Unexpected error: {}", err),
+    }
+}
+
 #[rstest(
     file,
     table_names,
@@ -35,6 +104,62 @@ async fn tables(file: &str, table_names: Vec<&str>) -> Result<(), Error> {
     Ok(())
 }
 
+#[rstest(
+    file,
+    pattern,
+    count,
+    case("spg_verein_TST.mdf", "tbl_", 101),
+    case("spg_verein_TST.mdf", "tbl_H_*", 14),
+    case("AWLT2005.mdf", "Product*", 5),
+    case("AWLT2005.mdf", "*Address", 2)
+)]
+#[async_std::test]
+async fn table_names_matching(file: &str, pattern: &str, count: usize) -> Result<(), Error> {
+    let db = MdfDatabase::open(format!("data/{}", file)).await?;
+
+    assert_eq!(db.table_names_matching(pattern).len(), count);
+
+    Ok(())
+}
+
+#[rstest(file, case("AWLT2005.mdf"), case("spg_verein_TST.mdf"))]
+#[async_std::test]
+async fn file_count(file: &str) -> Result<(), Error> {
+    let db = MdfDatabase::open(format!("data/{}", file)).await?;
+
+    assert_eq!(db.file_count(), 1);
+
+    Ok(())
+}
+
+#[rstest(file, version, case("AWLT2005.mdf", "SQL Server 2005"))]
+#[async_std::test]
+async fn sql_server_version(file: &str, version: &str) -> Result<(), Error> {
+    let db = MdfDatabase::open(format!("data/{}", file)).await?;
+
+    assert_eq!(db.sql_server_version().as_deref(), Some(version));
+
+    Ok(())
+}
+
+#[rstest(file, min_rows, max_rows, case("AWLT2005.mdf", 4000, 5000))]
+#[async_std::test]
+async fn estimated_total_rows(file: &str, min_rows: i64, max_rows: i64) -> Result<(), Error> {
+    let db = MdfDatabase::open(format!("data/{}", file)).await?;
+
+    let total_rows = db.estimated_total_rows();
+
+    assert!(
+        (min_rows..=max_rows).contains(&total_rows),
+        "{} rows are not within {}..={}",
+        total_rows,
+        min_rows,
+        max_rows
+    );
+
+    Ok(())
+}
+
 #[rstest(
     file,
     table_name,
@@ -54,6 +179,44 @@ async fn columns(file: &str, table_name: &str, column_names: Vec<&str>) -> Resul
     Ok(())
 }
 
+#[rstest(
+    file,
+    table_name,
+    column_names,
+    case("AWLT2005.mdf", "Address", vec!["AddressID", "AddressLine1", "AddressLine2", "City", "StateProvince", "CountryRegion", "PostalCode", "rowguid", "ModifiedDate"]),
+)]
+#[async_std::test]
+async fn column_names_in_schema_order(
+    file: &str,
+    table_name: &str,
+    column_names: Vec<&str>,
+) -> Result<(), Error> {
+    let db = MdfDatabase::open(format!("data/{}", file)).await?;
+
+    let columns = db.column_names(table_name).unwrap();
+
+    assert_eq!(columns, column_names);
+
+    Ok(())
+}
+
+#[rstest(
+    file,
+    table_name,
+    case("AWLT2005.mdf", "Address"),
+    case("spg_verein_TST.mdf", "tbl_PLZ")
+)]
+#[async_std::test]
+async fn column_names_without_padding(file: &str, table_name: &str) -> Result<(), Error> {
+    let db = MdfDatabase::open(format!("data/{}", file)).await?;
+
+    for column in db.column_names(table_name).unwrap() {
+        assert_eq!(column.trim_end_matches(&['\0', ' ', '†'][..]), column);
+    }
+
+    Ok(())
+}
+
 #[rstest(
     file,
     table_name,
@@ -82,8 +245,12 @@ async fn first_row(file: &str, table_name: &str, column: &str, value: &str) -> R
     count,
     case("AWLT2005.mdf", "Address", 450),
     case("spg_verein_TST.mdf", "tbl_Mitglied", 13),
-    // TODO: 3643 should be the correct number
-    case("spg_verein_TST.mdf", "tbl_Bankleitzahlen", 3549)
+    case("spg_verein_TST.mdf", "tbl_Bankleitzahlen", 3643),
+    case("AWLT2005.mdf", "ProductModel", 128),
+    case("AWLT2005.mdf", "CustomerAddress", 417),
+    // Tables whose rows fit on a single page
+    case("AWLT2005.mdf", "BuildVersion", 1),
+    case("AWLT2005.mdf", "ProductCategory", 41)
 )]
 #[async_std::test]
 async fn number_of_rows(file: &str, table_name: &str, count: usize) -> Result<(), Error> {
@@ -95,6 +262,24 @@ async fn number_of_rows(file: &str, table_name: &str, count: usize) -> Result<()
     Ok(())
 }
 
+#[rstest(
+    file,
+    table_name,
+    count,
+    case("AWLT2005.mdf", "Address", 450),
+    case("spg_verein_TST.mdf", "tbl_Mitglied", 13),
+    case("AWLT2005.mdf", "ProductModel", 128)
+)]
+#[async_std::test]
+async fn collect_rows(file: &str, table_name: &str, count: usize) -> Result<(), Error> {
+    let mut db = MdfDatabase::open(format!("data/{}", file)).await?;
+    let rows = db.collect_rows(table_name).await?;
+
+    assert_eq!(rows.len(), count);
+
+    Ok(())
+}
+
 #[rstest(
     file,
     table_name,
@@ -146,3 +331,512 @@ async fn rows(
 
     Ok(())
 }
+
+#[async_std::test]
+async fn error_log_with_nvarchar_max_column() -> Result<(), Error> {
+    let options = Options {
+        strict: true,
+        ..Options::default()
+    };
+    let mut db = MdfDatabase::open_with_options("data/AWLT2005.mdf", options).await?;
+
+    // Strict mode fails on values that cannot be decoded instead of skipping them
+    let rows = db.collect_rows("ErrorLog").await?;
+    for row in &rows {
+        assert!(matches!(row.value("ErrorMessage"), Some(Value::String(_))));
+    }
+
+    Ok(())
+}
+
+#[async_std::test]
+async fn product_model_rows_with_xml_column() -> Result<(), Error> {
+    let mut db = MdfDatabase::open("data/AWLT2005.mdf").await?;
+    let rows = db.collect_rows("ProductModel").await?;
+
+    assert_eq!(rows.len(), 128);
+    // Columns after a value that cannot be decoded are missing, rowguid and ModifiedDate
+    // follow CatalogDescription
+    for row in &rows {
+        assert!(matches!(row.value("rowguid"), Some(Value::Uuid(_))));
+        assert!(matches!(
+            row.value("ModifiedDate"),
+            Some(Value::DateTime(_))
+        ));
+        match row.value("CatalogDescription") {
+            Some(Value::Xml(bytes)) => assert!(bytes.starts_with(&[0xdf, 0xff])),
+            Some(Value::Null) => {}
+            value => panic!("Unexpected CatalogDescription: {:?}", value),
+        }
+    }
+    assert!(rows
+        .iter()
+        .any(|row| matches!(row.value("CatalogDescription"), Some(Value::Xml(_)))));
+
+    Ok(())
+}
+
+#[async_std::test]
+async fn address_rowguids() -> Result<(), Error> {
+    let mut db = MdfDatabase::open("data/AWLT2005.mdf").await?;
+    let rows = db
+        .rows("Address")
+        .unwrap()
+        .take(5)
+        .collect::<Vec<_>>()
+        .await;
+
+    assert_eq!(rows.len(), 5);
+    for row in rows {
+        match row.value("rowguid") {
+            Some(Value::Uuid(uuid)) => assert!(!uuid.is_nil()),
+            value => panic!("Unexpected rowguid: {:?}", value),
+        }
+    }
+
+    Ok(())
+}
+
+#[async_std::test]
+async fn customer_address_rows() -> Result<(), Error> {
+    let mut db = MdfDatabase::open("data/AWLT2005.mdf").await?;
+    let rows = db.collect_rows("CustomerAddress").await?;
+
+    // The rows are read in the order of the clustered primary key (CustomerID, AddressID)
+    let first_row = &rows[0];
+    assert_eq!(first_row.value("CustomerID"), Some(&Value::Int(29485)));
+    assert_eq!(first_row.value("AddressID"), Some(&Value::Int(1086)));
+    assert_eq!(
+        first_row.value("AddressType"),
+        Some(&Value::String(String::from("Main Office")))
+    );
+
+    for row in rows {
+        assert!(matches!(row.value("CustomerID"), Some(Value::Int(_))));
+        assert!(matches!(row.value("AddressID"), Some(Value::Int(_))));
+        assert!(matches!(row.value("AddressType"), Some(Value::String(_))));
+        assert!(matches!(row.value("rowguid"), Some(Value::Uuid(uuid)) if !uuid.is_nil()));
+        assert!(matches!(
+            row.value("ModifiedDate"),
+            Some(Value::DateTime(_))
+        ));
+    }
+
+    Ok(())
+}
+
+#[async_std::test]
+async fn unsupported_columns() -> Result<(), Error> {
+    let db = MdfDatabase::open("data/AWLT2005.mdf").await?;
+
+    // Values of varbinary(max) and xml columns are decoded only if stored in the record
+    let unsupported_columns = db.unsupported_columns();
+    for (table_name, column_name, type_name) in [
+        ("Product", "ThumbNailPhoto", "varbinary"),
+        ("ProductModel", "CatalogDescription", "xml"),
+    ] {
+        assert!(unsupported_columns.contains(&(
+            table_name.to_string(),
+            column_name.to_string(),
+            type_name.to_string()
+        )));
+    }
+    // All other types of AdventureWorksLT are decoded
+    assert!(unsupported_columns
+        .iter()
+        .all(|(_, _, type_name)| ["nvarchar", "varbinary", "xml"].contains(&type_name.as_str())));
+
+    Ok(())
+}
+
+#[async_std::test]
+async fn io_stats_of_repeated_scan() -> Result<(), Error> {
+    let mut db = MdfDatabase::open("data/AWLT2005.mdf").await?;
+
+    db.collect_rows("Address").await?;
+    let first_scan = db.io_stats();
+    db.collect_rows("Address").await?;
+    let second_scan = db.io_stats();
+
+    assert_eq!(second_scan.pages_read, first_scan.pages_read);
+    assert!(second_scan.cache_hits > first_scan.cache_hits);
+
+    Ok(())
+}
+
+#[async_std::test]
+async fn rows_tuples_in_column_order() -> Result<(), Error> {
+    let mut db = MdfDatabase::open("data/AWLT2005.mdf").await?;
+    let column_names = db.column_names("Address").unwrap();
+
+    let tuples = db
+        .rows_tuples("Address")
+        .unwrap()
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
+    let rows = db.collect_rows("Address").await?;
+
+    assert_eq!(tuples.len(), rows.len());
+    for (values, row) in tuples.iter().zip(rows.iter()) {
+        for (column_name, value) in column_names.iter().zip(values.iter()) {
+            assert_eq!(row.value(column_name), value.as_ref());
+        }
+    }
+
+    Ok(())
+}
+
+#[async_std::test]
+async fn clustered_table_rows_contain_all_columns() -> Result<(), Error> {
+    let mut db = MdfDatabase::open("data/AWLT2005.mdf").await?;
+    let mut column_names = db.column_names("Address").unwrap();
+    column_names.sort();
+
+    let row = db.rows("Address").unwrap().next().await.unwrap();
+    let row_column_names = row
+        .values()
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect::<Vec<_>>();
+
+    assert!(row_column_names.contains(&String::from("AddressID")));
+    assert_eq!(row_column_names, column_names);
+
+    Ok(())
+}
+
+#[async_std::test]
+async fn build_version() -> Result<(), Error> {
+    let mut db = MdfDatabase::open("data/AWLT2005.mdf").await?;
+    let rows = db.rows("BuildVersion").unwrap().collect::<Vec<_>>().await;
+
+    assert_eq!(rows.len(), 1);
+    let row = &rows[0];
+    assert_eq!(row.value("SystemInformationID"), Some(&Value::TinyInt(1)));
+    assert!(
+        matches!(row.value("Database Version"), Some(Value::String(version)) if version.starts_with("9."))
+    );
+    assert!(matches!(row.value("VersionDate"), Some(Value::DateTime(_))));
+    assert!(matches!(
+        row.value("ModifiedDate"),
+        Some(Value::DateTime(_))
+    ));
+
+    Ok(())
+}
+
+#[rstest(
+    file,
+    table_name,
+    key_column,
+    case("AWLT2005.mdf", "Address", "AddressID"),
+    case("AWLT2005.mdf", "Customer", "CustomerID"),
+    case("AWLT2005.mdf", "Product", "ProductID")
+)]
+#[async_std::test]
+async fn clustered_table_rows_in_key_order(
+    file: &str,
+    table_name: &str,
+    key_column: &str,
+) -> Result<(), Error> {
+    let mut db = MdfDatabase::open(format!("data/{}", file)).await?;
+    let keys = db
+        .rows(table_name)
+        .unwrap()
+        .map(|row| match row.value(key_column) {
+            Some(Value::Int(key)) => *key,
+            value => panic!("Unexpected key {:?}", value),
+        })
+        .collect::<Vec<_>>()
+        .await;
+
+    assert!(!keys.is_empty());
+    assert!(keys.windows(2).all(|keys| keys[0] < keys[1]));
+
+    Ok(())
+}
+
+#[rstest(file, table_name, case("AWLT2005.mdf", "Address"))]
+#[async_std::test]
+async fn row_index(file: &str, table_name: &str) -> Result<(), Error> {
+    let options = Options {
+        row_index: true,
+        ..Options::default()
+    };
+    let mut db = MdfDatabase::open_with_options(format!("data/{}", file), options).await?;
+    let rows = db.rows(table_name).unwrap().collect::<Vec<_>>().await;
+
+    assert!(!rows.is_empty());
+    for (index, row) in rows.iter().enumerate() {
+        assert_eq!(
+            row.value(ROW_INDEX_COLUMN),
+            Some(&Value::BigInt(index as i64))
+        );
+    }
+
+    Ok(())
+}
+
+#[async_std::test]
+async fn strict_rows_of_product_with_varbinary_max_column() -> Result<(), Error> {
+    let options = Options {
+        strict: true,
+        ..Options::default()
+    };
+    let mut db = MdfDatabase::open_with_options("data/AWLT2005.mdf", options).await?;
+
+    // ThumbNailPhoto is a varbinary(max) column holding small GIF images in the records
+    let product_rows = db.collect_rows("Product").await?;
+    assert_eq!(product_rows.len(), 295);
+    for row in &product_rows {
+        match row.value("ThumbNailPhoto") {
+            Some(Value::Binary(bytes)) => assert!(!bytes.is_empty()),
+            Some(Value::Null) => {}
+            value => panic!("Unexpected ThumbNailPhoto: {:?}", value),
+        }
+    }
+    assert!(matches!(
+        product_rows[0].value("ThumbNailPhoto"),
+        Some(Value::Binary(bytes)) if bytes.starts_with(b"GIF")
+    ));
+
+    let address_rows = db.rows("Address").unwrap().collect::<Vec<_>>().await;
+    assert_eq!(address_rows.len(), 450);
+
+    Ok(())
+}
+
+#[rstest(file, table_name, case("AWLT2005.mdf", "Address"))]
+#[async_std::test]
+async fn rids_are_distinct(file: &str, table_name: &str) -> Result<(), Error> {
+    let mut db = MdfDatabase::open(format!("data/{}", file)).await?;
+    let mut rows = db.rows_with_rid(table_name).unwrap();
+
+    let mut rids = Vec::new();
+    while let Some(row) = rows.next().await {
+        let (rid, _row) = row?;
+        rids.push(rid);
+    }
+
+    let mut unique_rids = rids.clone();
+    unique_rids.sort_by_key(|rid| (rid.file_id, rid.page_id, rid.slot));
+    unique_rids.dedup();
+
+    assert_eq!(rids.len(), unique_rids.len());
+
+    Ok(())
+}
+
+#[rstest(
+    file,
+    table_name,
+    column,
+    default_expr,
+    case("AWLT2005.mdf", "Address", "ModifiedDate", Some("(getdate())")),
+    case("AWLT2005.mdf", "Address", "rowguid", Some("(newid())")),
+    case("AWLT2005.mdf", "Address", "City", None)
+)]
+#[async_std::test]
+async fn column_default_expr(
+    file: &str,
+    table_name: &str,
+    column: &str,
+    default_expr: Option<&str>,
+) -> Result<(), Error> {
+    let db = MdfDatabase::open(format!("data/{}", file)).await?;
+
+    let columns = db.columns(table_name).unwrap();
+    let column = columns.iter().find(|c| c.name == column).unwrap();
+
+    assert_eq!(column.default_expr.as_deref(), default_expr);
+
+    Ok(())
+}
+
+#[rstest(
+    file,
+    table_name,
+    column,
+    identity,
+    case("AWLT2005.mdf", "Address", "AddressID", Some((1, 1))),
+    case("AWLT2005.mdf", "Address", "City", None)
+)]
+#[async_std::test]
+async fn column_identity(
+    file: &str,
+    table_name: &str,
+    column: &str,
+    identity: Option<(i64, i64)>,
+) -> Result<(), Error> {
+    let db = MdfDatabase::open(format!("data/{}", file)).await?;
+
+    let columns = db.columns(table_name).unwrap();
+    let column = columns.iter().find(|c| c.name == column).unwrap();
+
+    assert_eq!(column.identity, identity);
+
+    Ok(())
+}
+
+#[cfg(feature = "arrow")]
+#[async_std::test]
+async fn export_arrow() -> Result<(), Error> {
+    use arrow::array::StringArray;
+    use arrow::datatypes::DataType;
+
+    let mut db = MdfDatabase::open("data/AWLT2005.mdf").await?;
+    let batch = db.export_arrow("Address").await?;
+
+    assert_eq!(batch.num_rows(), 450);
+
+    let schema = batch.schema();
+    let (index, field) = schema.column_with_name("AddressLine1").unwrap();
+    assert_eq!(field.data_type(), &DataType::Utf8);
+
+    let address_lines = batch
+        .column(index)
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    assert_eq!(address_lines.value(0), "8713 Yosemite Ct.");
+
+    Ok(())
+}
+
+#[cfg(feature = "parquet")]
+#[async_std::test]
+async fn export_parquet() -> Result<(), Error> {
+    use arrow::array::StringArray;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    let path = std::env::temp_dir().join("oxidized-mdf-ProductCategory.parquet");
+
+    let mut db = MdfDatabase::open("data/AWLT2005.mdf").await?;
+    db.export_parquet("ProductCategory", &path).await?;
+
+    let reader = ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(&path)?)
+        .unwrap()
+        .build()
+        .unwrap();
+    let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+
+    assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 41);
+
+    let names = batches[0]
+        .column_by_name("Name")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    assert_eq!(names.value(0), "Bikes");
+
+    std::fs::remove_file(path)?;
+
+    Ok(())
+}
+
+#[rstest(
+    file,
+    table_name,
+    chunk_size,
+    chunk_sizes,
+    case("AWLT2005.mdf", "ProductCategory", 10, vec![10, 10, 10, 10, 1]),
+    case("AWLT2005.mdf", "ProductCategory", 41, vec![41]),
+    case("AWLT2005.mdf", "ProductCategory", 100, vec![41])
+)]
+#[async_std::test]
+async fn rows_chunked(
+    file: &str,
+    table_name: &str,
+    chunk_size: usize,
+    chunk_sizes: Vec<usize>,
+) -> Result<(), Error> {
+    let mut db = MdfDatabase::open(format!("data/{}", file)).await?;
+
+    let mut chunks = db.rows_chunked(table_name, chunk_size).unwrap();
+    let mut sizes = Vec::new();
+    while let Some(chunk) = chunks.next().await {
+        sizes.push(chunk?.len());
+    }
+
+    assert_eq!(sizes, chunk_sizes);
+
+    Ok(())
+}
+
+#[rstest(
+    file,
+    table_name,
+    row_count,
+    case("AWLT2005.mdf", "ProductCategory", 41)
+)]
+#[async_std::test]
+async fn table_handle(file: &str, table_name: &str, row_count: usize) -> Result<(), Error> {
+    let mut db = MdfDatabase::open(format!("data/{}", file)).await?;
+
+    let table = db.table(table_name).unwrap();
+    assert_eq!(table.name(), table_name);
+    assert_eq!(
+        table.columns().len(),
+        db.column_names(table_name).unwrap().len()
+    );
+    assert_eq!(table.row_count(), row_count as i64);
+
+    let rows = table.rows(&mut db).unwrap().count().await;
+    assert_eq!(rows, row_count);
+
+    Ok(())
+}
+
+#[async_std::test]
+async fn write_table_csv() -> Result<(), Error> {
+    let mut db = MdfDatabase::open("data/AWLT2005.mdf").await?;
+
+    let mut csv = Vec::new();
+    db.write_table_csv("ProductCategory", &mut csv).await?;
+
+    let csv = String::from_utf8(csv).unwrap();
+    let mut lines = csv.lines();
+    assert_eq!(
+        lines.next(),
+        Some("ProductCategoryID,ParentProductCategoryID,Name,rowguid,ModifiedDate")
+    );
+    assert_eq!(lines.count(), 41);
+
+    Ok(())
+}
+
+#[async_std::test]
+async fn write_table_sql() -> Result<(), Error> {
+    let mut db = MdfDatabase::open("data/AWLT2005.mdf").await?;
+
+    let mut sql = Vec::new();
+    db.write_table_sql("ProductCategory", &mut sql).await?;
+
+    // ProductCategoryID is an identity column
+    let sql = String::from_utf8(sql).unwrap();
+    let lines = sql.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 43);
+    assert_eq!(lines[0], "SET IDENTITY_INSERT [ProductCategory] ON;");
+    assert!(lines[1].starts_with(
+        "INSERT INTO [ProductCategory] ([ProductCategoryID], [ParentProductCategoryID], [Name], [rowguid], [ModifiedDate]) VALUES (1, NULL, N'Bikes', '"
+    ));
+    assert_eq!(lines[42], "SET IDENTITY_INSERT [ProductCategory] OFF;");
+
+    Ok(())
+}
+
+#[async_std::test]
+async fn rows_as_json() -> Result<(), Error> {
+    let mut db = MdfDatabase::open("data/AWLT2005.mdf").await?;
+
+    let mut rows = db.rows_as_json("ProductCategory").unwrap();
+    let first_row = rows.next().await.unwrap()?;
+
+    assert!(first_row
+        .starts_with(r#"{"ProductCategoryID":1,"ParentProductCategoryID":null,"Name":"Bikes""#));
+
+    Ok(())
+}